@@ -229,7 +229,14 @@ impl FM {
     /// May fail if the terminal crashes
     /// May also fail if the thread running in [`crate::app::Refresher`] crashed
     pub fn quit(self) -> Result<()> {
-        let final_path = self.status.lock().current_tab_path_str().to_owned();
+        let final_path = {
+            let status = self.status.lock();
+            status
+                .internal_settings
+                .final_path
+                .clone()
+                .unwrap_or_else(|| status.current_tab_path_str())
+        };
 
         clear_tmp_files();
 
@@ -237,7 +244,8 @@ impl FM {
         drop(self.event_dispatcher);
         self.displayer.quit();
         self.refresher.quit();
-        let status = self.status.lock();
+        let mut status = self.status.lock();
+        status.session.save();
         status.previewer.quit();
         if status.internal_settings.clear_before_quit {
             Self::clear()?;
@@ -1,4 +1,6 @@
 mod inner {
+    use std::path::{Path, PathBuf};
+
     use anyhow::{Context, Result};
     use ratatui::{
         layout::{Alignment, Rect},
@@ -16,8 +18,8 @@ mod inner {
     };
     use crate::{
         common::{
-            PathShortener, UtfWidth, ACTION_LOG_PATH, HELP_FIRST_SENTENCE, HELP_SECOND_SENTENCE,
-            LAZYGIT, LOG_FIRST_SENTENCE, LOG_SECOND_SENTENCE, NCDU,
+            collapse_home, home_dir, PathShortener, UtfWidth, ACTION_LOG_PATH, HELP_FIRST_SENTENCE,
+            HELP_SECOND_SENTENCE, LAZYGIT, LOG_FIRST_SENTENCE, LOG_SECOND_SENTENCE, NCDU,
         },
         modes::SAME_WINDOW_TOKEN,
     };
@@ -180,7 +182,7 @@ mod inner {
         pub fn new(status: &Status, tab: &Tab) -> Result<Self> {
             let full_width = status.internal_settings.term_size().0;
             let canvas_width = status.canvas_width()?;
-            let left = Self::make_left(tab, canvas_width)?;
+            let left = Self::make_left(tab, canvas_width, status.session.tilde_path())?;
             let right = Self::make_right(tab, canvas_width)?;
 
             Ok(Self {
@@ -190,14 +192,17 @@ mod inner {
             })
         }
 
-        fn make_left(tab: &Tab, width: u16) -> Result<Vec<ClickableString>> {
+        fn make_left(tab: &Tab, width: u16, tilde_path: bool) -> Result<Vec<ClickableString>> {
             let mut left = 0;
-            let shorten_path = Self::elem_shorten_path(tab, left)?;
-            left += shorten_path.width();
+            let mut left_elems = vec![];
+            for crumb in Self::elems_breadcrumb(tab, left, tilde_path)? {
+                left += crumb.width();
+                left_elems.push(crumb);
+            }
 
-            let filename = Self::elem_filename(tab, width, left)?;
+            left_elems.push(Self::elem_filename(tab, width, left)?);
 
-            Ok(vec![shorten_path, filename])
+            Ok(left_elems)
         }
 
         fn make_right(tab: &Tab, width: u16) -> Result<Vec<ClickableString>> {
@@ -218,18 +223,67 @@ mod inner {
             Ok(right_elems)
         }
 
-        fn elem_shorten_path(tab: &Tab, left: u16) -> Result<ClickableString> {
-            Ok(ClickableString::new(
-                format!(
-                    " {}",
-                    PathShortener::path(&tab.directory.path)
-                        .context("Couldn't parse path")?
-                        .shorten()
-                ),
-                Align::Left,
-                ActionMap::Cd,
-                left,
-            ))
+        /// The current path, split into clickable ancestors.
+        /// Clicking an ancestor moves there and selects the directory we came from.
+        /// Clicking the current directory opens the cd menu.
+        fn elems_breadcrumb(
+            tab: &Tab,
+            mut left: u16,
+            tilde_path: bool,
+        ) -> Result<Vec<ClickableString>> {
+            let crumbs = Self::breadcrumb(&tab.directory.path, tilde_path)?;
+            let mut elems = vec![];
+            for (index, (text, _)) in crumbs.iter().enumerate() {
+                let action = match crumbs.get(index + 1) {
+                    Some((_, child)) => ActionMap::Reveal(child.to_string_lossy().into_owned()),
+                    None => ActionMap::Cd,
+                };
+                let elem = ClickableString::new(text.to_owned(), Align::Left, action, left);
+                left += elem.width();
+                elems.push(elem);
+            }
+            Ok(elems)
+        }
+
+        /// Pairs of displayed text and ancestor path, from the root (`/` or `~`) to the current directory.
+        /// The texts are shortened like the whole path would be.
+        fn breadcrumb(path: &Path, tilde_path: bool) -> Result<Vec<(String, PathBuf)>> {
+            let (root, root_text) = match home_dir() {
+                Some(home) if tilde_path && path.starts_with(&home) => (home, "~"),
+                _ => (PathBuf::from("/"), "/"),
+            };
+            let components: Vec<String> = path
+                .strip_prefix(&root)?
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            let shortened = PathShortener::path(path)
+                .context("Couldn't parse path")?
+                .with_home_collapsed(tilde_path)
+                .shorten();
+            let mut texts: Vec<String> = shortened
+                .strip_prefix(root_text)
+                .unwrap_or_default()
+                .split('/')
+                .filter(|text| !text.is_empty())
+                .map(|text| text.to_owned())
+                .collect();
+            if texts.len() != components.len() {
+                texts = components.clone();
+            }
+
+            let mut ancestor = root.clone();
+            let mut crumbs = vec![(format!(" {root_text}"), root)];
+            for (index, (component, text)) in components.iter().zip(texts).enumerate() {
+                ancestor.push(component);
+                let sep = if index == 0 && root_text == "/" {
+                    ""
+                } else {
+                    "/"
+                };
+                crumbs.push((format!("{sep}{text}"), ancestor.clone()));
+            }
+            Ok(crumbs)
         }
 
         fn elem_filename(tab: &Tab, width: u16, left: u16) -> Result<ClickableString> {
@@ -379,14 +433,17 @@ mod inner {
         }
 
         fn string_first_row_position(tab: &Tab) -> Result<String> {
-            let len: u16;
-            let index: u16;
+            let len: usize;
+            let index: usize;
             if tab.display_mode.is_tree() {
-                index = tab.tree.selected_node().context("no node")?.index() as u16 + 1;
-                len = tab.tree.len() as u16;
+                index = tab.tree.selected_node().context("no node")?.index() + 1;
+                len = tab.tree.len();
             } else {
-                index = tab.directory.index as u16 + 1;
-                len = tab.directory.len() as u16;
+                index = tab.directory.index + 1;
+                len = tab.directory.len();
+                if tab.directory.is_loading() {
+                    return Ok(format!(" {index} / {len} loading... "));
+                }
             }
             Ok(format!(" {index} / {len} "))
         }
@@ -479,7 +536,9 @@ mod inner {
         fn strings_left(status: &Status, tab: &Tab) -> Vec<(String, Align)> {
             match &tab.preview {
                 Preview::Text(text_content) => match text_content.kind {
-                    TextKind::CommandStdout => Self::make_colored_text(text_content),
+                    TextKind::CommandStdout | TextKind::CommandStream => {
+                        Self::make_colored_text(text_content)
+                    }
                     TextKind::Help => Self::make_help(),
                     TextKind::Log => Self::make_log(),
                     _ => Self::make_default_preview(status, tab),
@@ -530,15 +589,20 @@ mod inner {
         }
 
         fn pick_previewed_fileinfo(status: &Status) -> String {
-            if status.session.dual() && status.session.preview() {
+            let filepath = if status.session.dual() && status.session.preview() {
                 status.tabs[1].preview.filepath()
             } else {
                 status.current_tab().preview.filepath()
+            };
+            if status.session.tilde_path() {
+                collapse_home(&filepath).into_owned()
+            } else {
+                filepath
             }
         }
 
         fn make_default_preview(status: &Status, tab: &Tab) -> Vec<(String, Align)> {
-            vec![
+            let mut strings = vec![
                 (
                     format!(" Preview as {kind} ", kind = tab.preview.kind_display()),
                     Align::Left,
@@ -550,7 +614,15 @@ mod inner {
                     ),
                     Align::Left,
                 ),
-            ]
+            ];
+            if Self::is_preview_pinned(status) {
+                strings.push((" Pinned ".to_owned(), Align::Left));
+            }
+            strings
+        }
+
+        fn is_preview_pinned(status: &Status) -> bool {
+            status.pinned_preview.is_some() && status.session.dual() && status.session.preview()
         }
 
         /// Make a default preview header
@@ -8,10 +8,14 @@ use indicatif::InMemoryTerm;
 use ratatui::layout::Size;
 use sysinfo::Disks;
 
-use crate::common::{is_in_path, open_in_current_neovim, NVIM, SS};
+use crate::common::{
+    is_in_path, open_in_current_neovim, open_multiple_in_current_neovim, NVIM, SS,
+};
 use crate::event::FmEvents;
 use crate::io::{execute_and_output, open_command_in_window, Args, Extension, External, Opener};
-use crate::modes::{copy_move, extract_extension, Content, Flagged};
+use crate::modes::{
+    copy_move, extract_extension, human_size, Content, ConvertWorker, Flagged, RemovalWorker,
+};
 
 /// Internal settings of the status.
 ///
@@ -30,6 +34,8 @@ pub struct InternalSettings {
     /// True if the user issued a quit event (`Key::Char('q')` by default).
     /// It's used to exit the main loop before reseting the cursor.
     pub must_quit: bool,
+    /// Path printed when leaving, for shell integration. The current directory if `None`.
+    pub final_path: Option<String>,
     /// NVIM RPC server address
     pub nvim_server: String,
     /// The opener used by the application.
@@ -43,11 +49,17 @@ pub struct InternalSettings {
     pub disks: Disks,
     /// true if the application was launched inside a neovim terminal emulator
     pub inside_neovim: bool,
-    /// queue of pairs (sources, dest) to be copied.
+    /// queue of (sources, dest, targets) to be copied, targets being the final paths of conflicting files.
     /// it shouldn't be massive under normal usage so we can use a vector instead of an efficient queue data structure.
-    pub copy_file_queue: Vec<(Vec<PathBuf>, PathBuf)>,
+    pub copy_file_queue: Vec<(Vec<PathBuf>, PathBuf, HashMap<PathBuf, PathBuf>)>,
     /// internal progressbar used to display copy progress
     pub in_mem_progress: Option<InMemoryTerm>,
+    /// flagged files being deleted or trashed in a thread
+    pub removal: Option<RemovalWorker>,
+    /// flagged images being converted in a thread
+    pub conversion: Option<ConvertWorker>,
+    /// label, bytes written and total bytes of the file being split or joined in a thread
+    pub split_join: Option<(String, u64, u64)>,
     /// true if the current terminal is disabled
     is_disabled: bool,
     /// true if the terminal should be cleared before exit. It's set to true when we reuse the window to start a new shell.
@@ -59,10 +71,14 @@ impl InternalSettings {
         let args = Args::parse();
         let force_clear = false;
         let must_quit = false;
+        let final_path = None;
         let nvim_server = args.server.clone();
         let inside_neovim = args.neovim;
         let copy_file_queue = vec![];
         let in_mem_progress = None;
+        let removal = None;
+        let conversion = None;
+        let split_join = None;
         let width = size.width;
         let height = size.height;
         let is_disabled = false;
@@ -70,6 +86,7 @@ impl InternalSettings {
         Self {
             force_clear,
             must_quit,
+            final_path,
             nvim_server,
             opener,
             disks,
@@ -78,6 +95,9 @@ impl InternalSettings {
             inside_neovim,
             copy_file_queue,
             in_mem_progress,
+            removal,
+            conversion,
+            split_join,
             is_disabled,
             clear_before_quit,
         }
@@ -155,12 +175,13 @@ impl InternalSettings {
         fm_sender: Arc<Sender<FmEvents>>,
         width: u16,
     ) -> Result<()> {
-        let (sources, dest) = self.copy_file_queue[0].clone();
+        let (sources, dest, targets) = self.copy_file_queue[0].clone();
         let (_, height) = self.term_size();
         let in_mem = copy_move(
             crate::modes::CopyMove::Copy,
             sources,
             dest,
+            targets,
             width,
             height,
             fm_sender,
@@ -269,9 +290,7 @@ impl InternalSettings {
 
     fn open_multiple_in_neovim(&mut self, paths: &[PathBuf]) {
         self.update_nvim_listen_address();
-        for path in paths {
-            open_in_current_neovim(path, &self.nvim_server);
-        }
+        open_multiple_in_current_neovim(paths, &self.nvim_server);
     }
 
     /// Set the must quit flag to true.
@@ -281,6 +300,12 @@ impl InternalSettings {
         self.must_quit = true
     }
 
+    /// Set the must quit flag to true and print `final_path` instead of the current directory when leaving.
+    pub fn quit_with_final_path(&mut self, final_path: String) {
+        self.final_path = Some(final_path);
+        self.quit()
+    }
+
     pub fn format_copy_progress(&self) -> Option<String> {
         let Some(copy_progress) = &self.in_mem_progress else {
             return None;
@@ -296,4 +321,30 @@ impl InternalSettings {
             ))
         }
     }
+
+    /// Content of the progress bar of the running removal, if any.
+    pub fn format_removal_progress(&self) -> Option<String> {
+        self.removal.as_ref().map(|removal| removal.contents())
+    }
+
+    /// Content of the progress bar of the running image conversion, if any.
+    pub fn format_conversion_progress(&self) -> Option<String> {
+        self.conversion
+            .as_ref()
+            .map(|conversion| conversion.contents())
+    }
+
+    /// Progress of the running split or join, if any.
+    pub fn format_split_join_progress(&self) -> Option<String> {
+        let (label, done, total) = self.split_join.as_ref()?;
+        if *total == 0 {
+            return Some(label.to_owned());
+        }
+        Some(format!(
+            "{label} {percent}% - {done} / {total}",
+            percent = done * 100 / total,
+            done = human_size(*done).trim(),
+            total = human_size(*total).trim(),
+        ))
+    }
 }
@@ -32,13 +32,20 @@ impl Previewer {
     /// - if the message is a request, it will create the associate preview and send it back to the application.
     ///   The application should then ask the status to attach the preview. It's complicated but I couldn't find a simpler way to check
     ///   for the preview.
+    ///
+    /// Requests are coalesced : when many requests are queued (holding a navigation key for example),
+    /// only the last one is built. Stale previews aren't built at all.
     pub fn new(tx_preview: mpsc::Sender<(PathBuf, Preview, usize)>) -> Self {
         let (tx, rx) = mpsc::channel::<PreviewRequest>();
         thread::spawn(move || {
             while let Some(request) = rx.iter().next() {
-                match request {
+                match Self::last_request(request, &rx) {
                     PreviewRequest::Request((path, index)) => {
-                        if let Ok(preview) = PreviewBuilder::new(&path).build() {
+                        if let Ok(preview) = PreviewBuilder::new(&path)
+                            .with_directory_listing()
+                            .with_configured_previewers()
+                            .build()
+                        {
                             tx_preview.send((path, preview, index)).unwrap();
                         };
                     }
@@ -49,6 +56,21 @@ impl Previewer {
         Self { tx }
     }
 
+    /// Drain the queued requests and returns the most recent one.
+    /// A quit request is returned immediately.
+    fn last_request(
+        mut request: PreviewRequest,
+        rx: &mpsc::Receiver<PreviewRequest>,
+    ) -> PreviewRequest {
+        while let Ok(next_request) = rx.try_recv() {
+            if matches!(next_request, PreviewRequest::Quit) {
+                return PreviewRequest::Quit;
+            }
+            request = next_request;
+        }
+        request
+    }
+
     /// Sends a "quit" message to the previewer loop. It will break the loop, exiting the previewer.
     pub fn quit(&self) {
         crate::log_info!("stopping previewer loop");
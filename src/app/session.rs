@@ -1,9 +1,11 @@
 use std::fs::File;
+use std::time::Instant;
 
 use serde::Serialize;
 use serde_yml::{from_reader, to_writer, Error as YamlError, Value as YamlValue};
 
 use crate::common::{tilde, SESSION_PATH};
+use crate::config::session_save_delay;
 use crate::io::MIN_WIDTH_FOR_DUAL_PANE;
 use crate::log_info;
 
@@ -13,6 +15,8 @@ use crate::log_info;
 /// Since it's specific to a tab, it's not stored here.
 ///
 /// Reads its display values from a session file and updates them when modified.
+/// Modifications are coalesced : the file is written once no setting changed for `session_save_delay` milliseconds,
+/// or when the application quits.
 /// The file is stored at [`crate::common::SESSION_PATH`] which points to `~/.config/fm/session.yaml`.
 /// Unreachable or unreadable files are ignored.
 ///
@@ -20,6 +24,10 @@ use crate::log_info;
 /// - do we display one or two tabs ? Default to true.
 /// - do we display files metadata ? Default to true.
 /// - do we use to second pane to preview files ? Default to false.
+/// - do we group the files by extension ? Default to false.
+/// - do we display the home directory as `~` in paths ? Default to false.
+/// - does the second pane list the directory selected in the first one ? Default to false.
+/// - are the line numbers of text previews relative to the top line ? Default to false.
 #[derive(Debug, Serialize)]
 pub struct Session {
     /// do we display one or two tabs ?
@@ -28,9 +36,20 @@ pub struct Session {
     metadata: bool,
     /// use the second pane to preview
     preview: bool,
+    /// group the files by extension in directory mode
+    group_by_extension: bool,
+    /// display the home directory as `~` in the displayed paths
+    tilde_path: bool,
+    /// list the directory selected in the first pane in the second one
+    follow_selection: bool,
+    /// number the lines of text previews relatively to the top displayed line
+    relative_line_numbers: bool,
     /// session filepath
     #[serde(skip_serializing)]
     filepath: String,
+    /// last modification not written to the session file yet
+    #[serde(skip_serializing)]
+    dirty_since: Option<Instant>,
 }
 
 impl Default for Session {
@@ -39,7 +58,12 @@ impl Default for Session {
             dual: true,
             metadata: true,
             preview: false,
+            group_by_extension: false,
+            tilde_path: false,
+            follow_selection: false,
+            relative_line_numbers: false,
             filepath: tilde(SESSION_PATH).to_string(),
+            dirty_since: None,
         }
     }
 }
@@ -76,6 +100,22 @@ impl Session {
             YamlValue::Bool(value) => self.preview = value,
             _ => self.preview = false,
         }
+        match yaml["group_by_extension"] {
+            YamlValue::Bool(value) => self.group_by_extension = value,
+            _ => self.group_by_extension = false,
+        }
+        match yaml["tilde_path"] {
+            YamlValue::Bool(value) => self.tilde_path = value,
+            _ => self.tilde_path = false,
+        }
+        match yaml["follow_selection"] {
+            YamlValue::Bool(value) => self.follow_selection = value,
+            _ => self.follow_selection = false,
+        }
+        match yaml["relative_line_numbers"] {
+            YamlValue::Bool(value) => self.relative_line_numbers = value,
+            _ => self.relative_line_numbers = false,
+        }
         self
     }
 
@@ -98,6 +138,22 @@ impl Session {
         self.preview
     }
 
+    pub fn group_by_extension(&self) -> bool {
+        self.group_by_extension
+    }
+
+    pub fn tilde_path(&self) -> bool {
+        self.tilde_path
+    }
+
+    pub fn follow_selection(&self) -> bool {
+        self.follow_selection
+    }
+
+    pub fn relative_line_numbers(&self) -> bool {
+        self.relative_line_numbers
+    }
+
     /// True iff the terminal is wide enough to display two panes
     pub fn display_wide_enough(width: u16) -> bool {
         width >= MIN_WIDTH_FOR_DUAL_PANE
@@ -113,22 +169,64 @@ impl Session {
 
     pub fn set_dual(&mut self, dual: bool) {
         self.dual = dual;
-        self.update_yaml_file();
+        self.mark_dirty();
     }
 
     pub fn toggle_dual(&mut self) {
         self.dual = !self.dual;
-        self.update_yaml_file();
+        self.mark_dirty();
     }
 
     pub fn toggle_metadata(&mut self) {
         self.metadata = !self.metadata;
-        self.update_yaml_file();
+        self.mark_dirty();
     }
 
     pub fn toggle_preview(&mut self) {
         self.preview = !self.preview;
-        self.update_yaml_file();
+        self.mark_dirty();
+    }
+
+    pub fn toggle_group_by_extension(&mut self) {
+        self.group_by_extension = !self.group_by_extension;
+        self.mark_dirty();
+    }
+
+    pub fn toggle_tilde_path(&mut self) {
+        self.tilde_path = !self.tilde_path;
+        self.mark_dirty();
+    }
+
+    pub fn toggle_follow_selection(&mut self) {
+        self.follow_selection = !self.follow_selection;
+        self.mark_dirty();
+    }
+
+    pub fn toggle_relative_line_numbers(&mut self) {
+        self.relative_line_numbers = !self.relative_line_numbers;
+        self.mark_dirty();
+    }
+
+    /// Records a modification. The session file will be written later.
+    fn mark_dirty(&mut self) {
+        self.dirty_since = Some(Instant::now());
+    }
+
+    /// Writes the session file if it was modified and no setting changed since `session_save_delay`.
+    pub fn save_if_idle(&mut self) {
+        if self
+            .dirty_since
+            .is_some_and(|since| since.elapsed() >= session_save_delay())
+        {
+            self.save();
+        }
+    }
+
+    /// Writes the session file if it was modified since its last writing.
+    pub fn save(&mut self) {
+        if self.dirty_since.take().is_some() {
+            self.update_yaml_file();
+        }
     }
 
     /// Writes itself to the session file.
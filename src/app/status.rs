@@ -1,13 +1,19 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::process::Child;
 use std::sync::{
     mpsc::{self, Sender, TryRecvError},
     Arc,
 };
+use std::thread;
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, Context, Result};
 use clap::Parser;
 use crossterm::event::{Event, KeyEvent};
 use opendal::EntryMode;
+use parking_lot::Mutex;
 use ratatui::layout::Size;
 use sysinfo::{Disk, Disks};
 
@@ -16,21 +22,31 @@ use crate::app::{
 };
 use crate::common::{
     current_username, disk_space, disk_used_by_path, filename_from_path, is_in_path,
-    is_sudo_command, path_to_string, row_to_window_index,
+    is_sudo_command, path_to_string, recursive_size, row_to_window_index, tilde,
+};
+use crate::config::{
+    compare_recursive, from_keyname, grep_flag_recursive, is_never_previewed, notes_filename,
+    on_select_command, Bindings, START_FOLDER,
 };
-use crate::config::{from_keyname, Bindings, START_FOLDER};
 use crate::event::FmEvents;
 use crate::io::{
-    build_tokio_greper, execute_and_capture_output, execute_sudo_command_with_password,
-    execute_without_output, get_cloud_token_names, google_drive, reset_sudo_faillock, Args,
-    Internal, Kind, Opener, MIN_WIDTH_FOR_DUAL_PANE,
+    build_tokio_files_greper, build_tokio_greper, execute_and_capture_output,
+    execute_and_stream_output, execute_sudo_command_with_password, execute_without_output,
+    files_with_matches, get_cloud_token_names, google_drive, join_parts, parse_size,
+    reset_sudo_faillock, run_on_select_command, script_command, split_file, touch,
+    wallpaper_setter, Args, GitFileStatus, InputHistory, Internal, Kind, Opener, TerminalPane,
+    MIN_WIDTH_FOR_DUAL_PANE,
 };
 use crate::modes::{
-    copy_move, parse_line_output, regex_flagger, shell_command_parser, BlockDeviceAction, Content,
-    ContentWindow, CopyMove, Direction as FuzzyDirection, Display, FileInfo, FileKind, FilterKind,
-    FuzzyFinder, FuzzyKind, InputCompleted, InputSimple, IsoDevice, Menu, MenuHolder,
-    MountCommands, MountRepr, Navigate, NeedConfirmation, PasswordKind, PasswordUsage, Permissions,
-    PickerCaller, Preview, PreviewBuilder, Search, Selectable, Users, SAME_WINDOW_TOKEN,
+    copy_as, copy_move, extract_entry_to_tmp, glob_flagger, human_size, parse_line_output,
+    regex_flagger, remove_xattr, replace_by, set_xattr, shell_command_parser, BlockDeviceAction,
+    CaseTransform, Comparison, ConflictChoice, Conflicts, Content, ContentWindow, ConvertWorker,
+    CopyMove, Direction as FuzzyDirection, Display, ExtensionKind, FileInfo, FileKind, FilterKind,
+    FuzzyFinder, FuzzyKind, Guarded, InputCompleted, InputSimple, IsoDevice, Menu, MenuHolder,
+    MoreInfos, MountCommands, MountRepr, Navigate, NeedConfirmation, NumberedSequence,
+    PasswordKind, PasswordUsage, Permissions, PickerCaller, Preview, PreviewBuilder,
+    ProtectedGuard, Removable, Removal, RemovalWorker, Search, Selectable, TextKind, Users,
+    SAME_WINDOW_TOKEN,
 };
 use crate::{log_info, log_line};
 
@@ -131,6 +147,20 @@ pub struct Status {
     pub previewer: Previewer,
     /// Preview manager
     pub thumbnail_manager: Option<ThumbnailManager>,
+    /// Shell command whose output is streamed into a preview: index of the tab and child process.
+    streamed_command: Option<(usize, Arc<Mutex<Child>>)>,
+    /// Shell running in a pane at the bottom of the screen
+    pub terminal_pane: Option<TerminalPane>,
+    /// File previewed in the second pane whatever the selection
+    pub pinned_preview: Option<PathBuf>,
+    /// Directory selected in the first pane and when it was selected, waiting to be listed in the second pane.
+    followed_selection: Option<(PathBuf, Instant)>,
+    /// File selected and when it was selected, waiting to be sent to the `on_select_command`.
+    pending_selection: Option<(PathBuf, Instant)>,
+    /// Last file sent to the `on_select_command`.
+    last_sent_selection: Option<PathBuf>,
+    /// Selected file and its preview, displayed over the panes until the next key press.
+    quick_look: Option<(PathBuf, Preview)>,
 }
 
 impl Status {
@@ -163,13 +193,24 @@ impl Status {
         let users_right = users_left.clone();
 
         let height = size.height as usize;
-        let tabs = [
-            Tab::new(&args, height, users_left)?,
-            Tab::new(&args, height, users_right)?,
+        let mut tabs = [
+            Tab::new(&args, height, users_left, fm_sender.clone())?,
+            Tab::new(&args, height, users_right, fm_sender.clone())?,
         ];
+        if session.group_by_extension() {
+            tabs.iter_mut()
+                .for_each(|tab| tab.set_group_by_extension(true));
+        }
         let (previewer_sender, preview_receiver) = mpsc::channel();
         let previewer = Previewer::new(previewer_sender);
         let thumbnail_manager = None;
+        let streamed_command = None;
+        let terminal_pane = None;
+        let pinned_preview = None;
+        let followed_selection = None;
+        let pending_selection = None;
+        let last_sent_selection = None;
+        let quick_look = None;
         Ok(Self {
             tabs,
             index,
@@ -182,6 +223,13 @@ impl Status {
             preview_receiver,
             previewer,
             thumbnail_manager,
+            streamed_command,
+            terminal_pane,
+            pinned_preview,
+            followed_selection,
+            pending_selection,
+            last_sent_selection,
+            quick_look,
         })
     }
 
@@ -234,6 +282,37 @@ impl Status {
         self.focus_follow_index();
     }
 
+    /// Open the selected directory in the other tab and select it. The current tab is left unchanged.
+    /// The dual pane is displayed if needed and the second pane stops previewing or following the selection.
+    /// Does nothing if the selected file isn't a directory.
+    pub fn enter_in_other_tab(&mut self) -> Result<()> {
+        let Ok(fileinfo) = self.current_tab().current_file() else {
+            return Ok(());
+        };
+        if !fileinfo.is_dir() {
+            return Ok(());
+        }
+        if !Session::display_wide_enough(self.term_width()) {
+            log_line!("The terminal is too narrow to display two panes");
+            return Ok(());
+        }
+        if !self.session.dual() {
+            self.session.toggle_dual();
+        }
+        if self.session.preview() {
+            self.session.toggle_preview();
+        }
+        if self.session.follow_selection() {
+            self.session.toggle_follow_selection();
+        }
+        let other = 1 - self.index;
+        self.set_menu_mode_no_refresh(other, Menu::Nothing)?;
+        self.tabs[other].display_mode = Display::Directory;
+        self.tabs[other].cd(&fileinfo.path)?;
+        self.next();
+        self.refresh_status()
+    }
+
     /// Select the left or right tab depending on where the user clicked.
     pub fn select_tab_from_col(&mut self, col: u16) -> Result<()> {
         if self.session.dual() {
@@ -296,10 +375,16 @@ impl Status {
             Window::Header => self.header_action(col, binds),
             Window::Files => {
                 if self.has_clicked_on_second_pane_preview() {
-                    if let Preview::Tree(tree) = &self.tabs[1].preview {
-                        let index = row_to_window_index(row) + self.tabs[1].window.top;
-                        let path = &tree.path_from_index(index)?;
-                        self.tabs[0].cd_to_file(path)?;
+                    let index = row_to_window_index(row) + self.tabs[1].window.top;
+                    let path = match &self.tabs[1].preview {
+                        Preview::Tree(tree) => Some(tree.path_from_index(index)?),
+                        Preview::Directory(listing) => {
+                            listing.path_from_index(index).map(|path| path.to_owned())
+                        }
+                        _ => None,
+                    };
+                    if let Some(path) = path {
+                        self.tabs[0].cd_to_file(&path)?;
                         self.index = 0;
                         self.focus = Focus::LeftFile;
                     }
@@ -538,6 +623,7 @@ impl Status {
     /// up or down.
     pub fn resize(&mut self, width: u16, height: u16) -> Result<()> {
         let couldnt_dual_but_want = self.couldnt_dual_but_want();
+        let height = self.resize_terminal_pane(width, height)?;
         self.internal_settings.update_size(width, height);
         if couldnt_dual_but_want {
             self.set_dual_pane_if_wide_enough(width)?;
@@ -546,6 +632,17 @@ impl Status {
         self.refresh_status()
     }
 
+    /// Resize the terminal pane if it's opened.
+    /// Returns the height left for the files and menus.
+    fn resize_terminal_pane(&mut self, width: u16, height: u16) -> Result<u16> {
+        let Some(terminal_pane) = &mut self.terminal_pane else {
+            return Ok(height);
+        };
+        let pane_height = TerminalPane::height(height);
+        terminal_pane.resize(pane_height.saturating_sub(2), width.saturating_sub(2))?;
+        Ok(height - pane_height)
+    }
+
     fn couldnt_dual_but_want(&self) -> bool {
         self.internal_settings.width < MIN_WIDTH_FOR_DUAL_PANE && self.session.dual()
     }
@@ -575,13 +672,17 @@ impl Status {
     }
 
     /// Check if the second pane should display a preview and force it.
+    /// If the second pane follows the selection, the selected directory will be listed there.
     pub fn update_second_pane_for_preview(&mut self) -> Result<()> {
+        self.remember_selection();
         if self.are_settings_requiring_dualpane_preview() {
             if self.can_display_dualpane_preview() {
                 self.set_second_pane_for_preview()?;
             } else {
                 self.tabs[1].preview = PreviewBuilder::empty();
             }
+        } else if self.are_settings_requiring_follow_selection() {
+            self.follow_selection();
         }
         Ok(())
     }
@@ -590,18 +691,147 @@ impl Status {
         self.index == 0 && self.session.dual() && self.session.preview()
     }
 
+    fn are_settings_requiring_follow_selection(&self) -> bool {
+        self.index == 0
+            && self.session.dual()
+            && self.session.follow_selection()
+            && self.can_display_dualpane_preview()
+    }
+
+    /// Time a directory should stay selected before being listed in the second pane.
+    /// It avoids building a listing for every directory when scrolling quickly.
+    const FOLLOW_SELECTION_DELAY: Duration = Duration::from_millis(150);
+
+    /// Remember the selected directory of the first pane. It will be listed in the second pane
+    /// by [`Status::check_followed_selection`] if it's still selected after a short delay.
+    /// Other files are ignored and the last directory stays listed.
+    fn follow_selection(&mut self) {
+        let Ok(fileinfo) = self.tabs[0].current_file() else {
+            return;
+        };
+        if !fileinfo.is_dir() || self.tabs[1].preview.filepath() == fileinfo.path.to_string_lossy()
+        {
+            self.followed_selection = None;
+            return;
+        }
+        let path = fileinfo.path.to_path_buf();
+        if !matches!(&self.followed_selection, Some((followed, _)) if followed == &path) {
+            self.followed_selection = Some((path, Instant::now()));
+        }
+    }
+
+    /// List the followed directory in the second pane if it has been selected for long enough.
+    pub fn check_followed_selection(&mut self) -> Result<()> {
+        let Some((path, since)) = &self.followed_selection else {
+            return Ok(());
+        };
+        if since.elapsed() < Self::FOLLOW_SELECTION_DELAY {
+            return Ok(());
+        }
+        let path = path.to_owned();
+        self.followed_selection = None;
+        if !self.are_settings_requiring_follow_selection() {
+            return Ok(());
+        }
+        self.tabs[1].set_display_mode(Display::Preview);
+        self.tabs[1].menu_mode = Menu::Nothing;
+        self.tabs[1].preview = PreviewBuilder::new(&path)
+            .with_directory_listing()
+            .build()?;
+        self.tabs[1].window.reset(self.tabs[1].preview.len());
+        Ok(())
+    }
+
+    /// Time a file should stay selected before the `on_select_command` is run.
+    /// Scrolling quickly doesn't spawn a process for every line.
+    const ON_SELECT_COMMAND_DELAY: Duration = Duration::from_millis(300);
+
+    /// Remember the selected file of the current tab in directory or tree mode.
+    /// The `on_select_command` will be run by [`Status::check_pending_selection`]
+    /// if it's still selected after a short delay.
+    fn remember_selection(&mut self) {
+        if on_select_command().is_empty() {
+            return;
+        }
+        let tab = self.current_tab();
+        if !matches!(tab.display_mode, Display::Directory | Display::Tree) {
+            return;
+        }
+        let Ok(fileinfo) = tab.current_file() else {
+            return;
+        };
+        let path = fileinfo.path.to_path_buf();
+        if self.last_sent_selection.as_ref() == Some(&path) {
+            self.pending_selection = None;
+            return;
+        }
+        if !matches!(&self.pending_selection, Some((pending, _)) if pending == &path) {
+            self.pending_selection = Some((path, Instant::now()));
+        }
+    }
+
+    /// Run the `on_select_command` with the pending selection if it has been selected for long enough.
+    pub fn check_pending_selection(&mut self) {
+        let Some((_, since)) = &self.pending_selection else {
+            return;
+        };
+        if since.elapsed() < Self::ON_SELECT_COMMAND_DELAY {
+            return;
+        }
+        let Some((path, _)) = self.pending_selection.take() else {
+            return;
+        };
+        if run_on_select_command(&path) {
+            self.last_sent_selection = Some(path);
+        }
+    }
+
+    /// Toggle the second pane following the selected directory of the first one.
+    /// It requires the dual pane and replaces the preview of the selected file.
+    /// When disabled, the second pane displays its own files again.
+    pub fn toggle_follow_selection(&mut self) -> Result<()> {
+        self.session.toggle_follow_selection();
+        self.followed_selection = None;
+        if self.session.follow_selection() {
+            if !self.session.dual() {
+                self.session.toggle_dual();
+            }
+            if self.session.preview() {
+                self.session.toggle_preview();
+            }
+            log_line!("The second pane follows the selection");
+            self.update_second_pane_for_preview()
+        } else {
+            log_line!("The second pane doesn't follow the selection anymore");
+            self.set_menu_mode(1, Menu::Nothing)?;
+            self.tabs[1].display_mode = Display::Directory;
+            self.tabs[1].refresh_view()
+        }
+    }
+
     fn can_display_dualpane_preview(&self) -> bool {
         Session::display_wide_enough(self.term_width())
     }
 
     /// Force preview the selected file of the first pane in the second pane.
+    /// If a file is pinned, it's previewed instead, once.
     /// Doesn't check if it has do.
     fn set_second_pane_for_preview(&mut self) -> Result<()> {
         self.tabs[1].set_display_mode(Display::Preview);
         self.tabs[1].menu_mode = Menu::Nothing;
+        if let Some(pinned) = &self.pinned_preview {
+            if self.tabs[1].preview.is_empty() {
+                self.previewer.build(pinned.to_owned(), 1)?;
+            }
+            return Ok(());
+        }
         let Some(fileinfo) = self.get_correct_fileinfo_for_preview() else {
             return Ok(());
         };
+        if is_never_previewed(&fileinfo.path) {
+            self.tabs[1].preview = PreviewBuilder::empty();
+            return Ok(());
+        }
         log_info!("sending preview request");
         self.previewer.build(fileinfo.path.to_path_buf(), 1)?;
         // self.preview_manager.enqueue(&fileinfo.path);
@@ -609,6 +839,44 @@ impl Status {
         Ok(())
     }
 
+    /// Display the preview of the selected file over the panes, or hide it if it's displayed.
+    /// The tab isn't modified.
+    pub fn toggle_quick_look(&mut self) -> Result<()> {
+        if self.quick_look.take().is_some() {
+            return Ok(());
+        }
+        let path = self.current_tab().current_file()?.path.to_path_buf();
+        let preview = PreviewBuilder::new(&path).build()?;
+        self.quick_look = Some((path, preview));
+        Ok(())
+    }
+
+    /// Hide the quick look window. Returns true if it was displayed.
+    pub fn close_quick_look(&mut self) -> bool {
+        self.quick_look.take().is_some()
+    }
+
+    /// The file displayed in the quick look window and its preview, if any.
+    pub fn quick_look(&self) -> Option<(&Path, &Preview)> {
+        self.quick_look
+            .as_ref()
+            .map(|(path, preview)| (path.as_path(), preview))
+    }
+
+    /// Pin the selected file of the first pane, previewing it in the second pane while browsing.
+    /// If a file is already pinned, it's unpinned and the second pane follows the selection again.
+    pub fn toggle_pinned_preview(&mut self) -> Result<()> {
+        if self.pinned_preview.take().is_some() {
+            log_line!("Preview unpinned");
+        } else {
+            let path = self.tabs[0].current_file()?.path.to_path_buf();
+            log_line!("Preview pinned to {path}", path = path.display());
+            self.pinned_preview = Some(path);
+        }
+        self.tabs[1].preview = PreviewBuilder::empty();
+        self.update_second_pane_for_preview()
+    }
+
     /// Build all the video thumbnails of a directory
     /// Build the the thumbnail manager if it hasn't been initialised yet. If there's still files in the queue, they're cleared first.
     pub fn thumbnail_directory_video(&mut self) {
@@ -663,7 +931,8 @@ impl Status {
     /// It may happen if the user navigates quickly with "heavy" previews (movies, large pdf, office documents etc.).
     fn attach_preview(&mut self, path: PathBuf, preview: Preview, index: usize) -> Result<()> {
         let compared_index = self.pick_correct_tab_from(index)?;
-        if !self.preview_has_correct_path(compared_index, path.as_path())? {
+        let is_pinned = index == 1 && self.pinned_preview.as_ref() == Some(&path);
+        if !is_pinned && !self.preview_has_correct_path(compared_index, path.as_path())? {
             return Ok(());
         }
         self.tabs[index].preview = preview;
@@ -799,6 +1068,21 @@ impl Status {
         }
     }
 
+    /// Set the modification time of the flagged files, or the selected one if nothing is flagged, to now.
+    /// Files which don't exist anymore are created empty.
+    pub fn touch_flagged(&mut self) -> Result<()> {
+        let paths = self.flagged_or_selected();
+        let mut touched = 0;
+        for path in paths.iter() {
+            match touch(path) {
+                Ok(()) => touched += 1,
+                Err(error) => log_info!("Couldn't touch {path}: {error}", path = path.display()),
+            }
+        }
+        log_line!("Touched {touched} of {len} files", len = paths.len());
+        self.refresh_tabs()
+    }
+
     fn flagged_or_selected_relative_to(&self, here: &Path) -> Vec<PathBuf> {
         self.flagged_or_selected()
             .iter()
@@ -817,6 +1101,154 @@ impl Status {
         self.menu.flagged.in_dir(&self.current_tab().directory.path)
     }
 
+    /// Flagged files of the current directory, in the order they're displayed.
+    /// Files flagged in its subdirectories are ignored.
+    pub fn flagged_in_display_order(&self) -> Vec<PathBuf> {
+        self.current_tab()
+            .directory
+            .content
+            .iter()
+            .filter(|file| self.menu.flagged.contains(&file.path))
+            .map(|file| file.path.to_path_buf())
+            .collect()
+    }
+
+    /// Compute the total size of the flagged files in a thread, directories are walked recursively.
+    /// The result is sent back with [`FmEvents::FlaggedTotalSize`].
+    pub fn flagged_total_size(&self) {
+        if self.menu.flagged.is_empty() {
+            log_line!("No flagged file");
+            return;
+        }
+        let flagged = self.menu.flagged.content.to_owned();
+        log_line!(
+            "Computing the size of {len} flagged files...",
+            len = flagged.len()
+        );
+        let fm_sender = self.fm_sender.clone();
+        thread::spawn(move || {
+            let size = flagged.iter().map(|path| recursive_size(path)).sum();
+            fm_sender
+                .send(FmEvents::FlaggedTotalSize(flagged, size))
+                .unwrap_or_default();
+        });
+    }
+
+    /// Compute the size of every child of the current directory in a thread.
+    /// The preview displays the progress and, once done, the children sorted by size.
+    pub fn disk_usage(&mut self) -> Result<()> {
+        let path = self.current_tab().directory_of_selected()?.to_owned();
+        let children: Vec<PathBuf> = std::fs::read_dir(&path)?
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .collect();
+        let total = children.len();
+        self.current_tab_mut().set_display_mode(Display::Preview);
+        self.current_tab_mut().preview = PreviewBuilder::disk_usage(&path, total);
+        let len = self.current_tab().preview.len();
+        self.current_tab_mut().window.reset(len);
+        let fm_sender = self.fm_sender.clone();
+        thread::spawn(move || {
+            let mut sizes = Vec::with_capacity(total);
+            for (done, child) in children.into_iter().enumerate() {
+                let current = filename_from_path(&child).unwrap_or_default().to_owned();
+                fm_sender
+                    .send(FmEvents::DiskUsageProgress(
+                        path.clone(),
+                        done,
+                        total,
+                        current,
+                    ))
+                    .unwrap_or_default();
+                let size = recursive_size(&child);
+                sizes.push((child, size));
+            }
+            fm_sender
+                .send(FmEvents::DiskUsageComputed(path, sizes))
+                .unwrap_or_default();
+        });
+        Ok(())
+    }
+
+    /// Append files read in the background to the tab whose directory is loaded with this id.
+    pub fn extend_directory(&mut self, id: u64, files: Vec<FileInfo>) {
+        if let Some(tab) = self
+            .tabs
+            .iter_mut()
+            .find(|tab| tab.directory.is_loaded_by(id))
+        {
+            tab.extend_directory(files);
+        }
+    }
+
+    /// Every file of the directory loaded with this id was read, sort them.
+    pub fn finish_directory_loading(&mut self, id: u64) {
+        if let Some(tab) = self
+            .tabs
+            .iter_mut()
+            .find(|tab| tab.directory.is_loaded_by(id))
+        {
+            tab.finish_directory_loading();
+        }
+    }
+
+    /// Set the git status of the tabs displaying this directory.
+    pub fn set_git_statuses(
+        &mut self,
+        path: &Path,
+        statuses: Option<HashMap<PathBuf, GitFileStatus>>,
+    ) {
+        for tab in self.tabs.iter_mut() {
+            tab.directory.set_git_statuses(path, statuses.clone());
+        }
+    }
+
+    /// Display the progress of the disk usage computation if its preview is still displayed.
+    pub fn set_disk_usage_progress(
+        &mut self,
+        path: &Path,
+        done: usize,
+        total: usize,
+        current: &str,
+    ) {
+        for tab in self.tabs.iter_mut() {
+            if let Preview::Text(text) = &mut tab.preview {
+                if text.is_disk_usage_of(path) {
+                    text.set_disk_usage_progress(done, total, current);
+                }
+            }
+        }
+    }
+
+    /// Display the sizes of the children if the disk usage preview is still displayed.
+    pub fn set_disk_usage(&mut self, path: &Path, sizes: Vec<(PathBuf, u64)>) {
+        let total = sizes.iter().map(|(_, size)| size).sum();
+        log_line!(
+            "Disk usage of {path}: {total}",
+            path = path.display(),
+            total = human_size(total).trim()
+        );
+        for tab in self.tabs.iter_mut() {
+            if let Preview::Text(text) = &mut tab.preview {
+                if text.is_disk_usage_of(path) {
+                    text.set_disk_usage(sizes);
+                    let len = tab.preview.len();
+                    tab.window.reset(len);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Display the total size of the flagged files and keep it for the flagged menu.
+    pub fn set_flagged_total_size(&mut self, flagged: Vec<PathBuf>, size: u64) {
+        log_line!(
+            "Flagged files: {size} in {len} files",
+            size = human_size(size).trim(),
+            len = flagged.len()
+        );
+        self.menu.flagged.set_total_size(flagged, size);
+    }
+
     /// Flag all files in the current directory or current tree.
     pub fn flag_all(&mut self) {
         match self.current_tab().display_mode {
@@ -835,6 +1267,59 @@ impl Status {
         }
     }
 
+    /// Display the next allowed number of bytes per line in the previews of binary files.
+    pub fn cycle_binary_bytes_per_row(&mut self) {
+        let mut cycled = false;
+        for tab in self.tabs.iter_mut() {
+            cycled |= tab.cycle_binary_bytes_per_row();
+        }
+        if !cycled {
+            log_line!("No binary file previewed");
+        }
+    }
+
+    /// Flag the files of the current directory modified before (`Ordering::Less`) or after (`Ordering::Greater`)
+    /// the selected file. Files whose modification time can't be read are skipped.
+    /// Only works in directory display.
+    pub fn flag_by_modification(&mut self, ordering: Ordering) {
+        if !matches!(self.current_tab().display_mode, Display::Directory) {
+            return;
+        }
+        let Ok(selected) = self.current_tab().current_file() else {
+            return;
+        };
+        let Ok(selected_time) = selected
+            .metadata()
+            .and_then(|metadata| Ok(metadata.modified()?))
+        else {
+            return;
+        };
+        let mut count = 0;
+        self.tabs[self.index]
+            .directory
+            .content
+            .iter()
+            .filter(|file| file.filename.as_ref() != "." && file.filename.as_ref() != "..")
+            .filter(|file| {
+                file.metadata()
+                    .and_then(|metadata| Ok(metadata.modified()?))
+                    .is_ok_and(|time| time.cmp(&selected_time) == ordering)
+            })
+            .for_each(|file| {
+                self.menu.flagged.push(file.path.to_path_buf());
+                count += 1;
+            });
+        let when = if ordering == Ordering::Less {
+            "before"
+        } else {
+            "after"
+        };
+        log_line!(
+            "Flagged {count} files modified {when} {filename}",
+            filename = selected.filename
+        );
+    }
+
     /// Reverse every flag in _current_ directory. Flagged files in other
     /// directory aren't affected.
     pub fn reverse_flags(&mut self) {
@@ -847,6 +1332,94 @@ impl Status {
         }
     }
 
+    /// Enter or leave the visual mode in directory and tree display.
+    /// The selected file becomes the anchor of the flagged range.
+    pub fn toggle_visual(&mut self) {
+        let tab = &mut self.tabs[self.index];
+        if tab.visual.is_active() {
+            tab.visual.leave();
+            log_line!("Visual mode off");
+            return;
+        }
+        let anchor = match tab.display_mode {
+            Display::Directory => match tab.directory.selected() {
+                Some(file) => file.path.to_path_buf(),
+                None => return,
+            },
+            Display::Tree => tab.tree.selected_path().to_path_buf(),
+            Display::Preview | Display::Fuzzy => return,
+        };
+        tab.visual.enter(anchor);
+        log_line!("Visual mode on");
+        self.update_visual_flags();
+    }
+
+    /// Select the file under the mouse while it's dragged and flag the files between it and the file where the drag started.
+    /// The first drag event enters the visual mode from the selected file, which was clicked when the button was pressed.
+    /// Dragging out of the files of the current tab does nothing.
+    pub fn mouse_drag(&mut self, row: u16) -> Result<()> {
+        if !self.focus.is_file()
+            || !matches!(self.window_from_row(row, self.term_size().1), Window::Files)
+        {
+            return Ok(());
+        }
+        let tab = &mut self.tabs[self.index];
+        if !tab.visual.is_active() {
+            let anchor = match tab.display_mode {
+                Display::Directory => match tab.directory.selected() {
+                    Some(file) => file.path.to_path_buf(),
+                    None => return Ok(()),
+                },
+                Display::Tree => tab.tree.selected_path().to_path_buf(),
+                Display::Preview | Display::Fuzzy => return Ok(()),
+            };
+            tab.visual.enter_from_mouse(anchor);
+        }
+        self.tab_select_row(row)?;
+        self.update_visual_flags();
+        Ok(())
+    }
+
+    /// Leave the visual mode entered by dragging the mouse. The files stay flagged.
+    pub fn mouse_release(&mut self) -> Result<()> {
+        let tab = &mut self.tabs[self.index];
+        if tab.visual.is_from_mouse() {
+            tab.visual.leave();
+            self.update_second_pane_for_preview()?;
+        }
+        Ok(())
+    }
+
+    /// Flag the visible files between the visual anchor and the selected file.
+    /// Does nothing if the visual mode isn't active.
+    pub fn update_visual_flags(&mut self) {
+        let tab = &mut self.tabs[self.index];
+        if !tab.visual.is_active() {
+            return;
+        }
+        let flagged = &mut self.menu.flagged;
+        match tab.display_mode {
+            Display::Directory => {
+                let visible: Vec<&Path> = tab
+                    .directory
+                    .content
+                    .iter()
+                    .map(|file| file.path.as_ref())
+                    .collect();
+                tab.visual
+                    .update(&visible, tab.directory.index, false, flagged);
+            }
+            Display::Tree => {
+                let displayable = tab.tree.displayable();
+                let visible: Vec<&Path> =
+                    displayable.lines().iter().map(|line| line.path()).collect();
+                tab.visual
+                    .update(&visible, displayable.index(), true, flagged);
+            }
+            Display::Preview | Display::Fuzzy => tab.visual.leave(),
+        }
+    }
+
     /// Flag the selected file if any
     pub fn toggle_flag_for_selected(&mut self) {
         let Ok(file) = self.current_tab().current_file() else {
@@ -890,80 +1463,337 @@ impl Status {
         self.update_second_pane_for_preview()
     }
 
+    /// Move to the directory where the selected symlink really points and select its target.
+    /// The link is fully resolved, following every intermediate link.
+    /// Broken links are logged and nothing moves.
+    pub fn follow_symlink_to_target(&mut self) -> Result<()> {
+        let fileinfo = self.current_tab().current_file()?;
+        if !matches!(fileinfo.file_kind, FileKind::SymbolicLink(_)) {
+            log_line!("{filename} isn't a symlink", filename = fileinfo.filename);
+            return Ok(());
+        }
+        let target = match std::fs::canonicalize(&fileinfo.path) {
+            Ok(target) => target,
+            Err(error) => {
+                log_line!(
+                    "Broken link {path}: {error}",
+                    path = fileinfo.path.display()
+                );
+                return Ok(());
+            }
+        };
+        self.current_tab_mut().jump(target)?;
+        self.update_second_pane_for_preview()
+    }
+
+    /// Move to the real path of the current directory, where every symlink of the path is resolved.
+    /// The selected file stays selected. Nothing moves if the path is already real.
+    pub fn cd_to_real_path(&mut self) -> Result<()> {
+        let path = self.current_tab().directory.path.to_path_buf();
+        let real_path = match std::fs::canonicalize(&path) {
+            Ok(real_path) => real_path,
+            Err(error) => {
+                log_line!("Couldn't resolve {path}: {error}", path = path.display());
+                return Ok(());
+            }
+        };
+        if real_path == path {
+            log_line!("{path} is already a real path", path = path.display());
+            return Ok(());
+        }
+        let selected = self
+            .current_tab()
+            .current_file()
+            .ok()
+            .and_then(|fileinfo| Some(real_path.join(fileinfo.path.strip_prefix(&path).ok()?)));
+        log_line!(
+            "{path} resolved to {real_path}",
+            path = path.display(),
+            real_path = real_path.display()
+        );
+        let tab = self.current_tab_mut();
+        tab.cd(&real_path)?;
+        if let Some(selected) = selected {
+            tab.go_to_file(selected);
+        }
+        self.update_second_pane_for_preview()
+    }
+
     /// Execute a move or a copy of the flagged files to current directory.
     /// A progress bar is displayed (invisible for small files) and a notification
     /// is sent every time, even for 0 bytes files...
     pub fn cut_or_copy_flagged_files(&mut self, cut_or_copy: CopyMove) -> Result<()> {
         let sources = self.menu.flagged.content.clone();
-        let dest = &self.current_tab().directory_of_selected()?.to_owned();
-
-        if self.is_simple_move(&cut_or_copy, &sources, dest) {
-            self.simple_move(&sources, dest)
-        } else {
-            self.complex_move(cut_or_copy, sources, dest)
-        }
+        let dest = self.current_tab().directory_of_selected()?.to_owned();
+        self.cut_or_copy_to(cut_or_copy, sources, &dest)
     }
 
-    fn is_simple_move(&self, cut_or_copy: &CopyMove, sources: &[PathBuf], dest: &Path) -> bool {
-        if matches!(cut_or_copy, CopyMove::Copy) {
-            return false;
+    /// Execute a move or a copy of the flagged files, or the selected one if none is flagged,
+    /// to the current directory of the other tab. The current tab isn't modified.
+    /// Nothing is done if the other tab isn't displayed or if both tabs are in the same directory.
+    pub fn cut_or_copy_to_other_tab(&mut self, cut_or_copy: CopyMove) -> Result<()> {
+        if !self.session.dual() || self.session.preview() {
+            log_line!("The other tab must be displayed");
+            return Ok(());
         }
-        if sources.len() != 1 {
-            return false;
+        let dest = self.tabs[1 - self.index].directory.path.to_path_buf();
+        if dest == self.current_tab().directory.path.as_ref() {
+            log_line!("Both tabs are in the same directory");
+            return Ok(());
         }
-        let disks = &self.disks();
-        let Some(s) = disk_used_by_path(disks, &sources[0]) else {
-            return false;
-        };
-        let Some(d) = disk_used_by_path(disks, dest) else {
-            return false;
+        let sources = if self.menu.flagged.is_empty() {
+            let selected = self.current_tab().current_file()?;
+            if matches!(selected.filename.as_ref(), "." | "..") {
+                return Ok(());
+            }
+            vec![selected.path.to_path_buf()]
+        } else {
+            self.menu.flagged.content.clone()
         };
-        s.mount_point() == d.mount_point()
+        self.cut_or_copy_to(cut_or_copy, sources, &dest)
     }
 
-    fn simple_move(&mut self, sources: &[PathBuf], dest: &Path) -> Result<()> {
-        let source = &sources[0];
-        let filename = filename_from_path(source)?;
-        let dest = dest.to_path_buf().join(filename);
-        match std::fs::rename(source, &dest) {
-            Ok(()) => {
-                log_line!(
-                    "Moved {source} to {dest}",
-                    source = source.display(),
-                    dest = dest.display()
-                )
-            }
-            Err(e) => {
-                log_info!("Error: {e:?}");
-                log_line!("Error: {e:?}")
-            }
+    /// Flag the files of the current tab which are missing or different in the directory of the other tab.
+    /// Files are compared by name, size and modification time.
+    /// Only the displayed files are compared, unless `compare_recursive` is set in the config file:
+    /// both directories are then walked recursively.
+    pub fn compare_tabs(&mut self) {
+        if !self.session.dual() || self.session.preview() {
+            log_line!("The other tab must be displayed");
+            return;
         }
-        self.clear_flags_and_reset_view()
+        let here = self.current_tab().directory.path.to_path_buf();
+        let there = self.tabs[1 - self.index].directory.path.to_path_buf();
+        if here == there {
+            log_line!("Both tabs are in the same directory");
+            return;
+        }
+        let children: Vec<PathBuf> = self
+            .current_tab()
+            .directory
+            .content
+            .iter()
+            .filter(|file| file.filename.as_ref() != "." && file.filename.as_ref() != "..")
+            .map(|file| file.path.to_path_buf())
+            .collect();
+        let comparison = Comparison::new(&here, &children, &there, compare_recursive());
+        let flagged = comparison.differing.len();
+        for path in comparison.differing {
+            self.menu.flagged.push(path);
+        }
+        log_line!(
+            "Flagged {flagged} files: {only_here} only here, {modified} modified. {only_there} only in the other tab.",
+            only_here = comparison.only_here,
+            modified = comparison.modified,
+            only_there = comparison.only_there,
+        );
     }
 
-    fn complex_move(
+    /// Copy or move the sources to `dest`.
+    /// If some names are already used in `dest`, the user resolves the conflicts first.
+    fn cut_or_copy_to(
         &mut self,
         cut_or_copy: CopyMove,
         sources: Vec<PathBuf>,
-        dest: &PathBuf,
+        dest: &Path,
     ) -> Result<()> {
-        let mut must_act_now = true;
-        if matches!(cut_or_copy, CopyMove::Copy) {
-            if !self.internal_settings.copy_file_queue.is_empty() {
-                log_info!("cut_or_copy_flagged_files: act later");
-                must_act_now = false;
+        let conflicts = match Conflicts::new(cut_or_copy, sources, dest) {
+            Ok(conflicts) => conflicts,
+            Err(error) => {
+                log_line!("{error}");
+                return Ok(());
             }
-            self.internal_settings
-                .copy_file_queue
-                .push((sources.to_owned(), dest.clone()));
+        };
+        if conflicts.is_empty() {
+            self.menu.conflicts = None;
+            let (cut_or_copy, sources, dest, targets) = conflicts.into_transfer();
+            return self.transfer(cut_or_copy, sources, &dest, targets);
         }
+        self.menu.conflicts = Some(conflicts);
+        self.set_menu_mode(
+            self.index,
+            Menu::NeedConfirmation(NeedConfirmation::Conflict),
+        )
+    }
 
-        if must_act_now {
+    /// Copy or move the sources to `dest`, once the protected files it modifies are confirmed:
+    /// moved sources and overwritten files.
+    fn transfer(
+        &mut self,
+        cut_or_copy: CopyMove,
+        sources: Vec<PathBuf>,
+        dest: &Path,
+        targets: HashMap<PathBuf, PathBuf>,
+    ) -> Result<()> {
+        let mut modified: Vec<PathBuf> = targets
+            .values()
+            .filter(|target| target.symlink_metadata().is_ok())
+            .cloned()
+            .collect();
+        if matches!(cut_or_copy, CopyMove::Move) {
+            modified.extend(sources.iter().cloned());
+        }
+        let action = Guarded::Transfer(cut_or_copy, sources, dest.to_path_buf(), targets);
+        self.guard(&modified, action)
+    }
+
+    fn start_transfer(
+        &mut self,
+        cut_or_copy: CopyMove,
+        sources: Vec<PathBuf>,
+        dest: &PathBuf,
+        targets: HashMap<PathBuf, PathBuf>,
+    ) -> Result<()> {
+        if sources.is_empty() {
+            log_line!("Every file was skipped, nothing was transfered");
+            return self.clear_flags_and_reset_view();
+        }
+        if self.is_simple_move(&cut_or_copy, &sources, dest) {
+            self.simple_move(&sources, dest, &targets)
+        } else {
+            self.complex_move(cut_or_copy, sources, dest, targets)
+        }
+    }
+
+    /// Resolve the current conflict with the choice typed by the user.
+    /// Uppercase choices apply to every remaining conflict, any other key cancels the transfer.
+    fn resolve_conflict(&mut self, c: char) -> Result<()> {
+        let Some(conflicts) = &mut self.menu.conflicts else {
+            return Ok(());
+        };
+        match ConflictChoice::from_char(c) {
+            Some((ConflictChoice::Rename, false)) => {
+                let name = conflicts.free_name().unwrap_or_default();
+                self.set_menu_mode(self.index, Menu::InputSimple(InputSimple::ConflictRename))?;
+                self.menu.input.replace(&name);
+                Ok(())
+            }
+            Some((choice, to_all)) => {
+                conflicts.resolve(choice, to_all);
+                self.next_conflict()
+            }
+            None => {
+                self.menu.conflicts = None;
+                log_line!("Transfer canceled, nothing was done.");
+                self.reset_menu_mode()?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Transfer the conflicting file under the typed name.
+    /// An invalid or already used name is asked again.
+    pub fn conflict_rename(&mut self) -> Result<()> {
+        let Some(conflicts) = &mut self.menu.conflicts else {
+            return self.reset_menu_mode().map(|_| ());
+        };
+        match conflicts.rename_current(self.menu.input.string().trim()) {
+            Ok(()) => self.next_conflict(),
+            Err(error) => {
+                log_line!("{error}");
+                Ok(())
+            }
+        }
+    }
+
+    /// Ask the next conflict or, once they're all resolved, start the transfer.
+    fn next_conflict(&mut self) -> Result<()> {
+        if self
+            .menu
+            .conflicts
+            .as_ref()
+            .is_some_and(|conflicts| !conflicts.is_empty())
+        {
+            return self.set_menu_mode(
+                self.index,
+                Menu::NeedConfirmation(NeedConfirmation::Conflict),
+            );
+        }
+        let Some(conflicts) = self.menu.conflicts.take() else {
+            return Ok(());
+        };
+        self.reset_menu_mode()?;
+        let (cut_or_copy, sources, dest, targets) = conflicts.into_transfer();
+        self.transfer(cut_or_copy, sources, &dest, targets)
+    }
+
+    fn is_simple_move(&self, cut_or_copy: &CopyMove, sources: &[PathBuf], dest: &Path) -> bool {
+        if matches!(cut_or_copy, CopyMove::Copy) {
+            return false;
+        }
+        if sources.len() != 1 {
+            return false;
+        }
+        let disks = &self.disks();
+        let Some(s) = disk_used_by_path(disks, &sources[0]) else {
+            return false;
+        };
+        let Some(d) = disk_used_by_path(disks, dest) else {
+            return false;
+        };
+        s.mount_point() == d.mount_point()
+    }
+
+    fn simple_move(
+        &mut self,
+        sources: &[PathBuf],
+        dest: &Path,
+        targets: &HashMap<PathBuf, PathBuf>,
+    ) -> Result<()> {
+        let source = &sources[0];
+        let filename = filename_from_path(source)?;
+        let moved = match targets.get(source) {
+            Some(target) => replace_by(source, target).map(|()| target.to_owned()),
+            None => {
+                let dest = dest.to_path_buf().join(filename);
+                std::fs::rename(source, &dest)
+                    .map(|()| dest)
+                    .map_err(|error| error.into())
+            }
+        };
+        match moved {
+            Ok(dest) => {
+                log_line!(
+                    "Moved {source} to {dest}",
+                    source = source.display(),
+                    dest = dest.display()
+                )
+            }
+            Err(e) => {
+                log_info!("Error: {e:?}");
+                log_line!("Error: {e:?}")
+            }
+        }
+        self.clear_flags_and_reset_view()
+    }
+
+    fn complex_move(
+        &mut self,
+        cut_or_copy: CopyMove,
+        sources: Vec<PathBuf>,
+        dest: &PathBuf,
+        targets: HashMap<PathBuf, PathBuf>,
+    ) -> Result<()> {
+        let mut must_act_now = true;
+        if matches!(cut_or_copy, CopyMove::Copy) {
+            if !self.internal_settings.copy_file_queue.is_empty() {
+                log_info!("cut_or_copy_flagged_files: act later");
+                must_act_now = false;
+            }
+            self.internal_settings.copy_file_queue.push((
+                sources.to_owned(),
+                dest.clone(),
+                targets.clone(),
+            ));
+        }
+
+        if must_act_now {
             log_info!("cut_or_copy_flagged_files: act now");
             let in_mem = copy_move(
                 cut_or_copy,
                 sources,
                 dest,
+                targets,
                 self.left_window_width(),
                 self.internal_settings.term_size().1,
                 Arc::clone(&self.fm_sender),
@@ -973,6 +1803,100 @@ impl Status {
         self.clear_flags_and_reset_view()
     }
 
+    /// Path of the copy of the selected file, named from the input, next to the selected file.
+    pub fn copy_as_target(&self) -> Result<PathBuf> {
+        let source = self.current_tab().current_file()?.path;
+        let parent = source
+            .parent()
+            .context("copy as: the file should have a parent")?;
+        Ok(parent.join(self.menu.input.string().trim()))
+    }
+
+    /// Copy the selected file to the typed name, next to it.
+    /// If a file already has this name, a confirmation is asked before overwriting it.
+    /// Directories are never overwritten, another name is asked.
+    pub fn copy_as(&mut self) -> Result<()> {
+        let name = self.menu.input.string();
+        let name = name.trim();
+        if name.is_empty() || name.contains('/') || matches!(name, "." | "..") {
+            log_line!("Invalid name {name}");
+            self.reset_menu_mode()?;
+            return Ok(());
+        }
+        let source = self.current_tab().current_file()?.path.to_path_buf();
+        let target = self.copy_as_target()?;
+        if target == source {
+            log_line!("The copy needs another name");
+            self.reset_menu_mode()?;
+            return Ok(());
+        }
+        if target.is_dir() && !target.is_symlink() {
+            log_line!(
+                "{target} is a directory, it can't be overwritten",
+                target = target.display()
+            );
+            return self.copy_as_another_name();
+        }
+        if target.exists() {
+            return self
+                .set_menu_mode(self.index, Menu::NeedConfirmation(NeedConfirmation::CopyAs));
+        }
+        self.start_copy_as(source, target)
+    }
+
+    /// The target of the copy exists and is overwritten, once confirmed if it's protected.
+    fn copy_as_overwrite(&mut self) -> Result<()> {
+        let source = self.current_tab().current_file()?.path.to_path_buf();
+        let target = self.copy_as_target()?;
+        let modified = vec![target.clone()];
+        self.guard(&modified, Guarded::CopyAs(source, target))
+    }
+
+    /// The target of the copy exists and isn't overwritten.
+    /// The name is asked again, with enough `_` appended to the typed one to make it unique.
+    fn copy_as_another_name(&mut self) -> Result<()> {
+        let mut name = self.menu.input.string().trim().to_owned();
+        let mut target = self.copy_as_target()?;
+        while target.exists() {
+            name.push('_');
+            target.set_file_name(&name);
+        }
+        self.set_menu_mode(self.index, Menu::InputSimple(InputSimple::CopyAs))?;
+        self.menu.input.replace(&name);
+        Ok(())
+    }
+
+    /// Copy the source to the target in a thread, displaying a progress bar.
+    /// Only one copy runs at once and the others are queued.
+    /// A queued copy keeps its target, indexed by its source, and is renamed once copied.
+    fn start_copy_as(&mut self, source: PathBuf, target: PathBuf) -> Result<()> {
+        self.reset_menu_mode()?;
+        let dest = target
+            .parent()
+            .context("copy as: target should have a parent")?
+            .to_path_buf();
+        let must_act_now = self.internal_settings.copy_file_queue.is_empty();
+        self.internal_settings.copy_file_queue.push((
+            vec![source.clone()],
+            dest,
+            HashMap::from([(source.clone(), target.clone())]),
+        ));
+        if !must_act_now {
+            log_info!("copy as: act later");
+            log_line!("Copy of {source} queued", source = source.display());
+            return Ok(());
+        }
+        let in_mem = copy_as(
+            source,
+            target,
+            self.left_window_width(),
+            self.internal_settings.term_size().1,
+            Arc::clone(&self.fm_sender),
+        )?;
+        self.internal_settings.store_copy_progress(in_mem);
+        self.current_tab_mut().refresh_view()
+    }
+
     pub fn copy_next_file_in_queue(&mut self) -> Result<()> {
         self.internal_settings
             .copy_next_file_in_queue(self.fm_sender.clone(), self.left_window_width())
@@ -986,12 +1910,12 @@ impl Status {
         self.fuzzy = None;
     }
 
-    pub fn fuzzy_find_files(&mut self) -> Result<()> {
+    pub fn fuzzy_find_files(&mut self, respect_gitignore: bool) -> Result<()> {
         let Some(fuzzy) = &self.fuzzy else {
             bail!("Fuzzy should be set");
         };
         let current_path = self.current_tab().current_path().to_path_buf();
-        fuzzy.find_files(current_path);
+        fuzzy.find_files(current_path, respect_gitignore);
         Ok(())
     }
 
@@ -1003,6 +1927,14 @@ impl Status {
         Ok(())
     }
 
+    pub fn fuzzy_command_history(&mut self) -> Result<()> {
+        let Some(fuzzy) = &self.fuzzy else {
+            bail!("Fuzzy should be set");
+        };
+        fuzzy.find_action(self.menu.input_history.commands());
+        Ok(())
+    }
+
     pub fn fuzzy_find_lines(&mut self) -> Result<()> {
         let Some(fuzzy) = &self.fuzzy else {
             bail!("Fuzzy should be set");
@@ -1032,6 +1964,10 @@ impl Status {
                 FuzzyKind::File => self.tabs[self.index].cd_to_file(Path::new(&pick))?,
                 FuzzyKind::Line => self.tabs[self.index].cd_to_file(&parse_line_output(&pick)?)?,
                 FuzzyKind::Action => self.fuzzy_send_event(&pick)?,
+                FuzzyKind::Command => {
+                    self.fuzzy_leave()?;
+                    return self.recall_command(&pick);
+                }
             }
         } else {
             log_info!("Fuzzy had nothing to pick from");
@@ -1039,6 +1975,27 @@ impl Status {
         self.fuzzy_leave()
     }
 
+    /// Open the menu of a command picked from history, with its input filled by the command.
+    /// The command isn't run, it can be edited before being validated.
+    fn recall_command(&mut self, pick: &str) -> Result<()> {
+        let Some((menu, command)) = InputHistory::recall(pick) else {
+            return Ok(());
+        };
+        if matches!(menu, Menu::InputCompleted(InputCompleted::Exec))
+            && self.menu.flagged.is_empty()
+        {
+            self.menu
+                .flagged
+                .push(self.current_tab().current_file()?.path.to_path_buf());
+        }
+        self.set_menu_mode(self.index, menu)?;
+        self.menu.input.replace(&command);
+        if let Menu::InputCompleted(input_completed) = menu {
+            self.complete(input_completed)?;
+        }
+        Ok(())
+    }
+
     /// Run a command directly from help.
     /// Search a command with fuzzy finder, if it's a keybinding, run it directly.
     /// If the result can't be parsed, nothing is done.
@@ -1181,7 +2138,7 @@ impl Status {
     }
 
     fn update_search(&mut self) -> Result<()> {
-        if let Ok(search) = Search::new(&self.menu.input.string()) {
+        if let Ok(search) = Search::new(&self.menu.input.string(), self.menu.search_kind) {
             self.current_tab_mut().search = search;
         };
         Ok(())
@@ -1229,6 +2186,36 @@ impl Status {
         Ok(())
     }
 
+    /// Flag every file matching a typed glob, keeping the existing flags.
+    /// Move to the "first" flagged file.
+    pub fn flag_from_glob(&mut self) -> Result<()> {
+        let input = self.menu.input.string();
+        if input.is_empty() {
+            return Ok(());
+        }
+        let paths = match self.current_tab().display_mode {
+            Display::Directory => self.tabs[self.index].directory.paths(),
+            Display::Tree => self.tabs[self.index].tree.paths(),
+            _ => return Ok(()),
+        };
+        let nb_flagged = match glob_flagger(&input, &paths, &mut self.menu.flagged) {
+            Ok(nb_flagged) => nb_flagged,
+            Err(error) => {
+                log_line!(
+                    "Invalid glob {input}: {error}",
+                    error = last_error_line(&error)
+                );
+                return Ok(());
+            }
+        };
+        log_line!("{nb_flagged} files flagged matching {input}");
+        if !self.menu.flagged.is_empty() {
+            self.tabs[self.index]
+                .go_to_file(self.menu.flagged.selected().context("no selected file")?);
+        }
+        Ok(())
+    }
+
     /// Open a the selected file with its opener
     pub fn open_selected_file(&mut self) -> Result<()> {
         let path = self.current_tab().current_file()?.path;
@@ -1243,6 +2230,51 @@ impl Status {
         }
     }
 
+    /// Extract the selected entry of the previewed archive into a temporary folder
+    /// and open it with its opener.
+    /// Does nothing if no archive is browsed or if its root is selected.
+    pub fn open_archive_entry(&mut self) -> Result<()> {
+        let tab = self.current_tab();
+        let (Display::Preview, Preview::Tree(tree)) = (&tab.display_mode, &tab.preview) else {
+            log_line!("Preview an archive as a tree to open one of its files");
+            return Ok(());
+        };
+        let archive = tree.root_path().to_path_buf();
+        let entry = tree.selected_path_relative_to_root()?.to_path_buf();
+        if entry.as_os_str().is_empty() {
+            return Ok(());
+        }
+        match extract_entry_to_tmp(&archive, &entry) {
+            Ok(path) => self.open_single_file(&path),
+            Err(error) => {
+                log_line!("Couldn't open {entry}: {error}", entry = entry.display());
+                Ok(())
+            }
+        }
+    }
+
+    /// Extract the archive next to it and refresh the view.
+    pub fn extract_archive(&mut self, path: &Path, extractor: Internal) -> Result<()> {
+        match extractor.open(path) {
+            Ok(()) => log_line!("Extracted {path}", path = path.display()),
+            Err(error) => log_line!("Couldn't extract {path}: {error}", path = path.display()),
+        }
+        self.refresh_tabs()
+    }
+
+    /// Open the notes file of the current directory, creating it if it doesn't exist.
+    /// Its name is read from the config file, `.fm-notes.md` by default.
+    pub fn open_notes(&mut self) -> Result<()> {
+        let path = self.current_tab().directory.path.join(notes_filename());
+        if !path.exists() {
+            std::fs::File::create(&path)?;
+            log_line!("Created {path}", path = path.display());
+            self.refresh_tabs()?;
+            self.current_tab_mut().go_to_file(&path);
+        }
+        self.open_single_file(&path)
+    }
+
     /// Open every flagged file with their respective opener.
     pub fn open_flagged_files(&mut self) -> Result<()> {
         self.internal_settings
@@ -1373,6 +2405,25 @@ impl Status {
         }
     }
 
+    /// Unmount the selected usb device if needed and power it off.
+    /// Will ask first for a sudo password which is immediatly forgotten.
+    pub fn power_off_removable(&mut self) -> Result<()> {
+        if self.menu.removable_devices.is_empty() {
+            return Ok(());
+        };
+        let device = &mut self.menu.removable_devices.content[self.menu.removable_devices.index];
+        if !device.is_usb() {
+            log_line!("Only usb disks can be powered off");
+            return Ok(());
+        }
+        if !self.menu.password_holder.has_sudo() {
+            self.ask_password(Some(BlockDeviceAction::POWEROFF), PasswordUsage::USB)
+        } else {
+            device.power_off(&mut self.menu.password_holder)?;
+            Ok(())
+        }
+    }
+
     pub fn mount_removable(&mut self) -> Result<()> {
         if self.menu.removable_devices.is_empty() {
             return Ok(());
@@ -1391,6 +2442,27 @@ impl Status {
         }
     }
 
+    /// Mount a device from the label or the UUID typed by the user.
+    /// The device is added to the removable devices and mounted the same way.
+    /// A sudo password may be asked.
+    pub fn mount_by_label(&mut self) -> Result<()> {
+        let identifier = self.menu.input.string();
+        self.menu.input.reset();
+        self.reset_menu_mode()?;
+        let removable = match Removable::from_label_or_uuid(identifier.trim()) {
+            Ok(removable) => removable,
+            Err(error) => {
+                log_line!("{error}");
+                return Ok(());
+            }
+        };
+        if removable.is_mounted() {
+            log_line!("{name} is already mounted", name = removable.name);
+        }
+        self.menu.removable_devices.push_and_select(removable);
+        self.mount_removable()
+    }
+
     /// Move to the selected removable device.
     pub fn go_to_removable(&mut self) -> Result<()> {
         let Some(path) = self.menu.find_removable_mount_point() else {
@@ -1407,6 +2479,179 @@ impl Status {
         self.parse_shell_command(shell_command, None, true)
     }
 
+    /// Expanded arguments of the typed shell command, quoted if they contain spaces.
+    /// Returns `None` if the command can't be parsed.
+    pub fn expanded_shell_command(&self) -> Option<String> {
+        let args = shell_command_parser(&self.menu.input.string(), self).ok()?;
+        Some(
+            args.iter()
+                .map(|arg| {
+                    if arg.contains(char::is_whitespace) {
+                        format!("'{arg}'")
+                    } else {
+                        arg.to_owned()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+
+    /// Parse the typed shell command and display its expansion.
+    /// It's only executed once confirmed.
+    pub fn shell_dry_run(&mut self) -> Result<()> {
+        if self.expanded_shell_command().is_none() {
+            self.reset_menu_mode()?;
+            return Ok(());
+        }
+        self.set_menu_mode(
+            self.index,
+            Menu::NeedConfirmation(NeedConfirmation::ShellCommand),
+        )
+    }
+
+    /// Execute the confirmed shell command like a normal one.
+    fn confirm_shell_command(&mut self) -> Result<()> {
+        let command = self.menu.input.string();
+        self.reset_menu_mode()?;
+        self.parse_shell_command(command, None, true)?;
+        Ok(())
+    }
+
+    /// Reads and parse a shell command and stream its output into the preview.
+    /// Sudo commands and commands executed in the same window can't be streamed and are executed normally.
+    pub fn stream_shell_command_from_input(&mut self) -> Result<()> {
+        let command = self.menu.input.string();
+        let Ok(args) = shell_command_parser(&command, self) else {
+            self.set_menu_mode(self.index, Menu::Nothing)?;
+            return Ok(());
+        };
+        let Some(executable) = args.first() else {
+            return Ok(());
+        };
+        if is_sudo_command(executable) || *executable == *SAME_WINDOW_TOKEN {
+            self.execute_parsed_command(args, command, true)?;
+            return Ok(());
+        }
+        if !is_in_path(executable) {
+            log_line!("{executable} isn't in path.");
+            self.reset_menu_mode()?;
+            return Ok(());
+        }
+        self.stream_command(args, command)
+    }
+
+    /// Run the parsed command in the current directory and stream its output in the preview.
+    /// A previous streamed command is killed first.
+    fn stream_command(&mut self, mut args: Vec<String>, command: String) -> Result<()> {
+        if args.is_empty() {
+            return Ok(());
+        }
+        let executable = args.remove(0);
+        let params: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        let current_dir = self.current_tab().directory_of_selected()?.to_owned();
+        self.cancel_streamed_command();
+        match execute_and_stream_output(executable, &params, &current_dir, self.fm_sender.clone()) {
+            Ok(child) => {
+                self.reset_menu_mode()?;
+                self.current_tab_mut().set_display_mode(Display::Preview);
+                self.current_tab_mut().preview = PreviewBuilder::streamed_command(command);
+                self.current_tab_mut().window.reset(0);
+                self.streamed_command = Some((self.index, child));
+            }
+            Err(e) => {
+                log_info!("Error {e:?}");
+                log_line!("Command {command} couldn't be started");
+            }
+        }
+        Ok(())
+    }
+
+    /// Pid of the streamed command, if any.
+    fn streamed_pid(&self) -> Option<u32> {
+        Some(self.streamed_command.as_ref()?.1.lock().id())
+    }
+
+    /// Append a line of the streamed command to its preview.
+    /// If the preview has been replaced, the command isn't needed anymore and is killed.
+    pub fn append_streamed_line(&mut self, pid: u32, line: String) {
+        if self.streamed_pid() != Some(pid) {
+            return;
+        }
+        let Some((index, _)) = self.streamed_command else {
+            return;
+        };
+        let tab = &mut self.tabs[index];
+        if let Preview::Text(text) = &mut tab.preview {
+            if matches!(text.kind, TextKind::CommandStream) {
+                text.push_line(line);
+                let len = tab.preview.len();
+                tab.window.append_len(len);
+                return;
+            }
+        }
+        self.cancel_streamed_command();
+    }
+
+    /// The streamed command exited. Log its exit code.
+    pub fn finish_streamed_command(&mut self, pid: u32, code: Option<i32>) {
+        if self.streamed_pid() != Some(pid) {
+            return;
+        }
+        self.streamed_command = None;
+        match code {
+            Some(code) => log_line!("Streamed command exited with code {code}"),
+            None => log_line!("Streamed command was terminated"),
+        }
+    }
+
+    /// Kill the streamed command if any is running.
+    /// Returns true if a command was killed.
+    pub fn cancel_streamed_command(&mut self) -> bool {
+        let Some((_, child)) = self.streamed_command.take() else {
+            return false;
+        };
+        if let Err(e) = child.lock().kill() {
+            log_info!("Couldn't kill streamed command: {e:?}");
+        }
+        log_line!("Streamed command canceled");
+        true
+    }
+
+    /// Open a terminal pane running a shell in the current directory.
+    /// If it's already opened, toggle its focus.
+    pub fn toggle_terminal_pane(&mut self) -> Result<()> {
+        if let Some(terminal_pane) = &mut self.terminal_pane {
+            terminal_pane.set_focus(!terminal_pane.is_focused());
+            return Ok(());
+        }
+        let (width, height) = crossterm::terminal::size()?;
+        let pane_height = TerminalPane::height(height);
+        self.terminal_pane = Some(TerminalPane::new(
+            &self.current_tab().directory.path,
+            pane_height.saturating_sub(2),
+            width.saturating_sub(2),
+            self.fm_sender.clone(),
+        )?);
+        self.resize(width, height)
+    }
+
+    /// Close the terminal pane, killing its shell if it's still running.
+    pub fn close_terminal_pane(&mut self) -> Result<()> {
+        if self.terminal_pane.take().is_none() {
+            return Ok(());
+        }
+        let (width, height) = crossterm::terminal::size()?;
+        self.resize(width, height)
+    }
+
+    /// True if a terminal pane is opened and keys are sent to its shell.
+    pub fn is_terminal_pane_focused(&self) -> bool {
+        self.terminal_pane
+            .as_ref()
+            .is_some_and(|terminal_pane| terminal_pane.is_focused())
+    }
+
     fn build_shell_command(shell_command: String, files: Option<Vec<String>>) -> String {
         if let Some(files) = &files {
             shell_command + " " + &files.join(" ")
@@ -1556,13 +2801,144 @@ impl Status {
     }
 
     /// Recursively delete all flagged files.
+    /// It's done in a thread, displaying a progress bar.
     pub fn confirm_delete_files(&mut self) -> Result<()> {
-        self.menu.delete_flagged_files()?;
+        let started = self.start_removal(Removal::Delete);
         self.reset_menu_mode()?;
-        self.clear_flags_and_reset_view()?;
+        if !started {
+            return Ok(());
+        }
+        self.clear_flags_and_reset_view()?;
         self.refresh_status()
     }
 
+    /// Delete or trash the flagged files in a thread.
+    /// Only one removal may run at once.
+    /// Returns true if the removal started, the flagged files are kept otherwise.
+    pub fn start_removal(&mut self, removal: Removal) -> bool {
+        if self.internal_settings.removal.is_some() {
+            log_line!("Wait for the current removal to finish or cancel it");
+            return false;
+        }
+        let (width, height) = self.internal_settings.term_size();
+        match RemovalWorker::start(
+            removal,
+            self.menu.flagged.content.clone(),
+            width,
+            height,
+            self.fm_sender.clone(),
+        ) {
+            Ok(worker) => {
+                self.internal_settings.removal = Some(worker);
+                true
+            }
+            Err(error) => {
+                log_info!("start removal: {error}");
+                false
+            }
+        }
+    }
+
+    /// Stop the running removal after the current file.
+    pub fn cancel_removal(&self) {
+        match &self.internal_settings.removal {
+            Some(removal) => removal.cancel(),
+            None => log_line!("No removal to cancel"),
+        }
+    }
+
+    /// The removal thread is done, the trash content is updated.
+    pub fn removal_finished(&mut self) -> Result<()> {
+        self.internal_settings.removal = None;
+        self.menu.trash.update()
+    }
+
+    /// Convert the flagged images, or the selected one, into the current directory in a thread.
+    /// The format is picked by the user. Only one conversion may run at once.
+    pub fn convert_images(&mut self) -> Result<()> {
+        if self.internal_settings.conversion.is_some() {
+            log_line!("Wait for the current conversion to finish");
+            return Ok(());
+        }
+        let Some(extension) = self.menu.image_converter.selected_extension() else {
+            return Ok(());
+        };
+        let sources = if self.menu.flagged.is_empty() {
+            vec![self.current_tab().current_file()?.path.to_path_buf()]
+        } else {
+            self.menu.flagged.content.clone()
+        };
+        let (width, height) = self.internal_settings.term_size();
+        match ConvertWorker::start(
+            extension,
+            sources,
+            self.current_tab().directory.path.to_path_buf(),
+            width,
+            height,
+            self.fm_sender.clone(),
+        ) {
+            Ok(worker) => self.internal_settings.conversion = Some(worker),
+            Err(error) => log_line!("{error}"),
+        }
+        Ok(())
+    }
+
+    /// The conversion thread is done, the converted images are flagged.
+    pub fn conversion_finished(&mut self, converted: Vec<PathBuf>) -> Result<()> {
+        self.internal_settings.conversion = None;
+        if converted.is_empty() {
+            return Ok(());
+        }
+        self.menu.flagged.clear();
+        for path in converted {
+            self.menu.flagged.push(path);
+        }
+        self.update_second_pane_for_preview()
+    }
+
+    /// Split the selected file into parts of the typed size in a thread.
+    /// Only one split or join may run at once.
+    pub fn split_file(&mut self) -> Result<()> {
+        if self.internal_settings.split_join.is_some() {
+            log_line!("Wait for the current split or join to finish");
+            return Ok(());
+        }
+        let chunk_size = match parse_size(&self.menu.input.string()) {
+            Ok(chunk_size) => chunk_size,
+            Err(error) => {
+                log_line!("{error}");
+                return Ok(());
+            }
+        };
+        let selected = self.current_tab().current_file()?;
+        let label = format!("split {name}", name = selected.filename);
+        match split_file(
+            selected.path.to_path_buf(),
+            chunk_size,
+            self.fm_sender.clone(),
+        ) {
+            Ok(()) => self.internal_settings.split_join = Some((label, 0, 0)),
+            Err(error) => log_line!("split: {error}"),
+        }
+        Ok(())
+    }
+
+    /// Join the parts of a split file in a thread, the selected file being one of them.
+    /// Only one split or join may run at once.
+    pub fn join_parts(&mut self) -> Result<()> {
+        if self.internal_settings.split_join.is_some() {
+            log_line!("Wait for the current split or join to finish");
+            return Ok(());
+        }
+        let selected = self.current_tab().current_file()?;
+        let label = format!("join {name}", name = selected.filename);
+        match join_parts(&selected.path, self.fm_sender.clone()) {
+            Ok(()) => self.internal_settings.split_join = Some((label, 0, 0)),
+            Err(error) => log_line!("join: {error}"),
+        }
+        Ok(())
+    }
+
     /// Empty the trash folder permanently.
     pub fn confirm_trash_empty(&mut self) -> Result<()> {
         self.menu.trash.empty_trash()?;
@@ -1596,7 +2972,128 @@ impl Status {
         Ok(())
     }
 
+    /// Ask a confirmation before renaming the flagged images of the current directory to their capture date.
+    pub fn exif_rename(&mut self) -> Result<()> {
+        let flagged = self.flagged_in_current_dir();
+        if flagged.is_empty() {
+            log_line!("Exif rename: flag some images first");
+            return Ok(());
+        }
+        let current_path = self.current_tab_path_str();
+        if !self.menu.bulk.exif_rename(flagged, &current_path) {
+            log_line!("Exif rename: no image can be renamed");
+            return Ok(());
+        }
+        self.set_menu_mode(
+            self.index,
+            Menu::NeedConfirmation(NeedConfirmation::BulkAction),
+        )
+    }
+
+    /// Ask a confirmation before changing the case of the flagged filenames of the current directory.
+    pub fn case_rename(&mut self, case_transform: CaseTransform) -> Result<()> {
+        let flagged = self.flagged_in_current_dir();
+        if flagged.is_empty() {
+            log_line!("Case rename: flag some files first");
+            return Ok(());
+        }
+        let current_path = self.current_tab_path_str();
+        if !self
+            .menu
+            .bulk
+            .case_rename(flagged, &current_path, case_transform)
+        {
+            log_line!("Case rename: no file can be renamed");
+            return Ok(());
+        }
+        self.set_menu_mode(
+            self.index,
+            Menu::NeedConfirmation(NeedConfirmation::BulkAction),
+        )
+    }
+
+    /// Ask a confirmation before URL decoding the flagged filenames of the current directory.
+    pub fn url_decode_rename(&mut self) -> Result<()> {
+        let flagged = self.flagged_in_current_dir();
+        if flagged.is_empty() {
+            log_line!("URL decode: flag some files first");
+            return Ok(());
+        }
+        let current_path = self.current_tab_path_str();
+        if !self.menu.bulk.url_decode_rename(flagged, &current_path) {
+            log_line!("URL decode: no file can be renamed");
+            return Ok(());
+        }
+        self.set_menu_mode(
+            self.index,
+            Menu::NeedConfirmation(NeedConfirmation::BulkAction),
+        )
+    }
+
+    /// Ask a confirmation before prefixing the flagged filenames of the current directory
+    /// with the numbered sequence typed by the user.
+    pub fn numbered_rename(&mut self) -> Result<()> {
+        let flagged = self.flagged_in_display_order();
+        let sequence = match NumberedSequence::parse(&self.menu.input.string(), flagged.len()) {
+            Ok(sequence) => sequence,
+            Err(error) => {
+                log_line!("Numbered rename: {error}");
+                return self.reset_menu_mode().map(|_| ());
+            }
+        };
+        let current_path = self.current_tab_path_str();
+        if !self
+            .menu
+            .bulk
+            .numbered_rename(flagged, &current_path, sequence)
+        {
+            log_line!("Numbered rename: no file can be renamed");
+            return self.reset_menu_mode().map(|_| ());
+        }
+        self.set_menu_mode(
+            self.index,
+            Menu::NeedConfirmation(NeedConfirmation::BulkAction),
+        )
+    }
+
+    /// Ask a confirmation before replacing the extension of the flagged filenames of the current directory
+    /// with the one typed by the user.
+    pub fn extension_rename(&mut self) -> Result<()> {
+        let flagged = self.flagged_in_current_dir();
+        let input = self.menu.input.string();
+        let extension = input.trim().trim_start_matches('.');
+        let current_path = self.current_tab_path_str();
+        if !self
+            .menu
+            .bulk
+            .extension_rename(flagged, &current_path, extension)
+        {
+            log_line!("Extension rename: no file can be renamed");
+            return self.reset_menu_mode().map(|_| ());
+        }
+        self.set_menu_mode(
+            self.index,
+            Menu::NeedConfirmation(NeedConfirmation::BulkAction),
+        )
+    }
+
     /// Execute the bulk action.
+    /// Move the flagged files to the trash, once the protected ones are confirmed.
+    pub fn trash_flagged(&mut self) -> Result<()> {
+        let flagged = self.menu.flagged.content.clone();
+        self.guard(&flagged, Guarded::Trash)
+    }
+
+    fn trash_flagged_files(&mut self) -> Result<()> {
+        self.menu.trash.update()?;
+        if !self.start_removal(Removal::Trash(self.menu.trash.clone())) {
+            return Ok(());
+        }
+        self.menu.flagged.clear();
+        self.current_tab_mut().refresh_view()?;
+        Ok(())
+    }
+
     pub fn confirm_bulk_action(&mut self) -> Result<()> {
         if let (Some(paths), Some(create)) = self.menu.bulk.execute()? {
             self.menu.flagged.update(paths);
@@ -1649,17 +3146,18 @@ impl Status {
             PasswordUsage::USB => match action {
                 Some(BlockDeviceAction::MOUNT) => self.mount_removable(),
                 Some(BlockDeviceAction::UMOUNT) => self.umount_removable(),
+                Some(BlockDeviceAction::POWEROFF) => self.power_off_removable(),
                 None => Ok(()),
             },
             PasswordUsage::ISO => match action {
                 Some(BlockDeviceAction::MOUNT) => self.mount_iso_drive(),
                 Some(BlockDeviceAction::UMOUNT) => self.umount_iso_drive(),
-                None => Ok(()),
+                Some(BlockDeviceAction::POWEROFF) | None => Ok(()),
             },
             PasswordUsage::CRYPTSETUP(_) => match action {
                 Some(BlockDeviceAction::MOUNT) => self.mount_encrypted_drive(),
                 Some(BlockDeviceAction::UMOUNT) => self.umount_encrypted_drive(),
-                None => Ok(()),
+                Some(BlockDeviceAction::POWEROFF) | None => Ok(()),
             },
             PasswordUsage::SUDOCOMMAND => self.run_sudo_command(sudo_command),
         }
@@ -1683,6 +3181,74 @@ impl Status {
         self.current_tab_mut().preview = preview;
     }
 
+    /// Display every information about the selected file in full screen.
+    pub fn properties(&mut self) -> Result<()> {
+        let file_info = self.current_tab().current_file()?;
+        let lines = MoreInfos::new(&file_info, &self.internal_settings.opener)
+            .with_home_collapsed(self.session.tilde_path())
+            .to_properties();
+        let preview = PreviewBuilder::properties(&file_info.path, lines);
+        self.current_tab_mut().set_display_mode(Display::Preview);
+        self.current_tab_mut().window.reset(preview.len());
+        self.current_tab_mut().preview = preview;
+        Ok(())
+    }
+
+    /// Ask a confirmation before running the selected script, since it may run arbitrary code.
+    /// The command running it is displayed while the user confirms.
+    pub fn ask_run_script(&mut self) -> Result<()> {
+        let path = self.current_tab().current_file()?.path;
+        let Some(command) = script_command(&path) else {
+            log_line!(
+                "{path} can't be run: it's not executable and no interpreter was found",
+                path = path.display()
+            );
+            return Ok(());
+        };
+        self.menu.script_command = Some(command);
+        self.set_menu_mode(
+            self.index,
+            Menu::NeedConfirmation(NeedConfirmation::RunScript),
+        )
+    }
+
+    /// Run the selected script in the current directory and stream its output in the preview.
+    /// Its exit code is logged once it's done.
+    fn confirm_run_script(&mut self) -> Result<()> {
+        let args = self
+            .menu
+            .script_command
+            .take()
+            .context("This file can't be run")?;
+        let command = args.join(" ");
+        log_info!("run script {command}");
+        self.stream_command(args, command)
+    }
+
+    /// Set the selected image as wallpaper with the configured command or an installed setter.
+    pub fn set_wallpaper(&mut self) -> Result<()> {
+        let file = self.current_tab().current_file()?;
+        if !matches!(
+            ExtensionKind::matcher(&file.extension.to_lowercase()),
+            ExtensionKind::Image
+        ) {
+            log_line!("{filename} isn't an image", filename = file.filename);
+            return Ok(());
+        }
+        let Some(mut args) = wallpaper_setter(&file.path) else {
+            log_line!("No wallpaper setter found. Install swww, swaybg, feh or nitrogen, or set wallpaper_command in the config file");
+            return Ok(());
+        };
+        let executable = args.remove(0);
+        let params: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
+        execute_without_output(&executable, &params)?;
+        log_line!(
+            "Wallpaper set with {executable}: {filename}",
+            filename = file.filename
+        );
+        Ok(())
+    }
+
     /// Set the nvim listen address from what the user typed.
     pub fn update_nvim_listen_address(&mut self) {
         self.internal_settings.update_nvim_listen_address()
@@ -1690,7 +3256,14 @@ impl Status {
 
     /// Execute a command requiring a confirmation (Delete, Move or Copy).
     /// The action is only executed if the user typed the char `y`
+    /// Modifying a protected file requires to type its full path too, see [`Status::guard`].
     pub fn confirm(&mut self, c: char, confirmed_action: NeedConfirmation) -> Result<()> {
+        if c != 'y' && matches!(confirmed_action, NeedConfirmation::CopyAs) {
+            return self.copy_as_another_name();
+        }
+        if matches!(confirmed_action, NeedConfirmation::Conflict) {
+            return self.resolve_conflict(c);
+        }
         if c == 'y' {
             if let Ok(must_leave) = self.match_confirmed_mode(confirmed_action) {
                 if must_leave {
@@ -1704,18 +3277,129 @@ impl Status {
         Ok(())
     }
 
+    /// Run a destructive action modifying `paths` at once if none of them is protected.
+    /// Otherwise the user has to type the full path of every protected one first,
+    /// the action runs once they're all confirmed.
+    /// Every destructive action goes through this guard. See `protected_paths` in the config file.
+    fn guard(&mut self, paths: &[PathBuf], action: Guarded) -> Result<()> {
+        match ProtectedGuard::new(paths, action) {
+            Ok(guard) => {
+                self.menu.protected = Some(guard);
+                self.ask_protected()
+            }
+            Err(action) => self.run_guarded(action),
+        }
+    }
+
+    /// Ask the full path of the next protected file.
+    fn ask_protected(&mut self) -> Result<()> {
+        let Some(guard) = &self.menu.protected else {
+            return Ok(());
+        };
+        if let Some(protected) = guard.current() {
+            log_line!(
+                "{protected} is protected ({nb} left). Type its full path to confirm.",
+                protected = protected.display(),
+                nb = guard.len()
+            );
+        }
+        self.menu.input.reset();
+        self.set_menu_mode(self.index, Menu::InputSimple(InputSimple::ConfirmProtected))
+    }
+
+    fn run_guarded(&mut self, action: Guarded) -> Result<()> {
+        match action {
+            Guarded::Delete => self.confirm_delete_files(),
+            Guarded::Trash => self.trash_flagged_files(),
+            Guarded::BulkRename => self.confirm_bulk_action(),
+            Guarded::CopyAs(source, target) => self.start_copy_as(source, target),
+            Guarded::Transfer(cut_or_copy, sources, dest, targets) => {
+                self.start_transfer(cut_or_copy, sources, &dest, targets)
+            }
+            Guarded::SaveFlagged(path) => self.write_flagged(&path),
+        }
+    }
+
+    /// Confirm the protected file whose full path was typed.
+    /// The guarded action runs once every protected file is confirmed.
+    /// Any other input cancels the action.
+    pub fn confirm_protected(&mut self) -> Result<()> {
+        let typed = self.menu.input.string();
+        let Some(guard) = &mut self.menu.protected else {
+            return self.reset_menu_mode().map(|_| ());
+        };
+        if !guard.confirm(Path::new(typed.trim())) {
+            self.menu.protected = None;
+            log_line!("The typed path doesn't match, nothing was done.");
+            return self.reset_menu_mode().map(|_| ());
+        }
+        if !guard.is_empty() {
+            return self.ask_protected();
+        }
+        let Some(guard) = self.menu.protected.take() else {
+            return Ok(());
+        };
+        self.menu.input.reset();
+        self.reset_menu_mode()?;
+        self.run_guarded(guard.into_action())
+    }
+
+    /// True if the user is asked to resolve the conflicts of a copy or a move
+    /// or to confirm the protected files of a destructive action.
+    fn is_waiting_for_user(&self) -> bool {
+        matches!(
+            self.current_tab().menu_mode,
+            Menu::NeedConfirmation(NeedConfirmation::Conflict)
+                | Menu::InputSimple(InputSimple::ConfirmProtected)
+        )
+    }
+
     /// Execute a `NeedConfirmation` action (delete, move, copy, empty trash)
     fn match_confirmed_mode(&mut self, confirmed_action: NeedConfirmation) -> Result<bool> {
         match confirmed_action {
-            NeedConfirmation::Delete => self.confirm_delete_files(),
-            NeedConfirmation::Move => self.cut_or_copy_flagged_files(CopyMove::Move),
-            NeedConfirmation::Copy => self.cut_or_copy_flagged_files(CopyMove::Copy),
+            NeedConfirmation::Delete => {
+                let flagged = self.menu.flagged.content.clone();
+                self.guard(&flagged, Guarded::Delete)?;
+                return Ok(self.is_waiting_for_user());
+            }
+            NeedConfirmation::Move => {
+                self.cut_or_copy_flagged_files(CopyMove::Move)?;
+                return Ok(self.is_waiting_for_user());
+            }
+            NeedConfirmation::Copy => {
+                self.cut_or_copy_flagged_files(CopyMove::Copy)?;
+                return Ok(self.is_waiting_for_user());
+            }
             NeedConfirmation::EmptyTrash => self.confirm_trash_empty(),
-            NeedConfirmation::BulkAction => self.confirm_bulk_action(),
+            NeedConfirmation::BulkAction => {
+                let renamed = self.menu.bulk.renamed_paths();
+                self.guard(&renamed, Guarded::BulkRename)?;
+                return Ok(self.is_waiting_for_user());
+            }
             NeedConfirmation::DeleteCloud => {
                 self.cloud_confirm_delete()?;
                 return Ok(true);
             }
+            NeedConfirmation::RunScript => {
+                self.confirm_run_script()?;
+                return Ok(true);
+            }
+            NeedConfirmation::CopyAs => {
+                self.copy_as_overwrite()?;
+                return Ok(true);
+            }
+            NeedConfirmation::ShellCommand => {
+                self.confirm_shell_command()?;
+                return Ok(true);
+            }
+            NeedConfirmation::SaveFlagged => {
+                let path = self.typed_path();
+                let modified = vec![path.clone()];
+                self.guard(&modified, Guarded::SaveFlagged(path))?;
+                return Ok(self.is_waiting_for_user());
+            }
+            // Conflicts are resolved by their own keys, see `Status::resolve_conflict`.
+            NeedConfirmation::Conflict => return Ok(true),
         }?;
         Ok(false)
     }
@@ -1760,6 +3444,130 @@ impl Status {
         self.reset_tabs_view()
     }
 
+    /// Set the typed extended attribute (`name=value`) on the selected file.
+    pub fn set_xattr(&mut self) -> Result<()> {
+        let path = self.current_tab().current_file()?.path;
+        match set_xattr(&path, &self.menu.input.string()) {
+            Ok(()) => log_line!("Extended attribute set on {path}", path = path.display()),
+            Err(error) => log_line!("Couldn't set the extended attribute: {error}"),
+        }
+        Ok(())
+    }
+
+    /// Save the flagged files to the typed path, one absolute path per line.
+    /// An existing file is only overwritten once confirmed, directories never are.
+    /// The menu is reset here since an existing file requires a confirmation.
+    pub fn save_flagged(&mut self) -> Result<()> {
+        if self.menu.flagged.is_empty() {
+            log_line!("No flagged file");
+            return self.reset_menu_mode().map(|_| ());
+        }
+        let path = self.typed_path();
+        if path.is_dir() {
+            log_line!(
+                "{path} is a directory, the flagged files can't be saved there",
+                path = path.display()
+            );
+            return self.reset_menu_mode().map(|_| ());
+        }
+        if path.exists() {
+            return self.set_menu_mode(
+                self.index,
+                Menu::NeedConfirmation(NeedConfirmation::SaveFlagged),
+            );
+        }
+        self.reset_menu_mode()?;
+        let modified = vec![path.clone()];
+        self.guard(&modified, Guarded::SaveFlagged(path))
+    }
+
+    /// Write the flagged files to `path`, one absolute path per line.
+    fn write_flagged(&mut self, path: &Path) -> Result<()> {
+        self.menu.input.reset();
+        let mut content = self.menu.flagged.content_to_string();
+        content.push('\n');
+        match std::fs::write(path, content) {
+            Ok(()) => log_line!(
+                "Saved {len} flagged files to {path}",
+                len = self.menu.flagged.len(),
+                path = path.display()
+            ),
+            Err(error) => log_line!("Couldn't save the flagged files: {error}"),
+        }
+        Ok(())
+    }
+
+    /// Replace the flagged files by those saved in the typed path.
+    /// Paths which don't exist anymore are skipped.
+    pub fn load_flagged(&mut self) -> Result<()> {
+        let path = self.typed_path();
+        match std::fs::read_to_string(&path) {
+            Ok(files) => {
+                self.menu.flagged.replace_by_string(files);
+                log_line!(
+                    "Loaded {len} flagged files from {path}",
+                    len = self.menu.flagged.len(),
+                    path = path.display()
+                );
+            }
+            Err(error) => log_line!("Couldn't load the flagged files: {error}"),
+        }
+        Ok(())
+    }
+
+    /// Flag the files of the current directory whose content matches the typed pattern.
+    /// Subdirectories are searched if `grep_flag_recursive` is set.
+    pub fn grep_flag(&mut self) -> Result<()> {
+        let pattern = self.menu.input.string();
+        if pattern.is_empty() {
+            return Ok(());
+        }
+        let directory = self.current_tab().directory.path.to_path_buf();
+        let Some(command) = build_tokio_files_greper(&pattern, &directory, grep_flag_recursive())
+        else {
+            log_line!("Grep flag: neither ripgrep nor grep is installed");
+            return Ok(());
+        };
+        log_line!("Searching files matching {pattern}...");
+        files_with_matches(command, pattern, directory, self.fm_sender.clone());
+        Ok(())
+    }
+
+    /// Flag the files found by the grep flag.
+    pub fn flag_files_with_matches(&mut self, pattern: &str, paths: Vec<PathBuf>) {
+        let len = paths.len();
+        paths
+            .into_iter()
+            .for_each(|path| self.menu.flagged.push(path));
+        log_line!("Flagged {len} files matching {pattern}");
+    }
+
+    /// Path typed by the user, with `~` expanded. Relative paths start from the current directory.
+    pub fn typed_path(&self) -> PathBuf {
+        let typed = self.menu.input.string();
+        let path = PathBuf::from(tilde(&typed).as_ref());
+        if path.is_absolute() {
+            path
+        } else {
+            self.current_tab()
+                .directory_of_selected()
+                .map_or_else(|_| path.clone(), |directory| directory.join(&path))
+        }
+    }
+
+    /// Remove the typed extended attribute from the selected file.
+    pub fn remove_xattr(&mut self) -> Result<()> {
+        let path = self.current_tab().current_file()?.path;
+        match remove_xattr(&path, &self.menu.input.string()) {
+            Ok(()) => log_line!(
+                "Extended attribute removed from {path}",
+                path = path.display()
+            ),
+            Err(error) => log_line!("Couldn't remove the extended attribute: {error}"),
+        }
+        Ok(())
+    }
+
     /// Enter the chmod mode where user can chmod a file.
     pub fn set_mode_chmod(&mut self) -> Result<()> {
         if self.current_tab_mut().directory.is_empty() {
@@ -1785,7 +3593,7 @@ impl Status {
 
     /// Compress the flagged files into an archive.
     /// Compression method is chosen by the user.
-    /// The archive is created in the current directory and is named "archive.tar.??" or "archive.zip".
+    /// The archive is created in the current directory and is named from the input, with the extension of the method.
     /// Files which are above the CWD are filtered out since they can't be added to an archive.
     /// Archive creation depends on CWD so we ensure it's set to the selected tab.
     pub fn compress(&mut self) -> Result<()> {
@@ -1795,10 +3603,11 @@ impl Status {
         if files_with_relative_paths.is_empty() {
             return Ok(());
         }
+        let name = self.menu.input.string();
         match self
             .menu
             .compression
-            .compress(files_with_relative_paths, here)
+            .compress(files_with_relative_paths, here, &name)
         {
             Ok(()) => (),
             Err(error) => log_info!("Error compressing files. Error: {error}"),
@@ -1833,20 +3642,63 @@ impl Status {
     /// ie. If you typed `"jpg"` before, it will move to the first file
     /// whose filename contains `"jpg"`.
     /// The current order of files is used.
+    /// In regex mode, every match is flagged when the tab displays a directory.
+    /// An invalid regex is recorded and displayed in the search menu.
     fn search(&mut self) -> Result<()> {
-        let Some(search) = self.build_search_from_input() else {
+        let searched = self.menu.input.string();
+        self.menu.search_error = None;
+        self.unflag_search_matches();
+        if searched.is_empty() {
             self.current_tab_mut().search = Search::empty();
             return Ok(());
+        }
+        let search = match Search::new(&searched, self.menu.search_kind) {
+            Ok(search) => search,
+            Err(error) => {
+                self.menu.search_error = Some(last_error_line(&error));
+                self.current_tab_mut().search = Search::empty();
+                return Ok(());
+            }
         };
-        self.search_and_update(search)
+        self.search_and_update(search)?;
+        if self.menu.search_kind.is_regex()
+            && matches!(self.current_tab().display_mode, Display::Directory)
+        {
+            self.flag_search_matches();
+        }
+        Ok(())
     }
 
-    fn build_search_from_input(&self) -> Option<Search> {
-        let searched = &self.menu.input.string();
-        if searched.is_empty() {
-            return None;
+    /// Add the matches of the regex search to the flagged files.
+    /// Files which weren't flagged before are remembered, so they're unflagged if the search changes.
+    fn flag_search_matches(&mut self) {
+        for path in self.tabs[self.index].search.paths.iter() {
+            if !self.menu.flagged.contains(path) {
+                self.menu.flagged.push(path.to_path_buf());
+                self.menu.search_flagged.push(path.to_path_buf());
+            }
+        }
+    }
+
+    /// Unflag the files flagged by the previous regex search only.
+    fn unflag_search_matches(&mut self) {
+        for path in std::mem::take(&mut self.menu.search_flagged) {
+            self.menu.flagged.remove(&path);
+        }
+    }
+
+    /// Switch the search between substring and regex matching.
+    /// If the search menu is opened, the typed input is searched again.
+    pub fn toggle_search_kind(&mut self) -> Result<()> {
+        self.menu.search_kind = self.menu.search_kind.toggle();
+        if matches!(
+            self.current_tab().menu_mode,
+            Menu::InputCompleted(InputCompleted::Search)
+        ) {
+            self.search()?;
         }
-        Search::new(searched).ok()
+        log_line!("Search: {kind}", kind = self.menu.search_kind);
+        Ok(())
     }
 
     fn search_and_update(&mut self, mut search: Search) -> Result<()> {
@@ -1868,6 +3720,24 @@ impl Status {
         self.search_again()
     }
 
+    /// Display only the flagged files of the current directory, or every file if they were already filtered.
+    /// The flagged files are read when the filter is set.
+    pub fn toggle_flagged_only(&mut self) -> Result<()> {
+        let filter = if matches!(
+            self.current_tab().settings.filter,
+            FilterKind::FlaggedOnly(_)
+        ) {
+            FilterKind::All
+        } else if self.menu.flagged.is_empty() {
+            log_line!("No flagged file");
+            return Ok(());
+        } else {
+            FilterKind::FlaggedOnly(self.menu.flagged.content.clone())
+        };
+        self.current_tab_mut().set_filter(filter)?;
+        self.search_again()
+    }
+
     /// input the typed char and update the filterkind.
     pub fn input_filter(&mut self, c: char) -> Result<()> {
         self.menu.input_insert(c)?;
@@ -2011,3 +3881,15 @@ fn find_keybind_from_fuzzy(line: &str) -> Result<KeyEvent> {
     };
     Ok(key)
 }
+
+/// The last non empty line of the error, where the regex crate describes what's wrong.
+fn last_error_line(error: &anyhow::Error) -> String {
+    error
+        .to_string()
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+        .to_owned()
+}
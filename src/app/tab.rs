@@ -3,6 +3,7 @@ use std::cmp::min;
 use std::iter::{Enumerate, Skip, Take};
 use std::path;
 use std::slice;
+use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -11,13 +12,14 @@ use crate::common::{
     has_last_modification_happened_less_than, path_to_string, row_to_window_index,
 };
 use crate::config::START_FOLDER;
-use crate::io::Args;
-use crate::log_info;
+use crate::event::FmEvents;
+use crate::io::{run_on_cd_command, Args};
 use crate::modes::{
-    Content, ContentWindow, Directory, Display, FileInfo, FileKind, FilterKind, Go, History,
-    IndexToIndex, Menu, Preview, PreviewBuilder, Search, Selectable, SortKind, To, Tree,
-    TreeBuilder, Users,
+    Content, ContentWindow, Directory, Display, FileInfo, FileKind, FilterKind, Go, GroupedRow,
+    History, IndexToIndex, Menu, Preview, PreviewBuilder, Search, Selectable, SortKind, To, Tree,
+    TreeBuilder, Users, Visual,
 };
+use crate::{log_info, log_line};
 
 pub struct TabSettings {
     /// read from command line
@@ -26,6 +28,11 @@ pub struct TabSettings {
     pub filter: FilterKind,
     /// The kind of sort used to display the files.
     pub sort_kind: SortKind,
+    /// Are the files grouped by extension ? Read from session.
+    pub group_by_extension: bool,
+    /// Directory whose hidden files are displayed regardless of `show_hidden`.
+    /// Cleared when leaving it.
+    pub hidden_here: Option<path::PathBuf>,
 }
 
 impl TabSettings {
@@ -33,10 +40,13 @@ impl TabSettings {
         let filter = FilterKind::All;
         let show_hidden = args.all;
         let sort_kind = SortKind::default();
+        let group_by_extension = false;
         Self {
             show_hidden,
             filter,
             sort_kind,
+            group_by_extension,
+            hidden_here: None,
         }
     }
 
@@ -44,6 +54,21 @@ impl TabSettings {
         self.show_hidden = !self.show_hidden;
     }
 
+    /// Are the hidden files of `path` displayed ?
+    /// True if they're displayed everywhere or only in this directory.
+    pub fn shows_hidden_in(&self, path: &path::Path) -> bool {
+        self.show_hidden || self.hidden_here.as_deref() == Some(path)
+    }
+
+    /// Display the hidden files of `path` only or stop doing it.
+    fn toggle_hidden_here(&mut self, path: &path::Path) {
+        if self.hidden_here.as_deref() == Some(path) {
+            self.hidden_here = None;
+        } else {
+            self.hidden_here = Some(path.to_path_buf());
+        }
+    }
+
     /// Apply the filter.
     pub fn set_filter(&mut self, filter: FilterKind) {
         self.filter = filter
@@ -96,6 +121,8 @@ pub struct Tab {
     /// Users & groups
     pub users: Users,
     pub origin_path: Option<std::path::PathBuf>,
+    /// Visual mode, flagging the files between an anchor and the selected file
+    pub visual: Visual,
 }
 
 impl Tab {
@@ -114,12 +141,22 @@ impl Tab {
     /// - doesn't exist
     /// - can't be explored
     /// - has no parent and isn't a directory (which can't happen)
-    pub fn new(args: &Args, height: usize, users: Users) -> Result<Self> {
+    pub fn new(
+        args: &Args,
+        height: usize,
+        users: Users,
+        fm_sender: Arc<Sender<FmEvents>>,
+    ) -> Result<Self> {
         let path = &START_FOLDER.get().context("Startfolder should be set")?;
         let start_dir = Self::start_dir(path)?;
         let settings = TabSettings::new(args);
-        let mut directory =
-            Directory::new(start_dir, &users, &settings.filter, settings.show_hidden)?;
+        let mut directory = Directory::new(
+            start_dir,
+            &users,
+            &settings.filter,
+            settings.show_hidden,
+            fm_sender,
+        )?;
         let display_mode = Display::default();
         let menu_mode = Menu::Nothing;
         let mut window = ContentWindow::new(directory.content.len(), height);
@@ -129,6 +166,7 @@ impl Tab {
         let index = directory.select_file(path);
         let tree = Tree::default();
         let origin_path = None;
+        let visual = Visual::default();
 
         window.scroll_to(index);
         Ok(Self {
@@ -144,6 +182,7 @@ impl Tab {
             tree,
             settings,
             origin_path,
+            visual,
         })
     }
 
@@ -237,6 +276,23 @@ impl Tab {
         Ok(())
     }
 
+    /// Append files of a huge directory read in the background.
+    pub fn extend_directory(&mut self, files: Vec<FileInfo>) {
+        self.directory.extend_loaded(files);
+        if self.display_mode.is_directory() {
+            self.window.set_len(self.directory.content.len());
+        }
+    }
+
+    /// Every file of the directory was read in the background, sort them and keep the selection.
+    pub fn finish_directory_loading(&mut self) {
+        self.directory.finish_loading(&self.settings);
+        if self.display_mode.is_directory() {
+            self.window.set_len(self.directory.content.len());
+            self.scroll_to(self.directory.index);
+        }
+    }
+
     /// Refresh the view if files were modified in current directory.
     /// If a refresh occurs, tries to select the same file as before.
     /// If it can't, the first file (`.`) is selected.
@@ -250,12 +306,24 @@ impl Tab {
             Display::Tree => self.tree.has_modified_dirs(),
             Display::Fuzzy => false,
         } {
-            self.refresh_and_reselect_file()
+            if let Some(changes) = self.refresh_with_changes()? {
+                log_line!("{changes}");
+            }
+            Ok(())
         } else {
             Ok(())
         }
     }
 
+    /// Refresh the view, keeping the selected file.
+    /// Returns a description of the files added or removed since the last refresh
+    /// and the variation of their size, None if nothing changed.
+    pub fn refresh_with_changes(&mut self) -> Result<Option<String>> {
+        let snapshot = self.directory.snapshot();
+        self.refresh_and_reselect_file()?;
+        Ok(self.directory.changes_since(&snapshot))
+    }
+
     /// Change the display mode.
     pub fn set_display_mode(&mut self, new_display_mode: Display) {
         self.search.reset_paths();
@@ -270,7 +338,7 @@ impl Tab {
         let path = self.directory.path.clone();
         let users = &self.users;
         self.tree = TreeBuilder::new(path.clone(), users)
-            .with_hidden(self.settings.show_hidden)
+            .with_hidden(self.settings.shows_hidden_in(&path))
             .with_filter_kind(&self.settings.filter)
             .with_sort_kind(sort_kind)
             .build();
@@ -359,7 +427,7 @@ impl Tab {
         match self.display_mode {
             Display::Preview => (),
             Display::Directory => {
-                let index = self.directory.select_file(&selected_path);
+                let index = self.directory.reselect_file(&selected_path);
                 self.scroll_to(index)
             }
             Display::Tree => {
@@ -406,6 +474,19 @@ impl Tab {
         Ok(())
     }
 
+    /// Display or hide the hidden files of the current directory only.
+    /// It's reverted when leaving the directory.
+    pub fn toggle_hidden_here(&mut self) -> Result<()> {
+        let path = self.directory.path.clone();
+        self.settings.toggle_hidden_here(&path);
+        self.directory.reset_files(&self.settings, &self.users)?;
+        self.window.reset(self.directory.content.len());
+        if self.display_mode.is_tree() {
+            self.make_tree(None)
+        }
+        Ok(())
+    }
+
     /// Set the line index to `index` and scroll there.
     pub fn scroll_to(&mut self, index: usize) {
         self.window.scroll_to(index);
@@ -416,6 +497,7 @@ impl Tab {
     /// by kind : directory first, files next, in alphanumeric order
     /// by filename,
     /// by date of modification,
+    /// by date of access,
     /// by size,
     /// by extension.
     /// The first letter is used to identify the method.
@@ -432,27 +514,77 @@ impl Tab {
         Ok(())
     }
 
+    /// Reverse the order of the current sort, or restore it, and sort the files again.
+    /// The reversal is kept when another kind of sort is chosen.
+    pub fn toggle_sort_reversed(&mut self) -> Result<()> {
+        let sort_kind = &mut self.settings.sort_kind;
+        sort_kind.set_reversed(!sort_kind.is_reversed());
+        if self.directory.content.is_empty() {
+            return Ok(());
+        }
+        match self.display_mode {
+            Display::Directory => self.resort_directory()?,
+            Display::Tree => self.resort_tree(),
+            _ => (),
+        }
+        Ok(())
+    }
+
     fn sort_directory(&mut self, c: char) -> Result<()> {
-        let path = self.current_file()?.path;
         self.settings.update_sort_from_char(c);
+        self.resort_directory()
+    }
+
+    fn resort_directory(&mut self) -> Result<()> {
+        let path = self.current_file()?.path;
         self.directory.sort(&self.settings.sort_kind);
+        self.settings
+            .sort_kind
+            .log_if_access_times_look_stale(&self.directory.content);
+        if self.settings.group_by_extension {
+            self.directory.group_by_extension();
+        }
         self.normal_go_top();
         self.directory.select_file(&path);
         Ok(())
     }
 
+    /// Group (or ungroup) the files by extension and reselect the same file.
+    pub fn set_group_by_extension(&mut self, group_by_extension: bool) {
+        self.settings.group_by_extension = group_by_extension;
+        let selected_path = self.directory.selected().map(|file| file.path.clone());
+        self.directory.sort(&self.settings.sort_kind);
+        if group_by_extension {
+            self.directory.group_by_extension();
+        }
+        let Some(selected_path) = selected_path else {
+            return;
+        };
+        let index = self.directory.select_file(&selected_path);
+        if matches!(self.display_mode, Display::Directory) {
+            self.window.reset(self.directory.content.len());
+            self.window.scroll_to(index);
+        }
+    }
+
     fn sort_tree(&mut self, c: char) {
         self.settings.update_sort_from_char(c);
+        self.resort_tree();
+    }
+
+    fn resort_tree(&mut self) {
         let selected_path = self.tree.selected_path().to_owned();
         self.make_tree(Some(self.settings.sort_kind));
         self.tree.go(To::Path(&selected_path));
     }
 
     pub fn set_sortkind_per_mode(&mut self) {
+        let reversed = self.settings.sort_kind.is_reversed();
         self.settings.sort_kind = match self.display_mode {
             Display::Tree => SortKind::tree_default(),
             _ => SortKind::default(),
         };
+        self.settings.sort_kind.set_reversed(reversed);
     }
 
     pub fn cd_to_file(&mut self, path: &path::Path) -> Result<()> {
@@ -479,6 +611,7 @@ impl Tab {
     /// Set the pathcontent to a new path.
     /// Reset the window.
     /// Add the last path to the history of visited paths.
+    /// Run the `on_cd_command` of the config file if the directory changed.
     /// Does nothing in preview or flagged display mode.
     pub fn cd(&mut self, path: &path::Path) -> Result<()> {
         if self.display_mode.is_preview() {
@@ -492,9 +625,16 @@ impl Tab {
                 return Ok(());
             }
         }
+        let has_changed = self.directory.path.as_ref() != path;
+        if has_changed {
+            self.settings.hidden_here = None;
+        }
         self.history.push(&self.current_file()?.path);
         self.directory
             .change_directory(path, &self.settings, &self.users)?;
+        if has_changed {
+            run_on_cd_command(path);
+        }
         if self.display_mode.is_tree() {
             self.make_tree(Some(self.settings.sort_kind));
             self.window.reset(self.tree.displayable().lines().len());
@@ -623,6 +763,20 @@ impl Tab {
         self.window.scroll_up_one(self.directory.index)
     }
 
+    /// Move the selected file one row up or down in the displayed order.
+    /// Nothing is modified on disk and the order is reset by a refresh or a sort.
+    pub fn reorder_selected(&mut self, up: bool) {
+        if !matches!(self.display_mode, Display::Directory) {
+            return;
+        }
+        self.directory.move_selected(up);
+        if up {
+            self.window.scroll_up_one(self.directory.index)
+        } else {
+            self.window.scroll_down_one(self.directory.index)
+        }
+    }
+
     /// Move to the top of the current directory.
     pub fn normal_go_top(&mut self) {
         self.directory.select_index(0);
@@ -745,20 +899,54 @@ impl Tab {
         }
     }
 
-    /// Move 30 lines up or an image in Ueberzug.
+    /// Display the next allowed number of bytes per line if a binary file is previewed.
+    /// Returns true if the preview was modified.
+    pub fn cycle_binary_bytes_per_row(&mut self) -> bool {
+        let Preview::Binary(binary) = &mut self.preview else {
+            return false;
+        };
+        binary.cycle_bytes_per_row();
+        self.window.reset(self.preview.len());
+        true
+    }
+
+    /// Fold or unfold the selected folder of an archive previewed as a tree.
+    /// Returns true if a tree is previewed.
+    pub fn preview_tree_toggle_fold(&mut self) -> bool {
+        if !matches!(self.display_mode, Display::Preview) {
+            return false;
+        }
+        let Preview::Tree(tree) = &mut self.preview else {
+            return false;
+        };
+        tree.toggle_fold(&self.users);
+        self.window.set_len(tree.display_len());
+        true
+    }
+
+    /// Move 30 lines up, an image in Ueberzug or to the previous node of a tree.
     pub fn preview_page_up(&mut self) {
         match &mut self.preview {
             Preview::Ueberzug(ref mut image) => image.up_one_row(),
+            Preview::Tree(ref mut tree) => {
+                tree.go(To::Prev);
+                self.window.scroll_up_one(tree.displayable().index());
+            }
             Preview::Binary(_) => self.window.preview_page_up(self.preview_binary_scroll()),
             _ => self.window.preview_page_up(self.preview_scroll()),
         }
     }
 
     /// Move down 30 rows except for Ueberzug where it moves 1 image down
+    /// and trees where it moves to the next node.
     pub fn preview_page_down(&mut self) {
         let len = self.preview.len();
         match &mut self.preview {
             Preview::Ueberzug(ref mut image) => image.down_one_row(),
+            Preview::Tree(ref mut tree) => {
+                tree.go(To::Next);
+                self.window.scroll_down_one(tree.displayable().index());
+            }
             Preview::Binary(_) => self
                 .window
                 .preview_page_down(self.preview_binary_scroll(), len),
@@ -769,11 +957,31 @@ impl Tab {
     /// Select a clicked row in display directory
     pub fn normal_select_row(&mut self, row: u16) {
         let screen_index = row_to_window_index(row);
-        let index = screen_index + self.window.top;
+        let index = if self.settings.group_by_extension {
+            let Some(index) = self.grouped_index_from_screen(screen_index) else {
+                return;
+            };
+            index
+        } else {
+            screen_index + self.window.top
+        };
         self.directory.select_index(index);
         self.window.scroll_to(index);
     }
 
+    /// Index in content of the file displayed at this screen row in grouped view.
+    /// None if the row is a group header.
+    fn grouped_index_from_screen(&self, screen_index: usize) -> Option<usize> {
+        let rows = self.directory.grouped_rows();
+        let top_row = self
+            .directory
+            .grouped_top_row(&rows, self.window.top, self.window.height);
+        match rows.get(top_row + screen_index)? {
+            GroupedRow::File(index) => Some(*index),
+            GroupedRow::Header(_) => None,
+        }
+    }
+
     /// Select a clicked row in display tree
     pub fn tree_select_row(&mut self, row: u16) -> Result<()> {
         let screen_index = row_to_window_index(row);
@@ -2,6 +2,8 @@
 pub const CONFIG_FOLDER: &str = "~/.config/fm";
 /// Configuration file path
 pub const CONFIG_PATH: &str = "~/.config/fm/config.yaml";
+/// Folder of keybindings fragments, merged after the config file in alphabetical order.
+pub const KEYBINDS_FOLDER: &str = "~/.config/fm/keybinds.d";
 /// Session file path
 pub const SESSION_PATH: &str = "~/.config/fm/session.yaml";
 /// Filepath of the opener config file
@@ -24,12 +26,18 @@ pub const TRASH_FOLDER_INFO: &str = "~/.local/share/Trash/info";
 pub const TRASH_INFO_EXTENSION: &str = ".trashinfo";
 /// File where marks are stored.
 pub const MARKS_FILEPATH: &str = "~/.config/fm/marks.cfg";
+/// Bookmarks file of ranger, imported as marks.
+pub const RANGER_BOOKMARKS_FILEPATH: &str = "~/.local/share/ranger/bookmarks";
 /// Temporary folder used when bulkrenaming files
 pub const TMP_FOLDER_PATH: &str = "/tmp";
 /// Video thumbnails
 pub const TMP_THUMBNAILS_DIR: &str = "/tmp/fm-thumbnails";
+/// Entries extracted from archives to be opened. A folder per fm process is created in it.
+pub const TMP_ARCHIVE_ENTRIES_DIR: &str = "/tmp/fm-archive-entries";
 /// setsid. Installed in most distros
 pub const SETSID: &str = "setsid";
+/// tmux, used to open shells in a new pane or window when fm runs inside tmux
+pub const TMUX: &str = "tmux";
 /// Default terminal application used when openening a program in shell or starting a new shell
 pub const DEFAULT_TERMINAL_APPLICATION: &str = "st";
 /// Default terminal flag to run a command when ran
@@ -81,20 +89,26 @@ pub const HARDCODED_SHORTCUTS: [&str; 9] = [
     "/usr",
     "/var",
 ];
+/// XDG user directories (Documents, Downloads, Pictures...) with their localized names.
+pub const XDG_USER_DIRS_PATH: &str = "~/.config/user-dirs.dirs";
 pub const RG_EXECUTABLE: &str = "rg --line-number --color=never .";
 pub const GREP_EXECUTABLE: &str = "grep -rI --line-number .";
+pub const RG_FILES_WITH_MATCHES: &str =
+    "rg --files-with-matches --color=never --hidden --no-ignore";
+pub const GREP_FILES_WITH_MATCHES: &str = "grep -lIs";
 pub const SSHFS_EXECUTABLE: &str = "sshfs";
 pub const NOTIFY_EXECUTABLE: &str = "notity-send";
 pub const EJECT_EXECUTABLE: &str = "eject";
 /// Encrypted devices bind description
 pub const ENCRYPTED_DEVICE_BINDS: &str = "m: mount   --   u: unmount   --   g: go to mount point";
 /// Sort presentation for the second window
-pub const SORT_LINES: [&str; 9] = [
+pub const SORT_LINES: [&str; 10] = [
     "Type the initial",
     "",
     "k:  by kind (default)",
     "n:  by name",
     "m:  by modification time",
+    "a:  by access time",
     "s:  by size",
     "e:  by extension",
     "",
@@ -106,6 +120,73 @@ pub const REMOTE_LINES: [&str; 4] = [
     "",
     "username hostname remote_path port",
 ];
+/// Archive name presentation for the second window
+pub const ARCHIVE_NAME_LINES: [&str; 4] = [
+    "Type the name of the archive",
+    "The extension of the compression method is added",
+    "",
+    "An empty name creates `archive`. Existing files are never overwritten.",
+];
+/// Numbered rename presentation for the second window
+pub const NUMBERED_RENAME_LINES: [&str; 4] = [
+    "Type the first number and the width of the sequence, like `1 2` for 01_, 02_...",
+    "Both are optional. The default width is the number of digits of the last number, at least 2.",
+    "",
+    "Flagged files of the current directory are numbered in their displayed order.",
+];
+/// Extension rename presentation for the second window
+pub const EXTENSION_RENAME_LINES: [&str; 4] = [
+    "Type the new extension of the flagged files, like `jpg`",
+    "Base names are kept. An empty extension removes it.",
+    "",
+    "Files whose new name is already used are skipped.",
+];
+/// Extended attribute setter presentation for the second window
+pub const SET_XATTR_LINES: [&str; 4] = [
+    "Type the name and the value of the extended attribute, like `user.comment=some text`",
+    "An existing attribute is replaced.",
+    "",
+    "Normal users can only set attributes of the `user.` namespace.",
+];
+/// Extended attribute remover presentation for the second window
+pub const REMOVE_XATTR_LINES: [&str; 1] = ["Type the name of the extended attribute to remove"];
+/// Flagged files saver presentation for the second window
+pub const SAVE_FLAGGED_LINES: [&str; 3] = [
+    "Type the path of the file where the flagged files are saved, one absolute path per line",
+    "An existing file is overwritten. Relative paths start from the current directory.",
+    "Load them back later with the action `FlaggedLoad`.",
+];
+/// Flagged files loader presentation for the second window
+pub const LOAD_FLAGGED_LINES: [&str; 2] = [
+    "Type the path of a file saved with `FlaggedSave`",
+    "The flagged files are replaced by its content. Paths which don't exist anymore are skipped.",
+];
+/// Grep flag presentation for the second window
+pub const GREP_FLAG_LINES: [&str; 3] = [
+    "Type a pattern, every file of the current directory whose content matches it is flagged",
+    "It's a regular expression, searched with ripgrep or grep.",
+    "Subdirectories are searched if `grep_flag_recursive` is set in the config file.",
+];
+pub const COPY_AS_LINES: [&str; 3] = [
+    "Type the name of the copy, created next to the selected file",
+    "Large files are copied in the background with a progress bar.",
+    "If a file already has this name, you can overwrite it or choose another name. Directories are never overwritten.",
+];
+pub const SPLIT_FILE_LINES: [&str; 3] = [
+    "Type the size of the parts, like 100M or 4G",
+    "The parts are created next to the file: file.part001, file.part002...",
+    "Join them back with the action JoinParts.",
+];
+pub const CONFLICT_RENAME_LINES: [&str; 3] = [
+    "Type the name of the copied or moved file in the destination",
+    "The input is filled with a name which isn't used.",
+    "The other conflicts are resolved next.",
+];
+pub const CONFIRM_PROTECTED_LINES: [&str; 3] = [
+    "A protected file is modified. Its full path is displayed in the log line.",
+    "Type it to confirm, every protected file is confirmed one at a time.",
+    "Anything else cancels.",
+];
 pub const CLOUD_NEWDIR_LINES: [&str; 1] = ["Create a new directory in current cloud path"];
 /// Chmod presentation for the second window
 pub const CHMOD_LINES: [&str; 5] = [
@@ -149,6 +230,23 @@ pub const SHELL_LINES: [&str; 13] = [
     "%t: execute the command in the same window",
     "%c: the current clipboard as a string",
 ];
+/// Streamed shell command presentation for second window
+pub const SHELL_STREAM_LINES: [&str; 8] = [
+    "Type a shell command, its output is displayed while it runs",
+    "",
+    "Pipes, redirections ( | < > >> ) and shell specific syntax (*) aren't supported.",
+    "The same expansions as the shell command are available.",
+    "`sudo` commands can't be streamed and are executed normally.",
+    "",
+    "Press the reset key (Escape) to stop the command.",
+    "The command is also stopped if the preview is closed.",
+];
+pub const SHELL_DRY_RUN_LINES: [&str; 4] = [
+    "Type a shell command, it's expanded and displayed before running",
+    "",
+    "The same expansions as the shell command are available.",
+    "Check the flagged files substitutions then confirm with y.",
+];
 /// Nvim address setter presentation for second window
 pub const NVIM_ADDRESS_LINES: [&str; 4] = [
     "Type the Neovim RPC address.",
@@ -162,12 +260,30 @@ pub const REGEX_LINES: [&str; 3] = [
     "",
     "Flag every file in current directory matching the typed regex",
 ];
+/// Glob presentation for second window
+pub const GLOB_LINES: [&str; 3] = [
+    "Type a glob like *.log or build_*",
+    "",
+    "Flag every file in current directory matching the typed glob. Flags are kept.",
+];
+/// Mount by label presentation for second window
+pub const MOUNT_LABEL_LINES: [&str; 3] = [
+    "Type the label or the UUID of a filesystem",
+    "",
+    "The device will be mounted like a removable device. It may require a sudo password.",
+];
 /// Newdir presentation for second window
 pub const NEWDIR_LINES: [&str; 3] = [
     "mkdir a new directory",
     "",
     "Nothing is done if the directory already exists",
 ];
+/// Newdir and enter presentation for second window
+pub const NEWDIR_AND_ENTER_LINES: [&str; 3] = [
+    "mkdir a new directory and cd into it",
+    "",
+    "Nested directories like a/b/c are created, the deepest one is entered",
+];
 /// New file presentation for second window
 pub const NEWFILE_LINES: [&str; 3] = [
     "touch a new file",
@@ -196,6 +312,8 @@ pub const RSVG_CONVERT: &str = "rsvg-convert";
 pub const JUPYTER: &str = "jupyter";
 /// pandoc. used to preview .doc & .odb documents
 pub const PANDOC: &str = "pandoc";
+/// man. used to format man pages
+pub const MAN: &str = "man";
 /// isoinfo. used to preview iso file content
 pub const ISOINFO: &str = "isoinfo";
 /// socket file explorer
@@ -206,6 +324,8 @@ pub const MKDIR: &str = "mkdir";
 pub const MOUNT: &str = "mount";
 /// umount is used to mount usb removable devices
 pub const UMOUNT: &str = "umount";
+/// udisksctl is used to power off usb removable devices after unmounting them
+pub const UDISKSCTL: &str = "udisksctl";
 /// lsblk is used to get mountpoints, info about encrypted drives
 pub const LSBLK: &str = "lsblk";
 /// cryptsetup is used to mount encrypted drives
@@ -218,8 +338,26 @@ pub const UDEVADM: &str = "udevadm";
 pub const NVIM: &str = "nvim";
 /// bsdtar executable, used to display common archive content
 pub const BSDTAR: &str = "bsdtar";
+/// ImageMagick 7 executable, used to convert images
+pub const MAGICK: &str = "magick";
+/// ImageMagick 6 executable, used to convert images
+pub const CONVERT: &str = "convert";
+/// xdotool, used to give the focus back to fm after opening a file in background
+pub const XDOTOOL: &str = "xdotool";
 /// 7z executable, used to display 7z archive content
 pub const SEVENZ: &str = "7z";
+/// Wallpaper setters tried in this order under Wayland, `{path}` is replaced by the image path
+pub const WAYLAND_WALLPAPER_COMMANDS: [&str; 3] = [
+    "swww img {path}",
+    "swaybg -m fill -i {path}",
+    "gsettings set org.gnome.desktop.background picture-uri file://{path}",
+];
+/// Wallpaper setters tried in this order under X11, `{path}` is replaced by the image path
+pub const X11_WALLPAPER_COMMANDS: [&str; 3] = [
+    "feh --bg-fill {path}",
+    "nitrogen --set-zoom-fill --save {path}",
+    "gsettings set org.gnome.desktop.background picture-uri file://{path}",
+];
 /// libreoffice executable
 pub const LIBREOFFICE: &str = "libreoffice";
 /// lazygit
@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use crate::common::UtfWidth;
+use crate::common::{collapse_home, UtfWidth};
 
 /// Shorten a path to be displayed in 50 chars or less.
 /// Each element of the path is shortened if needed.
@@ -24,6 +24,14 @@ impl PathShortener {
         self
     }
 
+    /// Replace the home directory by `~` if `collapse` is true.
+    pub fn with_home_collapsed(mut self, collapse: bool) -> Self {
+        if collapse {
+            self.path_str = collapse_home(&self.path_str).into_owned();
+        }
+        self
+    }
+
     pub fn shorten(self) -> String {
         if self.path_str.utf_width() < self.size {
             return self.path_str;
@@ -4,14 +4,17 @@ use std::fs::{metadata, File};
 use std::io::{BufRead, Write};
 use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::str::FromStr;
 
 use anyhow::{Context, Result};
 use copypasta::{ClipboardContext, ClipboardProvider};
 use sysinfo::Disk;
 use unicode_segmentation::UnicodeSegmentation;
+use walkdir::WalkDir;
 
-use crate::common::CONFIG_FOLDER;
+use crate::common::{CONFIG_FOLDER, TMP_ARCHIVE_ENTRIES_DIR};
+use crate::config::{clipboard_command, clipboard_paste_command};
 use crate::modes::{human_size, nvim, ContentWindow, Users};
 use crate::{log_info, log_line};
 
@@ -47,6 +50,17 @@ pub fn disk_space(disks: &[&Disk], path: &Path) -> String {
     disk_space_used(disk_used_by_path(disks, path))
 }
 
+/// Size in bytes of a file or of every file of a directory, recursively.
+/// Symbolic links aren't followed and unreadable files are ignored.
+pub fn recursive_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok()?.metadata().ok())
+        .filter(|metadata| !metadata.is_dir())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
 /// Print the final path & save it to a temporary file.
 /// Must be called last since we erase temporary with similar name
 /// before leaving.
@@ -122,15 +136,32 @@ pub fn extract_lines(content: String) -> Vec<String> {
     content.lines().map(|line| line.to_string()).collect()
 }
 
+/// Read the clipboard with the configured `clipboard_paste_command` if any, with the clipboard library otherwise.
 pub fn get_clipboard() -> Option<String> {
+    if let Some((exe, args)) = clipboard_paste_command().split_first() {
+        let output = Command::new(exe)
+            .args(args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        return String::from_utf8(output.stdout).ok();
+    }
     let Ok(mut ctx) = ClipboardContext::new() else {
         return None;
     };
     ctx.get_contents().ok()
 }
 
+/// Write the content in the clipboard with the configured `clipboard_command` if any, with the clipboard library otherwise.
 pub fn set_clipboard(content: String) {
     log_info!("copied to clipboard: {}", content);
+    if let Some((exe, args)) = clipboard_command().split_first() {
+        if let Err(error) = set_clipboard_with_command(exe, args, &content) {
+            log_info!("clipboard command {exe} failed: {error}");
+        }
+        return;
+    }
     let Ok(mut ctx) = ClipboardContext::new() else {
         return;
     };
@@ -141,6 +172,24 @@ pub fn set_clipboard(content: String) {
     let _ = ctx.get_contents();
 }
 
+/// Write the content to the stdin of the command and wait for it.
+/// Most clipboard commands (xclip, wl-copy) fork in background to serve the content.
+fn set_clipboard_with_command(exe: &str, args: &[String], content: &str) -> Result<()> {
+    let mut child = Command::new(exe)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .context("clipboard command: stdin should be piped")?
+        .write_all(content.as_bytes())?;
+    child.wait()?;
+    Ok(())
+}
+
 /// Copy the filename to the clipboard. Only the filename.
 pub fn filename_to_clipboard(path: &std::path::Path) {
     let Some(filename) = path.file_name() else {
@@ -156,6 +205,22 @@ pub fn filepath_to_clipboard(path: &std::path::Path) {
     set_clipboard(path)
 }
 
+/// Copy the image itself to the clipboard, not its path.
+/// The image is decoded and its pixels are sent to the clipboard,
+/// allowing to paste it in another application.
+pub fn image_to_clipboard(path: &std::path::Path) -> Result<()> {
+    let image = image::open(path)?.to_rgba8();
+    let (width, height) = image.dimensions();
+    let image_data = arboard::ImageData {
+        width: width as usize,
+        height: height as usize,
+        bytes: Cow::Owned(image.into_raw()),
+    };
+    arboard::Clipboard::new()?.set_image(image_data)?;
+    log_info!("copied image to clipboard: {path}", path = path.display());
+    Ok(())
+}
+
 /// Convert a row into a `crate::fm::ContentWindow` index.
 /// Just remove the header rows.
 pub fn row_to_window_index(row: u16) -> usize {
@@ -179,7 +244,7 @@ pub fn is_sudo_command(executable: &str) -> bool {
 pub fn open_in_current_neovim(path: &Path, nvim_server: &str) {
     let command = &format!(
         "<esc>:e {path}<cr><esc>:set number<cr><esc>:close<cr>",
-        path = path.display()
+        path = escape_for_nvim_input(path)
     );
     log_info!(
         "open_in_current_neovim {nvim_server} {path} {command}",
@@ -197,6 +262,45 @@ pub fn open_in_current_neovim(path: &Path, nvim_server: &str) {
     }
 }
 
+/// Open the paths in neovim as its argument list, allowing `:next` and `:prev` to navigate between them.
+/// A single path is opened with [`open_in_current_neovim`].
+pub fn open_multiple_in_current_neovim(paths: &[PathBuf], nvim_server: &str) {
+    if let [path] = paths {
+        return open_in_current_neovim(path, nvim_server);
+    }
+    let args = paths
+        .iter()
+        .map(|path| escape_for_nvim_input(path))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let command = &format!("<esc>:args {args}<cr><esc>:set number<cr><esc>:close<cr>");
+    log_info!("open_multiple_in_current_neovim {nvim_server} {command}");
+    match nvim(nvim_server, command) {
+        Ok(()) => log_line!(
+            "Opened {nb} files in neovim at {nvim_server}",
+            nb = paths.len()
+        ),
+        Err(error) => log_line!("Couldn't open files in neovim. Error {error:?}"),
+    }
+}
+
+/// Escape a path like `fnameescape` does, so it's read as a single filename by an ex command.
+/// `<` is replaced by `<lt>` since the command is sent as keys and `<...>` would be read as a key.
+fn escape_for_nvim_input(path: &Path) -> String {
+    let mut escaped = String::new();
+    for c in path.display().to_string().chars() {
+        if " \t\n*?[{`$\\%#'\"|!<".contains(c) {
+            escaped.push('\\');
+        }
+        if c == '<' {
+            escaped.push_str("<lt>");
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
 /// Creates a random string.
 /// The string starts with `fm-` and contains 7 random alphanumeric characters.
 pub fn random_name() -> String {
@@ -217,7 +321,14 @@ pub fn clear_tmp_files() {
     read_dir
         .filter_map(|e| e.ok())
         .filter(|e| e.file_name().to_string_lossy().starts_with("fm_thumbnail"))
-        .for_each(|e| std::fs::remove_file(e.path()).unwrap_or_default())
+        .for_each(|e| std::fs::remove_file(e.path()).unwrap_or_default());
+    let _ = std::fs::remove_dir_all(tmp_archive_entries_dir());
+}
+
+/// Folder where this fm process extracts the archive entries it opens.
+/// It's removed when fm exits.
+pub fn tmp_archive_entries_dir() -> PathBuf {
+    Path::new(TMP_ARCHIVE_ENTRIES_DIR).join(std::process::id().to_string())
 }
 
 /// True if the directory is empty,
@@ -361,12 +472,61 @@ pub fn path_to_config_folder() -> Result<PathBuf> {
     Ok(std::path::PathBuf::from_str(tilde(CONFIG_FOLDER).borrow())?)
 }
 
-fn home_dir() -> Option<PathBuf> {
+/// The home directory of the current user, read from `$HOME`.
+pub fn home_dir() -> Option<PathBuf> {
     std::env::var_os("HOME")
         .and_then(|h| if h.is_empty() { None } else { Some(h) })
         .map(PathBuf::from)
 }
 
+/// Collapse /home/user/Downloads to ~/Downloads where user is the current user.
+/// Paths outside of the home directory are unchanged.
+pub fn collapse_home(path_str: &str) -> Cow<'_, str> {
+    let Some(home) = home_dir() else {
+        return path_str.into();
+    };
+    let Some(after_home) = path_str.strip_prefix(home.to_string_lossy().as_ref()) else {
+        return path_str.into();
+    };
+    if after_home.is_empty() || after_home.starts_with('/') {
+        format!("~{after_home}").into()
+    } else {
+        path_str.into()
+    }
+}
+
+/// Collapse the home directory to `~` wherever a path starting with it appears in a text,
+/// like the messages of the log line. See [`collapse_home`].
+pub fn collapse_home_in_text(text: &str) -> Cow<'_, str> {
+    let Some(home) = home_dir() else {
+        return text.into();
+    };
+    let home = home.to_string_lossy();
+    if !text.contains(home.as_ref()) {
+        return text.into();
+    }
+    let is_path_char = |c: char| c.is_alphanumeric() || matches!(c, '/' | '-' | '_' | '.');
+    let mut collapsed = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(home.as_ref()) {
+        collapsed.push_str(&rest[..start]);
+        let after_home = &rest[start + home.len()..];
+        let starts_path = !collapsed.chars().last().is_some_and(is_path_char);
+        let ends_home = after_home
+            .chars()
+            .next()
+            .is_none_or(|c| c == '/' || !is_path_char(c));
+        if starts_path && ends_home {
+            collapsed.push('~');
+        } else {
+            collapsed.push_str(&home);
+        }
+        rest = after_home;
+    }
+    collapsed.push_str(rest);
+    collapsed.into()
+}
+
 /// Expand ~/Downloads to /home/user/Downloads where user is the current user.
 /// Copied from <https://gitlab.com/ijackson/rust-shellexpand/-/blob/main/src/funcs.rs?ref_type=heads#L673>
 pub fn tilde(input_str: &str) -> Cow<str> {
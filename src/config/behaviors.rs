@@ -0,0 +1,47 @@
+/// Where a shell is opened when fm runs inside tmux. Read from `shell_in_tmux` in the config file.
+/// Outside of tmux, the shell always replaces fm in its terminal until it exits.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum ShellInTmux {
+    /// The shell replaces fm in its terminal until it exits.
+    #[default]
+    Replace,
+    /// A new tmux pane below fm.
+    Split,
+    /// A new tmux window.
+    Window,
+    /// A new window of the configured terminal emulator.
+    NewTerminal,
+}
+
+impl ShellInTmux {
+    pub fn from_config(value: &str) -> Self {
+        match value {
+            "split" => Self::Split,
+            "window" => Self::Window,
+            "new-terminal" => Self::NewTerminal,
+            _ => Self::Replace,
+        }
+    }
+}
+
+/// What pressing enter on an archive does. Read from `archive_enter` in the config file.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum ArchiveEnter {
+    /// Open it with its configured opener.
+    #[default]
+    Open,
+    /// Preview its content as a tree.
+    Browse,
+    /// Extract it next to it, whatever its opener.
+    Extract,
+}
+
+impl ArchiveEnter {
+    pub fn from_config(value: &str) -> Self {
+        match value {
+            "browse" => Self::Browse,
+            "extract" => Self::Extract,
+            _ => Self::Open,
+        }
+    }
+}
@@ -1,11 +1,15 @@
-use std::{fs::File, path};
+use std::{
+    fs::{read_dir, File},
+    path,
+};
 
 use anyhow::Result;
 use ratatui::style::{Color, Style};
-use serde_yml::{from_reader, Value};
+use serde_yml::{from_reader, Mapping, Value};
 
 use crate::common::{
     is_in_path, tilde, CONFIG_PATH, DEFAULT_TERMINAL_APPLICATION, DEFAULT_TERMINAL_FLAG,
+    KEYBINDS_FOLDER,
 };
 use crate::config::{Bindings, ColorG};
 use crate::io::color_to_style;
@@ -39,12 +43,57 @@ impl Config {
     /// Updates the config from  a configuration content.
     fn update_from_config(&mut self, yaml: &Value) -> Result<()> {
         self.binds.update_normal(&yaml["keys"]);
-        self.binds.update_custom(&yaml["custom"]);
+        self.binds.update_mouse(&yaml["mouse"]);
         self.update_terminal(&yaml["terminal"]);
         self.update_terminal_flag(&yaml["terminal_emulator_flags"]);
         Ok(())
     }
 
+    /// Updates the keybindings from every yaml fragment of `~/.config/fm/keybinds.d`, in alphabetical order.
+    /// Like the config file, a fragment may have a `keys`, a `custom` and a `mouse` section. Later files win.
+    /// The `custom` sections are merged into `custom` which is applied once every file is read.
+    /// Unreadable or invalid fragments are logged and skipped.
+    fn update_binds_from_fragments(&mut self, custom: &mut Mapping) {
+        let Ok(entries) = read_dir(tilde(KEYBINDS_FOLDER).as_ref()) else {
+            return;
+        };
+        let mut fragments: Vec<path::PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "yaml" || extension == "yml")
+            })
+            .collect();
+        fragments.sort();
+        for fragment in fragments {
+            let yaml = match File::open(&fragment).map(from_reader::<File, Value>) {
+                Ok(Ok(yaml)) => yaml,
+                Ok(Err(error)) => {
+                    crate::log_info!(
+                        "Invalid keybindings fragment {fragment}: {error}",
+                        fragment = fragment.display()
+                    );
+                    continue;
+                }
+                Err(error) => {
+                    crate::log_info!(
+                        "Couldn't read keybindings fragment {fragment}: {error}",
+                        fragment = fragment.display()
+                    );
+                    continue;
+                }
+            };
+            crate::log_info!(
+                "Keybindings fragment {fragment}",
+                fragment = fragment.display()
+            );
+            self.binds.update_normal(&yaml["keys"]);
+            merge_custom(custom, &yaml["custom"]);
+            self.binds.update_mouse(&yaml["mouse"]);
+        }
+    }
+
     /// First we try to use the current terminal. If it's a fake one (ie. inside neovim float term),
     /// we look for the configured one,
     /// else nothing is done.
@@ -80,23 +129,40 @@ impl Config {
     }
 }
 
+/// Merge a `custom` section into the custom keybindings. A key bound again takes its new command.
+fn merge_custom(custom: &mut Mapping, yaml: &Value) {
+    if let Some(mappings) = yaml.as_mapping() {
+        for (key, command) in mappings {
+            custom.insert(key.clone(), command.clone());
+        }
+    }
+}
+
 /// Returns a config with values from :
 ///
 /// 1. hardcoded values
 ///
 /// 2. configured values from `~/.config/fm/config_file_name.yaml` if those files exists.
 ///
+/// 3. keybindings from the fragments of `~/.config/fm/keybinds.d/*.yaml`.
+///
+/// Custom keybindings of the config file and the fragments are applied last, all at once.
+///
 /// If the config file is poorly formated its simply ignored.
 pub fn load_config(path: &str) -> Result<Config> {
     let mut config = Config::default();
-    let Ok(file) = File::open(path::Path::new(&tilde(path).to_string())) else {
-        crate::log_info!("Couldn't read config file at {path}");
-        return Ok(config);
-    };
-    let Ok(yaml) = from_reader(file) else {
-        return Ok(config);
-    };
-    let _ = config.update_from_config(&yaml);
+    let mut custom = Mapping::new();
+    match File::open(path::Path::new(&tilde(path).to_string())) {
+        Ok(file) => {
+            if let Ok(yaml) = from_reader(file) {
+                let _ = config.update_from_config(&yaml);
+                merge_custom(&mut custom, &yaml["custom"]);
+            }
+        }
+        Err(_) => crate::log_info!("Couldn't read config file at {path}"),
+    }
+    config.update_binds_from_fragments(&mut custom);
+    config.binds.update_custom(&Value::Mapping(custom));
     Ok(config)
 }
 
@@ -223,6 +289,8 @@ pub struct MenuStyle {
     pub palette_2: Style,
     pub palette_3: Style,
     pub palette_4: Style,
+    /// Background of every other row of the files. Default: none.
+    pub stripe: Option<Color>,
 }
 
 impl Default for MenuStyle {
@@ -236,6 +304,7 @@ impl Default for MenuStyle {
             palette_2: color_to_style(Color::Rgb(230, 189, 87)),
             palette_3: color_to_style(Color::Rgb(230, 167, 255)),
             palette_4: color_to_style(Color::Rgb(59, 204, 255)),
+            stripe: None,
         }
     }
 }
@@ -253,6 +322,9 @@ impl MenuStyle {
                 update_style!(self.palette_2, menu_colors, "palette_2");
                 update_style!(self.palette_3, menu_colors, "palette_3");
                 update_style!(self.palette_4, menu_colors, "palette_4");
+                if let Some(color) = read_yaml_string(menu_colors, "stripe") {
+                    self.stripe = Some(crate::config::str_to_ratatui(color));
+                }
             }
         }
         self
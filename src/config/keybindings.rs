@@ -2,13 +2,22 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use std::string::ToString;
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEventKind};
 use serde_yml::Value;
 
 use crate::common::CONFIG_PATH;
 use crate::event::ActionMap;
 use crate::log_info;
 
+/// Mouse event of an extra button from its name in the config file.
+fn from_mouse_button_name(name: &str) -> Option<MouseEventKind> {
+    match name.to_lowercase().as_ref() {
+        "scroll-left" => Some(MouseEventKind::ScrollLeft),
+        "scroll-right" => Some(MouseEventKind::ScrollRight),
+        _ => None,
+    }
+}
+
 /// inspired by tuikit 0.5 : <https://github.com/lotabout/tuikit/blob/master/src/key.rs#L72-L271>
 #[rustfmt::skip]
 pub fn from_keyname(keyname: &str) -> Option<KeyEvent> {
@@ -220,6 +229,8 @@ pub struct Bindings {
     pub binds: HashMap<KeyEvent, ActionMap>,
     /// Remember every key binded to a custom action
     pub custom: Option<Vec<String>>,
+    /// Extra mouse buttons and their actions, read from the `mouse` section of the config file.
+    pub mouse: HashMap<MouseEventKind, ActionMap>,
 }
 
 impl Default for Bindings {
@@ -247,7 +258,7 @@ impl Bindings {
             (KeyEvent::new(KeyCode::Tab,          KeyModifiers::NONE), ActionMap::Tab),
             (KeyEvent::new(KeyCode::BackTab,      KeyModifiers::NONE), ActionMap::Tab),
 
-            (KeyEvent::new(KeyCode::Char(' '),    KeyModifiers::NONE), ActionMap::ToggleFlag),
+            (KeyEvent::new(KeyCode::Char(' '),    KeyModifiers::NONE), ActionMap::QuickLook),
             (KeyEvent::new(KeyCode::Char('/'),    KeyModifiers::NONE), ActionMap::Search),
             (KeyEvent::new(KeyCode::Char('*'),    KeyModifiers::NONE), ActionMap::FlagAll),
             (KeyEvent::new(KeyCode::Char('\''),   KeyModifiers::NONE), ActionMap::MarksJump),
@@ -260,6 +271,7 @@ impl Bindings {
             (KeyEvent::new(KeyCode::Char(':'),    KeyModifiers::NONE), ActionMap::Action),
             (KeyEvent::new(KeyCode::Char('6'),    KeyModifiers::NONE), ActionMap::History),
 
+            (KeyEvent::new(KeyCode::Char('b'),    KeyModifiers::SHIFT), ActionMap::BinaryCycleWidth),
             (KeyEvent::new(KeyCode::Char('c'),    KeyModifiers::SHIFT), ActionMap::Compress),
             (KeyEvent::new(KeyCode::Char('e'),    KeyModifiers::SHIFT), ActionMap::ToggleDisplayFull),
             (KeyEvent::new(KeyCode::Char('g'),    KeyModifiers::SHIFT), ActionMap::End),
@@ -272,6 +284,9 @@ impl Bindings {
             (KeyEvent::new(KeyCode::Char('m'),    KeyModifiers::SHIFT), ActionMap::MarksNew),
             (KeyEvent::new(KeyCode::Char('o'),    KeyModifiers::SHIFT), ActionMap::Sort),
             (KeyEvent::new(KeyCode::Char('p'),    KeyModifiers::SHIFT), ActionMap::Preview),
+            (KeyEvent::new(KeyCode::Char('q'),    KeyModifiers::SHIFT), ActionMap::QuitCdSelected),
+            (KeyEvent::new(KeyCode::Char('v'),    KeyModifiers::SHIFT), ActionMap::ToggleVisual),
+            (KeyEvent::new(KeyCode::Char('w'),    KeyModifiers::SHIFT), ActionMap::GlobMatch),
             (KeyEvent::new(KeyCode::Char('x'),    KeyModifiers::SHIFT), ActionMap::TrashMoveFile),
             (KeyEvent::new(KeyCode::Char('Z'),    KeyModifiers::SHIFT), ActionMap::TreeUnFoldAll),
 
@@ -317,14 +332,25 @@ impl Bindings {
             (KeyEvent::new(KeyCode::Char('s'),    KeyModifiers::ALT), ActionMap::TuiMenu),
             (KeyEvent::new(KeyCode::Char('t'),    KeyModifiers::ALT), ActionMap::Context),
             (KeyEvent::new(KeyCode::Char('x'),    KeyModifiers::ALT), ActionMap::TrashEmpty),
+            (KeyEvent::new(KeyCode::Char('y'),    KeyModifiers::ALT), ActionMap::CopyImage),
             (KeyEvent::new(KeyCode::Char('"'),    KeyModifiers::ALT), ActionMap::TempMarksNew),
             (KeyEvent::new(KeyCode::Char('\''),   KeyModifiers::ALT), ActionMap::MarksNew),
 
             (KeyEvent::new(KeyCode::Char('c'),    KeyModifiers::ALT | KeyModifiers::SHIFT), ActionMap::CloudDrive),
+            (KeyEvent::new(KeyCode::Char('e'),    KeyModifiers::ALT | KeyModifiers::SHIFT), ActionMap::ToggleGroupByExtension),
+            (KeyEvent::new(KeyCode::Char('g'),    KeyModifiers::ALT | KeyModifiers::SHIFT), ActionMap::GitDiff),
+            (KeyEvent::new(KeyCode::Char('l'),    KeyModifiers::ALT | KeyModifiers::SHIFT), ActionMap::GitLog),
+            (KeyEvent::new(KeyCode::Char('o'),    KeyModifiers::ALT | KeyModifiers::SHIFT), ActionMap::EnterInOtherTab),
+            (KeyEvent::new(KeyCode::Char('p'),    KeyModifiers::ALT | KeyModifiers::SHIFT), ActionMap::CopyCurrentDir),
             (KeyEvent::new(KeyCode::Char('r'),    KeyModifiers::ALT | KeyModifiers::SHIFT), ActionMap::RemovableDevices),
+            (KeyEvent::new(KeyCode::Char('s'),    KeyModifiers::ALT | KeyModifiers::SHIFT), ActionMap::ShellStream),
+            (KeyEvent::new(KeyCode::Char('t'),    KeyModifiers::ALT | KeyModifiers::SHIFT), ActionMap::CycleDisplayMode),
+            (KeyEvent::new(KeyCode::Char('y'),    KeyModifiers::ALT | KeyModifiers::SHIFT), ActionMap::CopyPreviewAll),
 
+            (KeyEvent::new(KeyCode::Char(' '),    KeyModifiers::CONTROL), ActionMap::ToggleFlag),
             (KeyEvent::new(KeyCode::Char('c'),    KeyModifiers::CONTROL), ActionMap::CopyFilename),
             (KeyEvent::new(KeyCode::Char('d'),    KeyModifiers::CONTROL), ActionMap::PageDown),
+            (KeyEvent::new(KeyCode::Char('e'),    KeyModifiers::CONTROL), ActionMap::FuzzyFindCommand),
             (KeyEvent::new(KeyCode::Char('f'),    KeyModifiers::CONTROL), ActionMap::FuzzyFind),
             (KeyEvent::new(KeyCode::Char('g'),    KeyModifiers::CONTROL), ActionMap::Shortcut),
             (KeyEvent::new(KeyCode::Char('s'),    KeyModifiers::CONTROL), ActionMap::FuzzyFindLine),
@@ -333,6 +359,10 @@ impl Bindings {
             (KeyEvent::new(KeyCode::Char('p'),    KeyModifiers::CONTROL), ActionMap::CopyFilepath),
             (KeyEvent::new(KeyCode::Char('q'),    KeyModifiers::CONTROL), ActionMap::ResetMode),
             (KeyEvent::new(KeyCode::Char('r'),    KeyModifiers::CONTROL), ActionMap::RefreshView),
+            (KeyEvent::new(KeyCode::Char('t'),    KeyModifiers::CONTROL), ActionMap::ToggleTerminalPane),
+            (KeyEvent::new(KeyCode::Char('w'),    KeyModifiers::CONTROL), ActionMap::ToggleSearchRegex),
+            (KeyEvent::new(KeyCode::Char('x'),    KeyModifiers::CONTROL), ActionMap::CancelRemoval),
+            (KeyEvent::new(KeyCode::Char('y'),    KeyModifiers::CONTROL), ActionMap::CopyPreviewVisible),
             (KeyEvent::new(KeyCode::Char('z'),    KeyModifiers::CONTROL), ActionMap::TreeFoldAll),
 
             (KeyEvent::new(KeyCode::Right,        KeyModifiers::SHIFT), ActionMap::SyncLTR),
@@ -340,6 +370,11 @@ impl Bindings {
             (KeyEvent::new(KeyCode::Left,         KeyModifiers::SHIFT), ActionMap::DeleteLine),
             (KeyEvent::new(KeyCode::Up,           KeyModifiers::SHIFT), ActionMap::PreviousThing),
 
+            (KeyEvent::new(KeyCode::Left,         KeyModifiers::ALT), ActionMap::MoveWordLeft),
+            (KeyEvent::new(KeyCode::Right,        KeyModifiers::ALT), ActionMap::MoveWordRight),
+            (KeyEvent::new(KeyCode::Up,           KeyModifiers::ALT), ActionMap::ReorderUp),
+            (KeyEvent::new(KeyCode::Down,         KeyModifiers::ALT), ActionMap::ReorderDown),
+
             (KeyEvent::new(KeyCode::Up,           KeyModifiers::CONTROL), ActionMap::FocusGoUp),
             (KeyEvent::new(KeyCode::Down,         KeyModifiers::CONTROL), ActionMap::FocusGoDown),
             (KeyEvent::new(KeyCode::Right,        KeyModifiers::CONTROL), ActionMap::FocusGoRight),
@@ -364,7 +399,12 @@ impl Bindings {
             (KeyEvent::new(KeyCode::F(12),        KeyModifiers::NONE), ActionMap::FlaggedFromClipboard),
         ]);
         let custom = None;
-        Self { binds, custom }
+        let mouse = HashMap::new();
+        Self {
+            binds,
+            custom,
+            mouse,
+        }
     }
 
     /// Returns an Option of action. None if the key isn't binded.
@@ -433,6 +473,40 @@ impl Bindings {
         self.custom = Some(custom);
     }
 
+    /// Returns the action bound to an extra mouse button, if any.
+    pub fn get_mouse(&self, kind: &MouseEventKind) -> Option<&ActionMap> {
+        self.mouse.get(kind)
+    }
+
+    /// Update the mouse binds from a config file.
+    /// Only the buttons reported by the terminal besides the left, middle and right clicks
+    /// and the vertical wheel can be bound: the horizontal wheel, `scroll-left` and `scroll-right`.
+    /// The back and forward side buttons can't be bound, crossterm rejects their events while parsing them.
+    /// Some mouse drivers can remap them to the horizontal wheel.
+    pub fn update_mouse(&mut self, yaml: &Value) {
+        let Some(mappings) = yaml.as_mapping() else {
+            return;
+        };
+        for yaml_key in mappings.keys() {
+            let Some(button) = yaml_key.as_str() else {
+                log_info!("{CONFIG_PATH}: Mouse button {yaml_key:?} is unreadable");
+                continue;
+            };
+            let Some(kind) = from_mouse_button_name(button) else {
+                log_info!("{CONFIG_PATH}: Mouse button {button} is unknown");
+                continue;
+            };
+            let Some(action_str) = yaml[yaml_key].as_str() else {
+                continue;
+            };
+            let Ok(action) = ActionMap::from_str(action_str) else {
+                log_info!("{CONFIG_PATH}: Action {action_str} is unknown");
+                continue;
+            };
+            self.mouse.insert(kind, action);
+        }
+    }
+
     /// Format all keybindings in alphabetical order.
     pub fn to_str(&self) -> String {
         let mut binds = vec![];
@@ -1,5 +1,6 @@
 //! Everything about configuration from text files in `$HOME/.config/fm`.
 //!
+//! - `behaviors` holds the enums of the configurable behaviors, like what pressing enter on an archive does,
 //! - [`cloud_config::cloud_config`] is the function used to create a valid token for cloud files (google drive only ATM),
 //! - `colors` holds everything about reading, parsing, converting & generating colors,
 //! - `configuration`]holds everything about the yaml files used and their configuration,
@@ -7,6 +8,7 @@
 //! - `oncelock_static` holds all the static files configured by config and argument parameters. Those static files are set once from config and can be read every where in the application.
 //! - [`keybindings::Bindings`] & [`keybindings::from_keyname`] are used to handle the user configured keybinds. The first creates default (hardcoded) keybinds and tries to update from from the config file, the second is used to read those configuration files.
 
+mod behaviors;
 mod cloud_config;
 mod colors;
 mod configuration;
@@ -14,12 +16,19 @@ mod gradient;
 mod keybindings;
 mod oncelock_static;
 
+pub use behaviors::{ArchiveEnter, ShellInTmux};
 pub use cloud_config::cloud_config;
 pub use colors::{extension_color, str_to_ratatui, ColorG, NormalFileColorer, MAX_GRADIENT_NORMAL};
 pub use configuration::{load_config, read_normal_file_colorer, Config, FileStyle, MenuStyle};
 pub use gradient::Gradient;
 pub use keybindings::{from_keyname, Bindings};
 pub use oncelock_static::{
-    set_configurable_static, set_icon_icon_with_metadata, with_icon, with_icon_metadata,
-    ARRAY_GRADIENT, COLORER, FILE_STYLES, MATCHER, MENU_STYLES, MONOKAI_THEME, START_FOLDER,
+    archive_enter, binary_ascii, binary_bytes_per_row, clipboard_command, clipboard_paste_command,
+    compare_recursive, configured_icon, delete_to_trash, fuzzy_respect_gitignore,
+    grep_flag_recursive, is_never_previewed, is_protected, max_preview_size_bytes, notes_filename,
+    on_cd_command, on_select_command, pager_command, previewer_command, scroll_off,
+    session_save_delay, set_configurable_static, set_icon_icon_with_metadata, shell_in_tmux,
+    syntax_theme, tree_connectors, tree_indent, wallpaper_command, with_binary_size_units,
+    with_icon, with_icon_metadata, LazyMutex, ARRAY_GRADIENT, COLORER, FILE_STYLES, MATCHER,
+    MENU_STYLES, START_FOLDER, SYNTAX_THEME,
 };
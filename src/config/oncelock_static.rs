@@ -1,8 +1,11 @@
 use std::{
+    collections::HashMap,
     fs::File,
+    io::{BufReader, Cursor},
     ops::DerefMut,
     path::{Path, PathBuf},
     sync::OnceLock,
+    time::Duration,
 };
 
 use anyhow::{anyhow, Result};
@@ -10,13 +13,15 @@ use nucleo::Matcher;
 use parking_lot::{Mutex, MutexGuard};
 use ratatui::style::Color;
 use serde_yml::{from_reader, Value};
-use syntect::highlighting::Theme;
+use syntect::highlighting::{Theme, ThemeSet};
 
 use crate::common::{tilde, CONFIG_PATH};
 use crate::config::{
-    read_normal_file_colorer, FileStyle, Gradient, MenuStyle, NormalFileColorer,
-    MAX_GRADIENT_NORMAL,
+    read_normal_file_colorer, ArchiveEnter, FileStyle, Gradient, MenuStyle, NormalFileColorer,
+    ShellInTmux, MAX_GRADIENT_NORMAL,
 };
+use crate::modes::{glob_to_regex, BinaryContent, CaseDependantRegex};
+use crate::{log_info, log_line};
 
 /// Starting folder of the application. Read from arguments if any `-P ~/Downloads` else it uses the current folder: `.`.
 pub static START_FOLDER: OnceLock<PathBuf> = OnceLock::new();
@@ -42,10 +47,53 @@ pub static COLORER: OnceLock<fn(usize) -> Color> = OnceLock::new();
 pub static ARRAY_GRADIENT: OnceLock<[Color; MAX_GRADIENT_NORMAL]> = OnceLock::new();
 
 /// Highlighting theme color used to preview code file
-pub static MONOKAI_THEME: OnceLock<Theme> = OnceLock::new();
+pub static SYNTAX_THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Name of the default highlighting theme, embedded in the binary.
+const DEFAULT_SYNTAX_THEME: &str = "Monokai Extended";
+
+/// Name of the configured highlighting theme, given to bat with `--theme`. Unset for `.tmTheme` files.
+static BAT_THEME: OnceLock<String> = OnceLock::new();
 
 static ICON: OnceLock<bool> = OnceLock::new();
 static ICON_WITH_METADATA: OnceLock<bool> = OnceLock::new();
+static SCROLL_OFF: OnceLock<usize> = OnceLock::new();
+static BINARY_SIZE_UNITS: OnceLock<bool> = OnceLock::new();
+static SESSION_SAVE_DELAY: OnceLock<u64> = OnceLock::new();
+static FUZZY_RESPECT_GITIGNORE: OnceLock<bool> = OnceLock::new();
+static BINARY_BYTES_PER_ROW: OnceLock<usize> = OnceLock::new();
+static BINARY_ASCII: OnceLock<bool> = OnceLock::new();
+static MAX_PREVIEW_SIZE_BYTES: OnceLock<u64> = OnceLock::new();
+static NEVER_PREVIEW: OnceLock<Vec<CaseDependantRegex>> = OnceLock::new();
+static PROTECTED_PATHS: OnceLock<Vec<PathBuf>> = OnceLock::new();
+static ON_CD_COMMAND: OnceLock<Vec<String>> = OnceLock::new();
+static ON_SELECT_COMMAND: OnceLock<Vec<String>> = OnceLock::new();
+static CLIPBOARD_COMMAND: OnceLock<Vec<String>> = OnceLock::new();
+static CLIPBOARD_PASTE_COMMAND: OnceLock<Vec<String>> = OnceLock::new();
+static NOTES_FILENAME: OnceLock<String> = OnceLock::new();
+static SHELL_IN_TMUX: OnceLock<ShellInTmux> = OnceLock::new();
+static ARCHIVE_ENTER: OnceLock<ArchiveEnter> = OnceLock::new();
+static DELETE_TO_TRASH: OnceLock<bool> = OnceLock::new();
+static COMPARE_RECURSIVE: OnceLock<bool> = OnceLock::new();
+static GREP_FLAG_RECURSIVE: OnceLock<bool> = OnceLock::new();
+static WALLPAPER_COMMAND: OnceLock<Vec<String>> = OnceLock::new();
+static PAGER_COMMAND: OnceLock<Vec<String>> = OnceLock::new();
+static TREE_INDENT: OnceLock<usize> = OnceLock::new();
+static TREE_CONNECTORS: OnceLock<bool> = OnceLock::new();
+static CONFIGURED_ICONS: OnceLock<HashMap<String, String>> = OnceLock::new();
+static PREVIEWERS: OnceLock<HashMap<String, Vec<String>>> = OnceLock::new();
+
+/// Default delay in milliseconds between the last modification of the session and its writing.
+const DEFAULT_SESSION_SAVE_DELAY: u64 = 1000;
+
+/// Default number of bytes read to preview a text or binary file: 16 MiB.
+const DEFAULT_MAX_PREVIEW_SIZE_BYTES: u64 = 16 * 1048576;
+
+/// Default width of a level of the tree, without the space separating the levels.
+const DEFAULT_TREE_INDENT: usize = 2;
+
+/// Default name of the notes file of a directory.
+const DEFAULT_NOTES_FILENAME: &str = ".fm-notes.md";
 
 /// Does the user wants nerdfont icons ? Default: false.
 pub fn with_icon() -> bool {
@@ -57,6 +105,178 @@ pub fn with_icon_metadata() -> bool {
     *ICON_WITH_METADATA.get().unwrap_or(&false)
 }
 
+/// Icon configured by the user for this extension, replacing the default one.
+pub fn configured_icon(extension: &str) -> Option<&'static str> {
+    CONFIGURED_ICONS
+        .get()?
+        .get(extension)
+        .map(|icon| icon.as_str())
+}
+
+/// Minimum number of lines kept between the selected line and the border of the window. Default: 0.
+pub fn scroll_off() -> usize {
+    *SCROLL_OFF.get().unwrap_or(&0)
+}
+
+/// Are sizes displayed in binary units (1K = 1024 bytes) instead of SI units (1k = 1000 bytes) ? Default: false.
+pub fn with_binary_size_units() -> bool {
+    *BINARY_SIZE_UNITS.get().unwrap_or(&false)
+}
+
+/// Time without modification of the session before it's written to its file. Default: 1 second.
+pub fn session_save_delay() -> Duration {
+    Duration::from_millis(
+        *SESSION_SAVE_DELAY
+            .get()
+            .unwrap_or(&DEFAULT_SESSION_SAVE_DELAY),
+    )
+}
+
+/// Does the fuzzy file finder skip the files ignored by git ? Default: true.
+/// It only has an effect inside git repositories.
+pub fn fuzzy_respect_gitignore() -> bool {
+    *FUZZY_RESPECT_GITIGNORE.get().unwrap_or(&true)
+}
+
+/// Number of bytes per line in the preview of binary files. Default: 16.
+pub fn binary_bytes_per_row() -> usize {
+    *BINARY_BYTES_PER_ROW.get().unwrap_or(&16)
+}
+
+/// Does the preview of binary files display the ASCII column ? Default: true.
+pub fn binary_ascii() -> bool {
+    *BINARY_ASCII.get().unwrap_or(&true)
+}
+
+/// Maximum number of bytes read to preview a text or binary file. Default: 16 MiB.
+/// Larger files are truncated.
+pub fn max_preview_size_bytes() -> u64 {
+    *MAX_PREVIEW_SIZE_BYTES
+        .get()
+        .unwrap_or(&DEFAULT_MAX_PREVIEW_SIZE_BYTES)
+}
+
+/// True if the filename matches an extension or a glob of `never_preview`. Default: none.
+/// Those files aren't previewed automatically in the second pane.
+pub fn is_never_previewed(path: &Path) -> bool {
+    let Some(filename) = path.file_name().and_then(|filename| filename.to_str()) else {
+        return false;
+    };
+    NEVER_PREVIEW
+        .get()
+        .is_some_and(|patterns| patterns.iter().any(|pattern| pattern.is_match(filename)))
+}
+
+/// True if the path is one of `protected_paths` or one of their direct children. Default: none.
+/// Deleting or moving those files requires to type their full path.
+pub fn is_protected(path: &Path) -> bool {
+    PROTECTED_PATHS.get().is_some_and(|protected_paths| {
+        protected_paths
+            .iter()
+            .any(|protected| path == protected || path.parent() == Some(protected.as_path()))
+    })
+}
+
+/// Command and its arguments run when the current directory changes. Default: none.
+pub fn on_cd_command() -> &'static [String] {
+    ON_CD_COMMAND
+        .get()
+        .map_or(&[], |command| command.as_slice())
+}
+
+/// Command and its arguments run when the selected file changes. Default: none.
+pub fn on_select_command() -> &'static [String] {
+    ON_SELECT_COMMAND
+        .get()
+        .map_or(&[], |command| command.as_slice())
+}
+
+/// Command and its arguments previewing files with this lowercase extension, if any.
+/// The path of the file is appended to the arguments.
+pub fn previewer_command(extension: &str) -> Option<&'static [String]> {
+    PREVIEWERS
+        .get()?
+        .get(extension)
+        .map(|command| command.as_slice())
+}
+
+/// Command and its arguments setting the wallpaper, `{path}` being replaced by the image path.
+/// Default: none, an installed setter is detected.
+pub fn wallpaper_command() -> &'static [String] {
+    WALLPAPER_COMMAND
+        .get()
+        .map_or(&[], |command| command.as_slice())
+}
+
+/// Pager and its arguments used to read the selected file full screen.
+/// Default: none, `$PAGER` or `less` is used.
+pub fn pager_command() -> &'static [String] {
+    PAGER_COMMAND
+        .get()
+        .map_or(&[], |command| command.as_slice())
+}
+
+/// Command and its arguments reading the copied text from its stdin. Default: none, the clipboard library is used.
+pub fn clipboard_command() -> &'static [String] {
+    CLIPBOARD_COMMAND
+        .get()
+        .map_or(&[], |command| command.as_slice())
+}
+
+/// Command and its arguments writing the clipboard content to its stdout. Default: none, the clipboard library is used.
+pub fn clipboard_paste_command() -> &'static [String] {
+    CLIPBOARD_PASTE_COMMAND
+        .get()
+        .map_or(&[], |command| command.as_slice())
+}
+
+/// Name of the notes file opened in the current directory. Default: `.fm-notes.md`.
+pub fn notes_filename() -> &'static str {
+    NOTES_FILENAME
+        .get()
+        .map_or(DEFAULT_NOTES_FILENAME, |filename| filename.as_str())
+}
+
+/// Where shells are opened when fm runs inside tmux. Default: in the window of fm.
+pub fn shell_in_tmux() -> ShellInTmux {
+    SHELL_IN_TMUX.get().copied().unwrap_or_default()
+}
+
+/// What pressing enter on an archive does. Default: open it with its opener.
+pub fn archive_enter() -> ArchiveEnter {
+    ARCHIVE_ENTER.get().copied().unwrap_or_default()
+}
+
+/// Does the delete action move the files to the trash instead of deleting them permanently ? Default: false.
+pub fn delete_to_trash() -> bool {
+    *DELETE_TO_TRASH.get().unwrap_or(&false)
+}
+
+/// Does the comparison of the tabs directories walk them recursively ? Default: false.
+pub fn compare_recursive() -> bool {
+    *COMPARE_RECURSIVE.get().unwrap_or(&false)
+}
+
+/// Does the action FlaggedFromGrep search the subdirectories too ? Default: false.
+pub fn grep_flag_recursive() -> bool {
+    *GREP_FLAG_RECURSIVE.get().unwrap_or(&false)
+}
+
+/// Width of a level of the tree, without the space separating the levels. Default: 2.
+pub fn tree_indent() -> usize {
+    *TREE_INDENT.get().unwrap_or(&DEFAULT_TREE_INDENT)
+}
+
+/// Are the levels of the tree linked with box-drawing connectors (├──, └──) instead of spaces ? Default: true.
+pub fn tree_connectors() -> bool {
+    *TREE_CONNECTORS.get().unwrap_or(&true)
+}
+
+/// Highlighting theme used to preview code files. Default: Monokai Extended.
+pub fn syntax_theme() -> &'static Theme {
+    SYNTAX_THEME.get_or_init(monokai_theme)
+}
+
 fn set_start_folder(start_folder: &str) -> Result<()> {
     START_FOLDER
         .set(std::fs::canonicalize(tilde(start_folder).as_ref()).unwrap_or_default())
@@ -94,22 +314,15 @@ fn read_yaml_bool(yaml: &Value, key: &str) -> Option<bool> {
     yaml[key].as_bool()
 }
 
-fn read_icon_icon_with_metadata() -> (bool, bool) {
-    let Ok(file) = File::open(Path::new(&tilde(CONFIG_PATH).to_string())) else {
-        crate::log_info!("Couldn't read config file at {CONFIG_PATH}");
-        return (false, false);
-    };
-    let Ok(yaml) = from_reader::<File, Value>(file) else {
-        return (false, false);
-    };
+fn read_icon_icon_with_metadata(yaml: &Value) -> (bool, bool) {
     let mut icon: bool = false;
     let mut icon_with_metadata: bool = false;
-    if let Some(i) = read_yaml_bool(&yaml, "icon") {
+    if let Some(i) = read_yaml_bool(yaml, "icon") {
         icon = i;
     }
     if !icon {
         icon_with_metadata = false;
-    } else if let Some(icon_with) = read_yaml_bool(&yaml, "icon_with_metadata") {
+    } else if let Some(icon_with) = read_yaml_bool(yaml, "icon_with_metadata") {
         icon_with_metadata = icon_with;
     }
     (icon, icon_with_metadata)
@@ -124,8 +337,8 @@ fn read_icon_icon_with_metadata() -> (bool, bool) {
 /// - If the values aren't in the yaml file, both are false,
 /// - If icon is false, icon_with_metadata is false,
 /// - Otherwise, we use the values from the file.
-pub fn set_icon_icon_with_metadata() -> Result<()> {
-    let (icon, icon_with_metadata) = read_icon_icon_with_metadata();
+pub fn set_icon_icon_with_metadata(yaml: &Value) -> Result<()> {
+    let (icon, icon_with_metadata) = read_icon_icon_with_metadata(yaml);
     ICON.set(icon)
         .map_err(|_| anyhow!("ICON shouldn't be set"))?;
     ICON_WITH_METADATA
@@ -134,6 +347,394 @@ pub fn set_icon_icon_with_metadata() -> Result<()> {
     Ok(())
 }
 
+/// Read the `icons` section of the config file, mapping extensions to icons, and store it in a static value.
+/// A space is added after each icon, like the default ones. Unreadable entries are ignored.
+fn set_configured_icons(yaml: &Value) -> Result<()> {
+    let mut icons = HashMap::new();
+    if let Some(mapping) = yaml["icons"].as_mapping() {
+        for (extension, icon) in mapping.iter() {
+            let (Some(extension), Some(icon)) = (extension.as_str(), icon.as_str()) else {
+                crate::log_info!("Unreadable icon {extension:?}: {icon:?}");
+                continue;
+            };
+            icons.insert(
+                extension.to_owned(),
+                format!("{icon} ", icon = icon.trim_end()),
+            );
+        }
+    }
+    CONFIGURED_ICONS
+        .set(icons)
+        .map_err(|_| anyhow!("CONFIGURED_ICONS shouldn't be set"))?;
+    Ok(())
+}
+
+/// Parse the config file. `Null` if it can't be read, every value is then set to its default.
+fn read_config_yaml() -> Value {
+    let Ok(file) = File::open(Path::new(&tilde(CONFIG_PATH).to_string())) else {
+        crate::log_info!("Couldn't read config file at {CONFIG_PATH}");
+        return Value::Null;
+    };
+    from_reader::<File, Value>(file).unwrap_or(Value::Null)
+}
+
+fn read_scroll_off(yaml: &Value) -> usize {
+    yaml["scroll_off"].as_u64().unwrap_or_default() as usize
+}
+
+/// Read `scroll_off` from the config file and store it in a static value.
+/// If the value can't be read, it's set to 0 and the default padding is used.
+fn set_scroll_off(yaml: &Value) -> Result<()> {
+    SCROLL_OFF
+        .set(read_scroll_off(yaml))
+        .map_err(|_| anyhow!("SCROLL_OFF shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `size_units` from the config file and store it in a static value.
+/// Only `binary` changes the units, anything else keeps the SI units.
+fn set_binary_size_units(yaml: &Value) -> Result<()> {
+    let binary = yaml["size_units"].as_str() == Some("binary");
+    BINARY_SIZE_UNITS
+        .set(binary)
+        .map_err(|_| anyhow!("BINARY_SIZE_UNITS shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `session_save_delay` from the config file and store it in a static value.
+/// If the value can't be read, the default delay is used.
+fn set_session_save_delay(yaml: &Value) -> Result<()> {
+    let delay = yaml["session_save_delay"]
+        .as_u64()
+        .unwrap_or(DEFAULT_SESSION_SAVE_DELAY);
+    SESSION_SAVE_DELAY
+        .set(delay)
+        .map_err(|_| anyhow!("SESSION_SAVE_DELAY shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `fuzzy_respect_gitignore` from the config file and store it in a static value.
+fn set_fuzzy_respect_gitignore(yaml: &Value) -> Result<()> {
+    let respect = yaml["fuzzy_respect_gitignore"].as_bool().unwrap_or(true);
+    FUZZY_RESPECT_GITIGNORE
+        .set(respect)
+        .map_err(|_| anyhow!("FUZZY_RESPECT_GITIGNORE shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `binary_bytes_per_row` and `binary_ascii` from the config file and store them in static values.
+/// Only the values of [`BinaryContent::BYTES_PER_ROW`] are allowed, 16 is used otherwise.
+fn set_binary_preview(yaml: &Value) -> Result<()> {
+    let bytes_per_row = yaml["binary_bytes_per_row"]
+        .as_u64()
+        .map(|width| width as usize)
+        .filter(|width| BinaryContent::BYTES_PER_ROW.contains(width))
+        .unwrap_or(16);
+    let ascii = yaml["binary_ascii"].as_bool().unwrap_or(true);
+    BINARY_BYTES_PER_ROW
+        .set(bytes_per_row)
+        .map_err(|_| anyhow!("BINARY_BYTES_PER_ROW shouldn't be set"))?;
+    BINARY_ASCII
+        .set(ascii)
+        .map_err(|_| anyhow!("BINARY_ASCII shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `max_preview_size_bytes` from the config file and store it in a static value.
+/// 0 isn't allowed and the default value is used instead.
+fn set_max_preview_size_bytes(yaml: &Value) -> Result<()> {
+    let size = yaml["max_preview_size_bytes"]
+        .as_u64()
+        .filter(|&size| size > 0)
+        .unwrap_or(DEFAULT_MAX_PREVIEW_SIZE_BYTES);
+    MAX_PREVIEW_SIZE_BYTES
+        .set(size)
+        .map_err(|_| anyhow!("MAX_PREVIEW_SIZE_BYTES shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `never_preview` from the config file and store it in a static value.
+/// Each entry is either a glob like `core.*` or an extension like `log`, matched as `*.log`.
+/// Invalid entries are ignored and reported.
+fn set_never_preview(yaml: &Value) -> Result<()> {
+    let patterns = yaml["never_preview"]
+        .as_sequence()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.as_str())
+        .map(|entry| {
+            if entry.contains(['*', '?', '[']) {
+                entry.to_owned()
+            } else {
+                format!("*.{extension}", extension = entry.trim_start_matches('.'))
+            }
+        })
+        .filter_map(
+            |glob| match CaseDependantRegex::new(&glob_to_regex(&glob)) {
+                Ok(pattern) => Some(pattern),
+                Err(error) => {
+                    log_line!("never_preview: invalid pattern {glob}: {error}");
+                    None
+                }
+            },
+        )
+        .collect();
+    NEVER_PREVIEW
+        .set(patterns)
+        .map_err(|_| anyhow!("NEVER_PREVIEW shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `protected_paths` from the config file, expand their `~` and store them in a static value.
+fn set_protected_paths(yaml: &Value) -> Result<()> {
+    let paths = yaml["protected_paths"]
+        .as_sequence()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.as_str())
+        .map(|entry| PathBuf::from(tilde(entry.trim()).as_ref()))
+        .collect();
+    PROTECTED_PATHS
+        .set(paths)
+        .map_err(|_| anyhow!("PROTECTED_PATHS shouldn't be set"))?;
+    Ok(())
+}
+
+/// Command of the config file stored at `key`, split by [`split_command`]. Empty if it's not set.
+fn read_command(yaml: &Value, key: &str) -> Vec<String> {
+    yaml[key]
+        .as_str()
+        .map(|command| split_command(key, command))
+        .unwrap_or_default()
+}
+
+/// Split a command of the config file into its executable and its arguments like a shell would:
+/// quoted arguments may hold spaces. An invalid command, like an unclosed quote, is reported and ignored.
+fn split_command(key: &str, command: &str) -> Vec<String> {
+    match shell_words::split(command) {
+        Ok(args) => with_bat_theme(args),
+        Err(error) => {
+            log_line!("{key}: invalid command {command}: {error}");
+            vec![]
+        }
+    }
+}
+
+/// bat uses the configured highlighting theme unless the command already sets one.
+fn with_bat_theme(mut args: Vec<String>) -> Vec<String> {
+    let Some(theme) = BAT_THEME.get() else {
+        return args;
+    };
+    let is_bat = args.first().is_some_and(|executable| {
+        matches!(
+            Path::new(executable)
+                .file_name()
+                .and_then(|name| name.to_str()),
+            Some("bat" | "batcat")
+        )
+    });
+    if is_bat && !args.iter().any(|arg| arg.starts_with("--theme")) {
+        args.insert(1, format!("--theme={theme}"));
+    }
+    args
+}
+
+/// Read `on_cd_command` from the config file, split it like a shell would and store it in a static value.
+fn set_on_cd_command(yaml: &Value) -> Result<()> {
+    let command = read_command(yaml, "on_cd_command");
+    ON_CD_COMMAND
+        .set(command)
+        .map_err(|_| anyhow!("ON_CD_COMMAND shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `on_select_command` from the config file, split it like a shell would and store it in a static value.
+fn set_on_select_command(yaml: &Value) -> Result<()> {
+    let command = read_command(yaml, "on_select_command");
+    ON_SELECT_COMMAND
+        .set(command)
+        .map_err(|_| anyhow!("ON_SELECT_COMMAND shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read the `previewers` section of the config file, mapping extensions to commands, and store it in a static value.
+/// Extensions are lowercased and commands are split like a shell would. Unreadable entries are ignored.
+fn set_previewers(yaml: &Value) -> Result<()> {
+    let mut previewers = HashMap::new();
+    if let Some(mapping) = yaml["previewers"].as_mapping() {
+        for (extension, command) in mapping.iter() {
+            let (Some(extension), Some(command)) = (extension.as_str(), command.as_str()) else {
+                crate::log_info!("Unreadable previewer {extension:?}: {command:?}");
+                continue;
+            };
+            let command = split_command(extension, command);
+            if command.is_empty() {
+                continue;
+            }
+            previewers.insert(extension.to_lowercase(), command);
+        }
+    }
+    PREVIEWERS
+        .set(previewers)
+        .map_err(|_| anyhow!("PREVIEWERS shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `wallpaper_command` from the config file, split it like a shell would and store it in a static value.
+fn set_wallpaper_command(yaml: &Value) -> Result<()> {
+    let command = read_command(yaml, "wallpaper_command");
+    WALLPAPER_COMMAND
+        .set(command)
+        .map_err(|_| anyhow!("WALLPAPER_COMMAND shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `pager_command` from the config file, split it like a shell would and store it in a static value.
+fn set_pager_command(yaml: &Value) -> Result<()> {
+    let command = read_command(yaml, "pager_command");
+    PAGER_COMMAND
+        .set(command)
+        .map_err(|_| anyhow!("PAGER_COMMAND shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `clipboard_command` and `clipboard_paste_command` from the config file,
+/// split them like a shell would and store them in static values.
+fn set_clipboard_commands(yaml: &Value) -> Result<()> {
+    CLIPBOARD_COMMAND
+        .set(read_command(yaml, "clipboard_command"))
+        .map_err(|_| anyhow!("CLIPBOARD_COMMAND shouldn't be set"))?;
+    CLIPBOARD_PASTE_COMMAND
+        .set(read_command(yaml, "clipboard_paste_command"))
+        .map_err(|_| anyhow!("CLIPBOARD_PASTE_COMMAND shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `notes_filename` from the config file and store it in a static value.
+/// Empty names and paths aren't allowed and the default name is used instead.
+fn set_notes_filename(yaml: &Value) -> Result<()> {
+    let filename = yaml["notes_filename"]
+        .as_str()
+        .map(|name| name.trim().to_owned())
+        .filter(|name| !name.is_empty() && !name.contains('/'))
+        .unwrap_or_else(|| DEFAULT_NOTES_FILENAME.to_owned());
+    NOTES_FILENAME
+        .set(filename)
+        .map_err(|_| anyhow!("NOTES_FILENAME shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `shell_in_tmux` from the config file and store it in a static value.
+fn set_shell_in_tmux(yaml: &Value) -> Result<()> {
+    let shell_in_tmux = yaml["shell_in_tmux"]
+        .as_str()
+        .map(ShellInTmux::from_config)
+        .unwrap_or_default();
+    SHELL_IN_TMUX
+        .set(shell_in_tmux)
+        .map_err(|_| anyhow!("SHELL_IN_TMUX shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `archive_enter` from the config file and store it in a static value.
+fn set_archive_enter(yaml: &Value) -> Result<()> {
+    let archive_enter = yaml["archive_enter"]
+        .as_str()
+        .map(ArchiveEnter::from_config)
+        .unwrap_or_default();
+    ARCHIVE_ENTER
+        .set(archive_enter)
+        .map_err(|_| anyhow!("ARCHIVE_ENTER shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `delete_to_trash` from the config file and store it in a static value.
+fn set_delete_to_trash(yaml: &Value) -> Result<()> {
+    let delete_to_trash = yaml["delete_to_trash"].as_bool().unwrap_or(false);
+    DELETE_TO_TRASH
+        .set(delete_to_trash)
+        .map_err(|_| anyhow!("DELETE_TO_TRASH shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `compare_recursive` from the config file and store it in a static value.
+fn set_compare_recursive(yaml: &Value) -> Result<()> {
+    let recursive = yaml["compare_recursive"].as_bool().unwrap_or(false);
+    COMPARE_RECURSIVE
+        .set(recursive)
+        .map_err(|_| anyhow!("COMPARE_RECURSIVE shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `tree_indent` and `tree_connectors` from the config file and store them in static values.
+/// The indentation is kept between 1 and 8.
+fn set_tree_glyphs(yaml: &Value) -> Result<()> {
+    let indent = yaml["tree_indent"]
+        .as_u64()
+        .map(|indent| (indent as usize).clamp(1, 8))
+        .unwrap_or(DEFAULT_TREE_INDENT);
+    let connectors = yaml["tree_connectors"].as_bool().unwrap_or(true);
+    TREE_INDENT
+        .set(indent)
+        .map_err(|_| anyhow!("TREE_INDENT shouldn't be set"))?;
+    TREE_CONNECTORS
+        .set(connectors)
+        .map_err(|_| anyhow!("TREE_CONNECTORS shouldn't be set"))?;
+    Ok(())
+}
+
+/// Read `grep_flag_recursive` from the config file and store it in a static value.
+fn set_grep_flag_recursive(yaml: &Value) -> Result<()> {
+    let recursive = yaml["grep_flag_recursive"].as_bool().unwrap_or(false);
+    GREP_FLAG_RECURSIVE
+        .set(recursive)
+        .map_err(|_| anyhow!("GREP_FLAG_RECURSIVE shouldn't be set"))?;
+    Ok(())
+}
+
+fn monokai_theme() -> Theme {
+    let mut monokai = BufReader::new(Cursor::new(include_bytes!(
+        "../../assets/themes/Monokai_Extended.tmTheme"
+    )));
+    ThemeSet::load_from_reader(&mut monokai).expect("Couldn't find monokai theme")
+}
+
+/// Load a theme from its name, among the themes provided by syntect, or from a `.tmTheme` file.
+fn load_syntax_theme(name: &str) -> Option<Theme> {
+    if name.ends_with(".tmTheme") {
+        return ThemeSet::get_theme(tilde(name).as_ref()).ok();
+    }
+    ThemeSet::load_defaults().themes.remove(name)
+}
+
+/// Read `syntax_theme` from the config file.
+/// Unknown themes are logged and replaced by the default theme.
+/// A named theme is also given to bat, see [`with_bat_theme`].
+fn read_syntax_theme(yaml: &Value) -> Theme {
+    let Some(name) = yaml["syntax_theme"].as_str().map(|name| name.to_owned()) else {
+        return monokai_theme();
+    };
+    if name == DEFAULT_SYNTAX_THEME {
+        return monokai_theme();
+    }
+    let Some(theme) = load_syntax_theme(&name) else {
+        log_info!("Unknown syntax theme {name}, using {DEFAULT_SYNTAX_THEME}");
+        log_line!("Unknown syntax theme {name}, using {DEFAULT_SYNTAX_THEME}");
+        return monokai_theme();
+    };
+    if !name.ends_with(".tmTheme") {
+        let _ = BAT_THEME.set(name);
+    }
+    theme
+}
+
+fn set_syntax_theme(yaml: &Value) -> Result<()> {
+    SYNTAX_THEME
+        .set(read_syntax_theme(yaml))
+        .map_err(|_| anyhow!("SYNTAX_THEME shouldn't be set"))?;
+    Ok(())
+}
+
 /// Set all the values which could be configured from config file or arguments staticly.
 /// It allows us to read those values globally without having to pass them through to every function.
 /// All values use a [`std::sync::OnceLock`] internally.
@@ -142,7 +743,31 @@ pub fn set_configurable_static(start_folder: &str) -> Result<()> {
     set_menu_styles()?;
     set_file_styles()?;
     set_normal_file_colorer()?;
-    set_icon_icon_with_metadata()
+    let yaml = read_config_yaml();
+    set_scroll_off(&yaml)?;
+    set_binary_size_units(&yaml)?;
+    set_session_save_delay(&yaml)?;
+    set_fuzzy_respect_gitignore(&yaml)?;
+    set_binary_preview(&yaml)?;
+    set_max_preview_size_bytes(&yaml)?;
+    set_never_preview(&yaml)?;
+    set_protected_paths(&yaml)?;
+    set_syntax_theme(&yaml)?;
+    set_on_cd_command(&yaml)?;
+    set_on_select_command(&yaml)?;
+    set_clipboard_commands(&yaml)?;
+    set_notes_filename(&yaml)?;
+    set_shell_in_tmux(&yaml)?;
+    set_archive_enter(&yaml)?;
+    set_delete_to_trash(&yaml)?;
+    set_compare_recursive(&yaml)?;
+    set_grep_flag_recursive(&yaml)?;
+    set_wallpaper_command(&yaml)?;
+    set_pager_command(&yaml)?;
+    set_tree_glyphs(&yaml)?;
+    set_configured_icons(&yaml)?;
+    set_previewers(&yaml)?;
+    set_icon_icon_with_metadata(&yaml)
 }
 
 /// Copied from [Helix](https://github.com/helix-editor/helix/blob/master/helix-core/src/fuzzy.rs)
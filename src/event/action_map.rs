@@ -15,88 +15,157 @@ pub enum ActionMap {
     Action,
     Back,
     Backspace,
+    BinaryCycleWidth,
     Bulk,
+    CancelRemoval,
     Cd,
+    CdToRealPath,
     Chmod,
     ClearFlags,
     CliMenu,
     CloudDrive,
+    CompareTabs,
     Compress,
     Context,
+    ConvertImages,
+    CopyCurrentDir,
     CopyFilename,
     CopyFilepath,
+    CopyImage,
+    CopyAs,
     CopyPaste,
+    CopyPreviewAll,
+    CopyPreviewVisible,
+    CopyToOtherTab,
     CutPaste,
+    CycleDisplayMode,
     Delete,
+    DeletePermanently,
     DeleteLine,
+    DiskUsage,
     DisplayFlagged,
     EncryptedDrive,
     End,
     Enter,
+    EnterInOtherTab,
     Exec,
+    ExifRename,
     Filter,
     FlagAll,
+    FlagNewer,
+    FlagOlder,
     FlaggedToClipboard,
     FlaggedFromClipboard,
+    FlaggedFromGrep,
+    FlaggedLoad,
+    FlaggedSave,
+    FlaggedTotalSize,
     FocusGoLeft,
     FocusGoRight,
     FocusGoDown,
     FocusGoUp,
+    FollowSymlink,
     FuzzyFind,
+    FuzzyFindAll,
+    FuzzyFindCommand,
     FuzzyFindHelp,
     FuzzyFindLine,
+    GitDiff,
+    GitLog,
+    GlobMatch,
     GoRoot,
     GoStart,
     Help,
     History,
     Home,
+    JoinParts,
     KeyHome,
     Log,
+    MarksImportRanger,
     MarksJump,
     MarksNew,
     MoveDown,
+    MoveToOtherTab,
     MoveLeft,
     MoveRight,
     MoveUp,
+    MoveWordLeft,
+    MoveWordRight,
     NextThing,
     NewDir,
+    NewDirAndEnter,
     NewFile,
     Nothing,
+    NumberedRename,
     NvimFilepicker,
     NvimSetAddress,
     OpenConfig,
     OpenFile,
     OpenAll,
+    OpenArchiveEntry,
+    OpenNotes,
     PageDown,
     PageUp,
+    Pager,
+    PinPreview,
     Preview,
+    PreviewScriptOutput,
     PreviousThing,
+    Properties,
+    QuickLook,
     Quit,
+    QuitCdSelected,
+    QuitWithoutCd,
+    RefreshChanges,
     RefreshIfNeeded,
     RefreshView,
     RegexMatch,
     RemoteMount,
     RemovableDevices,
+    RemoveXattr,
+    ReorderDown,
+    ReorderUp,
     Rename,
+    RenameExtension,
+    RenameLowercase,
+    RenameSnakeCase,
+    RenameUppercase,
+    RenameUrlDecode,
     ResetMode,
     ReverseFlags,
     Search,
     SearchNext,
+    SetWallpaper,
+    SetXattr,
     Shell,
     ShellCommand,
+    ShellDryRun,
+    ShellStream,
     TempMarksJump,
     TempMarksNew,
     TuiMenu,
     Shortcut,
     Sort,
+    SplitFile,
     Symlink,
     SyncLTR,
     Tab,
     ToggleDisplayFull,
     ToggleDualPane,
     ToggleFlag,
+    ToggleFlaggedOnly,
+    ToggleFollowSelection,
+    ToggleRelativeLineNumbers,
+    ToggleGroupByExtension,
     ToggleHidden,
+    ToggleHiddenHere,
+    ToggleSortReversed,
+    ToggleSearchRegex,
+    ToggleTildePath,
     TogglePreviewSecond,
+    ToggleTerminalPane,
+    ToggleVisual,
+    TouchFlagged,
     TrashEmpty,
     TrashMoveFile,
     TrashOpen,
@@ -105,6 +174,7 @@ pub enum ActionMap {
     TreeFold,
     TreeFoldAll,
     TreeUnFoldAll,
+    Reveal(String),
     Custom(String),
 }
 
@@ -116,76 +186,134 @@ impl ActionMap {
             Self::Action => EventAction::action(status),
             Self::Back => EventAction::back(status),
             Self::Backspace => EventAction::backspace(status),
+            Self::BinaryCycleWidth => EventAction::binary_cycle_width(status),
             Self::Bulk => EventAction::bulk(status),
+            Self::CancelRemoval => EventAction::cancel_removal(status),
             Self::Cd => EventAction::cd(status),
+            Self::CdToRealPath => EventAction::cd_to_real_path(status),
             Self::Chmod => EventAction::chmod(status),
             Self::ClearFlags => EventAction::clear_flags(status),
             Self::CliMenu => EventAction::cli_menu(status),
             Self::CloudDrive => EventAction::cloud_drive(status),
+            Self::CompareTabs => EventAction::compare_tabs(status),
             Self::Compress => EventAction::compress(status),
+            Self::ConvertImages => EventAction::convert_images(status),
             Self::Context => EventAction::context(status),
+            Self::CopyCurrentDir => EventAction::copy_current_dir(status),
             Self::CopyFilename => EventAction::copy_filename(status),
             Self::CopyFilepath => EventAction::copy_filepath(status),
+            Self::CopyImage => EventAction::copy_image_to_clipboard(status),
+            Self::CopyAs => EventAction::copy_as(status),
             Self::CopyPaste => EventAction::copy_paste(status),
+            Self::CopyPreviewAll => EventAction::copy_preview_all(status),
+            Self::CopyPreviewVisible => EventAction::copy_preview_visible(status),
+            Self::CopyToOtherTab => EventAction::copy_to_other_tab(status),
             Self::CutPaste => EventAction::cut_paste(status),
+            Self::CycleDisplayMode => EventAction::cycle_display_mode(status),
             Self::Delete => EventAction::delete(status),
+            Self::DeletePermanently => EventAction::delete_permanently(status),
             Self::DeleteLine => EventAction::delete_line(status),
+            Self::DiskUsage => EventAction::disk_usage(status),
             Self::DisplayFlagged => EventAction::display_flagged(status),
             Self::EncryptedDrive => EventAction::encrypted_drive(status),
             Self::End => EventAction::end(status),
             Self::Enter => EventAction::enter(status, binds),
+            Self::EnterInOtherTab => EventAction::enter_in_other_tab(status),
             Self::Exec => EventAction::exec(status),
+            Self::ExifRename => EventAction::exif_rename(status),
             Self::Filter => EventAction::filter(status),
             Self::FlagAll => EventAction::flag_all(status),
+            Self::FlagNewer => EventAction::flag_newer(status),
+            Self::FlagOlder => EventAction::flag_older(status),
             Self::FlaggedToClipboard => EventAction::flagged_to_clipboard(status),
             Self::FlaggedFromClipboard => EventAction::flagged_from_clipboard(status),
+            Self::FlaggedFromGrep => EventAction::flagged_from_grep(status),
+            Self::FlaggedLoad => EventAction::flagged_load(status),
+            Self::FlaggedSave => EventAction::flagged_save(status),
+            Self::FlaggedTotalSize => EventAction::flagged_total_size(status),
             Self::FocusGoLeft => EventAction::focus_go_left(status),
             Self::FocusGoRight => EventAction::focus_go_right(status),
             Self::FocusGoDown => EventAction::focus_go_down(status),
             Self::FocusGoUp => EventAction::focus_go_up(status),
+            Self::FollowSymlink => EventAction::follow_symlink_to_target(status),
             Self::FuzzyFind => EventAction::fuzzyfind(status),
+            Self::FuzzyFindAll => EventAction::fuzzyfind_all(status),
+            Self::FuzzyFindCommand => EventAction::fuzzyfind_command(status),
             Self::FuzzyFindHelp => EventAction::fuzzyfind_help(status, binds),
             Self::FuzzyFindLine => EventAction::fuzzyfind_line(status),
+            Self::GitDiff => EventAction::git_diff_selected(status),
+            Self::GitLog => EventAction::git_log(status),
             Self::GoRoot => EventAction::go_root(status),
             Self::GoStart => EventAction::go_start(status),
             Self::Help => EventAction::help(status, binds),
             Self::History => EventAction::history(status),
             Self::Home => EventAction::home(status),
+            Self::JoinParts => EventAction::join_parts(status),
             Self::KeyHome => EventAction::key_home(status),
             Self::Log => EventAction::log(status),
+            Self::MarksImportRanger => EventAction::marks_import_ranger(status),
             Self::MarksJump => EventAction::marks_jump(status),
             Self::MarksNew => EventAction::marks_new(status),
             Self::MoveDown => EventAction::move_down(status),
+            Self::MoveToOtherTab => EventAction::move_to_other_tab(status),
             Self::MoveLeft => EventAction::move_left(status),
             Self::MoveRight => EventAction::move_right(status),
             Self::MoveUp => EventAction::move_up(status),
+            Self::MoveWordLeft => EventAction::move_word_left(status),
+            Self::MoveWordRight => EventAction::move_word_right(status),
             Self::NextThing => EventAction::next_thing(status),
             Self::NewDir => EventAction::new_dir(status),
+            Self::NewDirAndEnter => EventAction::new_dir_and_enter(status),
             Self::NewFile => EventAction::new_file(status),
+            Self::NumberedRename => EventAction::numbered_rename(status),
             Self::NvimFilepicker => EventAction::nvim_filepicker(status),
             Self::NvimSetAddress => EventAction::set_nvim_server(status),
             Self::OpenConfig => EventAction::open_config(status),
             Self::OpenFile => EventAction::open_file(status),
             Self::OpenAll => EventAction::open_all(status),
+            Self::OpenArchiveEntry => EventAction::open_archive_entry(status),
+            Self::OpenNotes => EventAction::open_notes(status),
             Self::PageDown => EventAction::page_down(status),
             Self::PageUp => EventAction::page_up(status),
+            Self::Pager => EventAction::pager(status),
+            Self::PinPreview => EventAction::pin_preview(status),
             Self::Preview => EventAction::preview(status),
+            Self::PreviewScriptOutput => EventAction::preview_script_output(status),
             Self::PreviousThing => EventAction::previous_thing(status),
+            Self::Properties => EventAction::properties(status),
+            Self::QuickLook => EventAction::quick_look(status),
             Self::Quit => EventAction::quit(status),
+            Self::QuitCdSelected => EventAction::quit_cd_selected(status),
+            Self::QuitWithoutCd => EventAction::quit_without_cd(status),
+            Self::RefreshChanges => EventAction::refresh_changes(status),
             Self::RefreshIfNeeded => EventAction::refresh_if_needed(status),
             Self::RefreshView => EventAction::refresh_view(status),
             Self::RegexMatch => EventAction::regex_match(status),
+            Self::GlobMatch => EventAction::glob_match(status),
             Self::RemoteMount => EventAction::remote_mount(status),
             Self::RemovableDevices => EventAction::removable_devices(status),
+            Self::RemoveXattr => EventAction::remove_xattr(status),
+            Self::ReorderDown => EventAction::reorder_down(status),
+            Self::ReorderUp => EventAction::reorder_up(status),
             Self::Rename => EventAction::rename(status),
+            Self::RenameExtension => EventAction::rename_extension(status),
+            Self::RenameLowercase => EventAction::rename_lowercase(status),
+            Self::RenameSnakeCase => EventAction::rename_snake_case(status),
+            Self::RenameUppercase => EventAction::rename_uppercase(status),
+            Self::RenameUrlDecode => EventAction::rename_url_decode(status),
             Self::ResetMode => EventAction::reset_mode(status),
             Self::ReverseFlags => EventAction::reverse_flags(status),
             Self::Search => EventAction::search(status),
             Self::SearchNext => EventAction::search_next(status),
+            Self::SetWallpaper => EventAction::set_wallpaper(status),
+            Self::SetXattr => EventAction::set_xattr(status),
             Self::Shell => EventAction::shell(status),
             Self::ShellCommand => EventAction::shell_command(status),
+            Self::ShellDryRun => EventAction::shell_dry_run(status),
+            Self::ShellStream => EventAction::shell_stream(status),
             Self::Shortcut => EventAction::shortcut(status),
             Self::Sort => EventAction::sort(status),
+            Self::SplitFile => EventAction::split_file(status),
             Self::Symlink => EventAction::symlink(status),
             Self::SyncLTR => EventAction::sync_ltr(status),
             Self::Tab => EventAction::tab(status),
@@ -194,8 +322,19 @@ impl ActionMap {
             Self::ToggleDisplayFull => EventAction::toggle_display_full(status),
             Self::ToggleDualPane => EventAction::toggle_dualpane(status),
             Self::ToggleFlag => EventAction::toggle_flag(status),
+            Self::ToggleFlaggedOnly => EventAction::toggle_flagged_only(status),
+            Self::ToggleFollowSelection => EventAction::toggle_follow_selection(status),
+            Self::ToggleRelativeLineNumbers => EventAction::toggle_relative_line_numbers(status),
+            Self::ToggleGroupByExtension => EventAction::toggle_group_by_extension(status),
             Self::ToggleHidden => EventAction::toggle_hidden(status),
+            Self::ToggleHiddenHere => EventAction::toggle_hidden_here(status),
+            Self::ToggleSortReversed => EventAction::toggle_sort_reversed(status),
+            Self::ToggleSearchRegex => EventAction::toggle_search_regex(status),
+            Self::ToggleTildePath => EventAction::toggle_tilde_path(status),
             Self::TogglePreviewSecond => EventAction::toggle_preview_second(status),
+            Self::ToggleTerminalPane => EventAction::toggle_terminal_pane(status),
+            Self::ToggleVisual => EventAction::toggle_visual(status),
+            Self::TouchFlagged => EventAction::touch_flagged(status),
             Self::TrashEmpty => EventAction::trash_empty(status),
             Self::TrashMoveFile => EventAction::trash_move_file(status),
             Self::TrashOpen => EventAction::trash_open(status),
@@ -205,6 +344,7 @@ impl ActionMap {
             Self::TreeFoldAll => EventAction::tree_fold_all(status),
             Self::TreeUnFoldAll => EventAction::tree_unfold_all(status),
             Self::TuiMenu => EventAction::tui_menu(status),
+            Self::Reveal(path) => EventAction::reveal(status, path),
             Self::Custom(string) => EventAction::custom(status, string),
 
             Self::Nothing => Ok(()),
@@ -216,57 +356,99 @@ impl ActionMap {
             Self::Action => "ACTION",
             Self::Back => "move back to previous dir",
             Self::Backspace => "delete previous char",
+            Self::BinaryCycleWidth => "cycle the number of bytes per line of binary previews",
             Self::Bulk => "BULK",
+            Self::CancelRemoval => "cancel the running deletion or move to trash",
             Self::Cd => "CD",
+            Self::CdToRealPath => {
+                "cd to the real path of the current directory, resolving symlinks"
+            }
             Self::Chmod => "CHMOD ",
             Self::ClearFlags => "clear flags",
             Self::CliMenu => "CLI APPS",
+            Self::CompareTabs => "flag the files missing or different in the other tab",
             Self::Compress => "compress into an archive",
+            Self::ConvertImages => "convert the flagged images to another format",
             Self::Context => "CONTEXT",
+            Self::CopyCurrentDir => "copy current directory path to clipboard",
             Self::CopyFilename => "copy filename to clipboard",
             Self::CopyFilepath => "copy filepath to clipboard",
+            Self::CopyImage => "copy the selected image to clipboard",
+            Self::CopyAs => "copy the selected file next to it under a new name",
             Self::CopyPaste => "copy to current dir",
             Self::CloudDrive => "navigate into a cloud drive",
             Self::Custom(_) => "custom command",
+            Self::CopyPreviewAll => "copy the whole preview to clipboard",
+            Self::CopyPreviewVisible => "copy the visible lines of the preview to clipboard",
+            Self::CopyToOtherTab => "copy to the directory of the other tab",
             Self::CutPaste => "move to current dir",
-            Self::Delete => "delete files permanently",
+            Self::CycleDisplayMode => "cycle the display modes: directory, tree",
+            Self::Delete => {
+                "delete files permanently, or move them to trash if `delete_to_trash` is set"
+            }
+            Self::DeletePermanently => "delete files permanently, whatever `delete_to_trash`",
             Self::DeleteLine => "delete the whole line / Sync left tab from right tab",
+            Self::DiskUsage => {
+                "disk usage of the children of the current directory, sorted by size"
+            }
             Self::DisplayFlagged => "FLAGGED",
             Self::EncryptedDrive => "ENCRYPTED DRIVE",
             Self::End => "go to last line",
             Self::Enter => "Execute mode then NORMAL",
+            Self::EnterInOtherTab => "open the selected directory in the other tab",
             Self::Exec => "OPEN WITH ",
+            Self::ExifRename => "rename flagged images to their capture date",
             Self::Filter => "FILTER ",
             Self::FlagAll => "flag all",
+            Self::FlagNewer => "flag the files modified after the selected one",
+            Self::FlagOlder => "flag the files modified before the selected one",
             Self::FlaggedFromClipboard => "flag existing files from primary clipboard",
+            Self::FlaggedFromGrep => "flag the files whose content matches a pattern",
             Self::FlaggedToClipboard => "copy flagged files to primary clipbloard",
+            Self::FlaggedLoad => "replace the flagged files by those saved in a file",
+            Self::FlaggedSave => "save the flagged files to a file",
+            Self::FlaggedTotalSize => "compute the total size of the flagged files",
             Self::FocusGoDown => "move focus to bottom",
             Self::FocusGoLeft => "move focus to left",
             Self::FocusGoRight => "move focus to right",
             Self::FocusGoUp => "move focus to up",
+            Self::FollowSymlink => "go to the directory of the selected symlink target",
             Self::FuzzyFind => "fuzzy finder for file",
+            Self::FuzzyFindAll => "fuzzy finder for file, files ignored by git included",
+            Self::FuzzyFindCommand => "fuzzy finder for previous commands",
             Self::FuzzyFindHelp => "fuzzy finder from help",
             Self::FuzzyFindLine => "fuzzy finder for line",
+            Self::GitDiff => "diff the selected file against its git HEAD version",
+            Self::GitLog => "last git commits of the selected directory",
             Self::GoRoot => "move to root (/)",
             Self::GoStart => "move to starting point",
             Self::Help => "help",
             Self::History => "HISTORY",
             Self::Home => "move to $HOME",
+            Self::JoinParts => "join the parts of a split file",
             Self::KeyHome => "go to first line",
             Self::Log => "open the logs",
+            Self::MarksImportRanger => "MARKS: Import ranger bookmarks",
             Self::MarksJump => "MARKS: Jump",
             Self::MarksNew => "MARKS: Save",
             Self::MoveDown => "one line down",
+            Self::MoveToOtherTab => "move to the directory of the other tab",
             Self::MoveLeft => "cd to parent directory ",
             Self::MoveRight => "cd to child directory",
             Self::MoveUp => "one line up  ",
+            Self::MoveWordLeft => "move the cursor one word left",
+            Self::MoveWordRight => "move the cursor one word right",
             Self::NewDir => "NEWDIR ",
+            Self::NewDirAndEnter => "create a new directory and enter it",
             Self::NewFile => "NEWFILE",
             Self::NextThing => "select next 'thing'",
             Self::Nothing => "do nothing",
+            Self::NumberedRename => "prefix the flagged filenames with a numbered sequence",
             Self::NvimFilepicker => "open in current nvim session",
             Self::NvimSetAddress => "setup the nvim rpc address",
             Self::OpenAll => "open all flagged files",
+            Self::OpenArchiveEntry => "extract and open the selected file of the previewed archive",
+            Self::OpenNotes => "open the notes file of the current directory",
             Self::OpenConfig => "open the config file",
             Self::OpenFile => {
                 "open the selected file with :
@@ -283,23 +465,46 @@ impl ActionMap {
             }
             Self::PageDown => "10 lines down",
             Self::PageUp => "10 lines up",
+            Self::Pager => "read the selected file in a pager",
+            Self::PinPreview => "pin the selected file in the preview of the second pane",
             Self::Preview => "preview this file",
+            Self::PreviewScriptOutput => "run the selected script and preview its output",
             Self::PreviousThing => "select previous 'thing'",
+            Self::Properties => "display every information about the selected file",
+            Self::QuickLook => "toggle a large preview of the selected file over the panes",
             Self::Quit => "quit",
+            Self::QuitCdSelected => "quit and cd to the selected directory",
+            Self::QuitWithoutCd => "quit without changing directory",
+            Self::RefreshChanges => "refresh and display what changed since the last refresh",
             Self::RefreshIfNeeded => "refresh the terminal if we have to",
             Self::RefreshView => "refresh view",
             Self::RegexMatch => "REGEXMATCH",
+            Self::GlobMatch => "flag files matching a glob",
             Self::RemoteMount => "MOUNT REMOTE PATH",
             Self::RemovableDevices => "REMOVABLE MTP DEVICES",
+            Self::RemoveXattr => "remove an extended attribute of the selected file",
+            Self::ReorderDown => "move the selected file one line down in the display order",
+            Self::ReorderUp => "move the selected file one line up in the display order",
             Self::Rename => "RENAME",
+            Self::RenameExtension => "replace the extension of the flagged filenames",
+            Self::RenameLowercase => "lowercase the flagged filenames",
+            Self::RenameSnakeCase => "convert the flagged filenames to snake_case",
+            Self::RenameUppercase => "uppercase the flagged filenames",
+            Self::RenameUrlDecode => "URL decode the flagged filenames",
+            Self::Reveal(_) => "move to the parent of a path and select it",
             Self::ResetMode => "NORMAL",
             Self::ReverseFlags => "reverse flags",
             Self::Search => "SEARCH",
             Self::SearchNext => "search next matching element",
+            Self::SetWallpaper => "set the selected image as wallpaper",
+            Self::SetXattr => "set an extended attribute of the selected file",
             Self::Shell => "shell in current directory",
             Self::ShellCommand => "run a shell command",
+            Self::ShellDryRun => "display the expanded shell command before running it",
+            Self::ShellStream => "run a shell command and stream its output",
             Self::Shortcut => "SHORTCUT",
             Self::Sort => "SORT",
+            Self::SplitFile => "split the selected file into parts",
             Self::Symlink => "symlink to current dir",
             Self::SyncLTR => "Sync right tab from left tab path",
             Self::TempMarksJump => "TEMP MARKS: Jump",
@@ -308,8 +513,27 @@ impl ActionMap {
             Self::ToggleDisplayFull => "toggle full metadata display of files",
             Self::ToggleDualPane => "toggle dual pane - if the width is sufficiant",
             Self::ToggleFlag => "toggle flag on a file",
+            Self::ToggleFlaggedOnly => "display only the flagged files of the directory",
+            Self::ToggleFollowSelection => {
+                "toggle the second pane listing the directory selected in the first one"
+            }
+            Self::ToggleGroupByExtension => "toggle the files grouped by extension",
+            Self::ToggleRelativeLineNumbers => {
+                "toggle the line numbers of text previews relative to the top line"
+            }
             Self::ToggleHidden => "toggle hidden",
+            Self::ToggleHiddenHere => "toggle hidden in this directory only",
+            Self::ToggleSortReversed => "reverse the current sort order, whatever the key",
+            Self::ToggleSearchRegex => "toggle the search between substring and regex",
+            Self::ToggleTildePath => {
+                "toggle the display of the home directory as ~ in the displayed paths"
+            }
             Self::TogglePreviewSecond => "toggle a preview on the second pane",
+            Self::ToggleTerminalPane => "open a terminal pane or toggle its focus",
+            Self::ToggleVisual => {
+                "toggle visual mode: flag the files between the anchor and the selection"
+            }
+            Self::TouchFlagged => "set the modification time of the flagged files to now",
             Self::TrashEmpty => "Empty the trash",
             Self::TrashMoveFile => "move to trash",
             Self::TrashOpen => "Open the trash (enter to restore, del clear)",
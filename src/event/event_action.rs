@@ -1,4 +1,6 @@
 use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::path;
 
 use anyhow::{Context, Result};
@@ -6,17 +8,24 @@ use indicatif::InMemoryTerm;
 
 use crate::app::{Focus, Status, Tab};
 use crate::common::{
-    filename_to_clipboard, filepath_to_clipboard, get_clipboard, is_in_path,
-    open_in_current_neovim, set_clipboard, tilde, CONFIG_PATH, GIO,
+    filename_to_clipboard, filepath_to_clipboard, get_clipboard, image_to_clipboard, is_in_path,
+    open_in_current_neovim, open_multiple_in_current_neovim, path_to_string, set_clipboard, tilde,
+    CONFIG_PATH, GIO,
+};
+use crate::config::{
+    archive_enter, delete_to_trash, fuzzy_respect_gitignore, ArchiveEnter, Bindings, START_FOLDER,
+};
+use crate::io::{
+    git_diff_head, git_log, open_in_pager, open_shell_in_tmux, open_shell_in_window, read_log,
+    Extension, GitFileStatus, Internal,
 };
-use crate::config::{Bindings, START_FOLDER};
-use crate::io::{open_shell_in_window, read_log};
 use crate::log_info;
 use crate::log_line;
 use crate::modes::{
-    help_string, lsblk_and_cryptsetup_installed, ContentWindow, Direction as FuzzyDirection,
-    Display, FuzzyKind, InputCompleted, InputSimple, LeaveMenu, MarkAction, Menu, Navigate,
-    NeedConfirmation, PreviewBuilder, RemovableDevices, Search, Selectable,
+    help_string, lsblk_and_cryptsetup_installed, CaseTransform, ContentWindow, CopyMove,
+    Direction as FuzzyDirection, Display, FileInfo, FuzzyKind, InputCompleted, InputSimple,
+    LeaveMenu, MarkAction, Menu, Navigate, NeedConfirmation, PreviewBuilder, RemovableDevices,
+    Search, Selectable,
 };
 
 /// Links events from tuikit to custom actions.
@@ -36,11 +45,54 @@ impl EventAction {
         Ok(())
     }
 
+    /// Quit and print the selected directory instead of the current one.
+    /// If the selected file isn't a directory, its parent is printed.
+    pub fn quit_cd_selected(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Self::quit(status);
+        }
+        let tab = status.current_tab();
+        let selected = tab.current_file()?;
+        let final_path = if selected.is_dir() {
+            path_to_string(&selected.path)
+        } else {
+            path_to_string(&tab.directory_of_selected()?)
+        };
+        status.internal_settings.quit_with_final_path(final_path);
+        Ok(())
+    }
+
+    /// Quit and print the directory where fm was started, so the shell doesn't change its directory.
+    pub fn quit_without_cd(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Self::quit(status);
+        }
+        let final_path = match std::env::var("PWD") {
+            Ok(pwd) => pwd,
+            Err(_) => path_to_string(&std::env::current_dir()?),
+        };
+        status.internal_settings.quit_with_final_path(final_path);
+        Ok(())
+    }
+
     /// Refresh the current view, reloading the files. Move the selection to top.
     pub fn refresh_view(status: &mut Status) -> Result<()> {
         status.refresh_view()
     }
 
+    /// Refresh the current view and log what changed since the last refresh:
+    /// number of files added, removed and the variation of their size.
+    pub fn refresh_changes(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        match status.current_tab_mut().refresh_with_changes()? {
+            Some(changes) => log_line!("{changes}"),
+            None => log_line!("Nothing changed since the last refresh"),
+        }
+        Ok(())
+    }
+
     /// Refresh the views if files were modified in current directory.
     pub fn refresh_if_needed(status: &mut Status) -> Result<()> {
         status.menu.flagged.remove_non_existant();
@@ -55,6 +107,13 @@ impl EventAction {
     /// Leave current mode to normal mode.
     /// Reset the inputs and completion, reset the window, exit the preview.
     pub fn reset_mode(status: &mut Status) -> Result<()> {
+        if status.cancel_streamed_command() {
+            return Ok(());
+        }
+        if status.current_tab().visual.is_active() {
+            status.toggle_visual();
+            return Ok(());
+        }
         if status.focus.is_file() && status.current_tab().display_mode.is_preview() {
             status.leave_preview()?;
         }
@@ -74,6 +133,60 @@ impl EventAction {
         Ok(())
     }
 
+    /// Toggle the files grouped by extension in directory mode.
+    /// A header line is displayed before each group.
+    pub fn toggle_group_by_extension(status: &mut Status) -> Result<()> {
+        status.session.toggle_group_by_extension();
+        let group_by_extension = status.session.group_by_extension();
+        status
+            .tabs
+            .iter_mut()
+            .for_each(|tab| tab.set_group_by_extension(group_by_extension));
+        Ok(())
+    }
+
+    /// Toggle the second pane listing the directory selected in the first one.
+    pub fn toggle_follow_selection(status: &mut Status) -> Result<()> {
+        status.toggle_follow_selection()
+    }
+
+    /// Toggle the line numbers of text previews between absolute and relative to the top line.
+    pub fn toggle_relative_line_numbers(status: &mut Status) -> Result<()> {
+        status.session.toggle_relative_line_numbers();
+        Ok(())
+    }
+
+    /// Toggle the display of the home directory as `~` in the headers.
+    pub fn toggle_tilde_path(status: &mut Status) -> Result<()> {
+        status.session.toggle_tilde_path();
+        Ok(())
+    }
+
+    /// Move to the real location of the selected symlink target, in its own directory.
+    pub fn follow_symlink_to_target(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.follow_symlink_to_target()
+    }
+
+    /// Move to the real path of the current directory, resolving every symlink of the path.
+    pub fn cd_to_real_path(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        status.cd_to_real_path()
+    }
+
+    /// Open the selected directory in the other tab, which is selected.
+    /// The current tab is left unchanged. Does nothing for other files.
+    pub fn enter_in_other_tab(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.enter_in_other_tab()
+    }
+
     /// Toggle between dualpane and single pane. Does nothing if the width
     /// is too low to display both panes.
     pub fn toggle_dualpane(status: &mut Status) -> Result<()> {
@@ -108,14 +221,31 @@ impl EventAction {
         status.refresh_view()
     }
 
+    /// Cycle the display mode of the current tab: Directory -> Tree -> Directory.
+    /// Preview and Fuzzy aren't affected since they require an explicit entry.
+    pub fn cycle_display_mode(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        match status.current_tab().display_mode {
+            Display::Directory | Display::Tree => Self::tree(status),
+            Display::Preview | Display::Fuzzy => Ok(()),
+        }
+    }
+
     /// Fold the current node of the tree.
     /// Has no effect on "file" nodes.
+    /// While previewing an archive, its selected folder is folded.
     pub fn tree_fold(status: &mut Status) -> Result<()> {
         if !status.focus.is_file() {
             return Ok(());
         }
         let tab = status.current_tab_mut();
+        if tab.preview_tree_toggle_fold() {
+            return Ok(());
+        }
         tab.tree.toggle_fold(&tab.users);
+        status.update_visual_flags();
         Ok(())
     }
 
@@ -128,6 +258,7 @@ impl EventAction {
         }
         let tab = status.current_tab_mut();
         tab.tree.unfold_all(&tab.users);
+        status.update_visual_flags();
         Ok(())
     }
 
@@ -140,6 +271,7 @@ impl EventAction {
         }
         let tab = status.current_tab_mut();
         tab.tree.fold_all(&tab.users);
+        status.update_visual_flags();
         Ok(())
     }
 
@@ -169,6 +301,67 @@ impl EventAction {
         status.current_tab_mut().make_preview()
     }
 
+    /// Preview the diff of the selected file against its version in git HEAD.
+    /// The colors of git are preserved.
+    /// Does nothing but logging if the file isn't tracked by git or is unchanged.
+    pub fn git_diff_selected(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        let Ok(file_info) = status.current_tab().current_file() else {
+            return Ok(());
+        };
+        let path = file_info.path.to_path_buf();
+        match git_diff_head(&path) {
+            Ok(diff) if diff.is_empty() => {
+                log_line!("No change since HEAD for {path}", path = path.display())
+            }
+            Ok(diff) => status.preview_command_output(
+                diff,
+                format!("git diff HEAD -- {path}", path = path.display()),
+            ),
+            Err(error) => log_line!("{error}"),
+        }
+        Ok(())
+    }
+
+    /// Preview the last commits affecting the selected directory, or the directory of the selected file.
+    /// The colors of git are preserved.
+    /// Does nothing but logging outside of a git repository.
+    pub fn git_log(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        let Ok(file_info) = status.current_tab().current_file() else {
+            return Ok(());
+        };
+        let directory = if file_info.path.is_dir() {
+            file_info.path.to_path_buf()
+        } else {
+            status.current_tab().directory_of_selected()?.to_path_buf()
+        };
+        match git_log(&directory) {
+            Ok(log) if log.is_empty() => {
+                log_line!("No commit for {path}", path = directory.display())
+            }
+            Ok(log) => status.preview_command_output(
+                log,
+                format!("git log -- {path}", path = directory.display()),
+            ),
+            Err(error) => log_line!("{error}"),
+        }
+        Ok(())
+    }
+
+    /// Pin the selected file : the second pane keeps previewing it while browsing.
+    /// Another call unpins it.
+    pub fn pin_preview(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.toggle_pinned_preview()
+    }
+
     /// Toggle the display of hidden files.
     pub fn toggle_hidden(status: &mut Status) -> Result<()> {
         if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
@@ -177,6 +370,23 @@ impl EventAction {
         status.current_tab_mut().toggle_hidden()
     }
 
+    /// Toggle the display of hidden files in the current directory only.
+    pub fn toggle_hidden_here(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        status.current_tab_mut().toggle_hidden_here()
+    }
+
+    /// Reverse the order of the current sort or restore it.
+    /// It's kept when another kind of sort is chosen.
+    pub fn toggle_sort_reversed(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        status.current_tab_mut().toggle_sort_reversed()
+    }
+
     /// Remove every flag on files in this directory and others.
     pub fn clear_flags(status: &mut Status) -> Result<()> {
         if status.focus.is_file() {
@@ -213,6 +423,48 @@ impl EventAction {
         Ok(())
     }
 
+    /// Enter the mode flagging the files of the current directory whose content matches a pattern.
+    pub fn flagged_from_grep(status: &mut Status) -> Result<()> {
+        if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(InputSimple::GrepFlag)
+        ) {
+            return status.reset_menu_mode().map(|_| ());
+        }
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::GrepFlag))
+    }
+
+    /// Enter the mode saving the flagged files to a file.
+    pub fn flagged_save(status: &mut Status) -> Result<()> {
+        if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(InputSimple::SaveFlagged)
+        ) {
+            return status.reset_menu_mode().map(|_| ());
+        }
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::SaveFlagged))
+    }
+
+    /// Enter the mode replacing the flagged files by those saved in a file.
+    pub fn flagged_load(status: &mut Status) -> Result<()> {
+        if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(InputSimple::LoadFlagged)
+        ) {
+            return status.reset_menu_mode().map(|_| ());
+        }
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::LoadFlagged))
+    }
+
     /// Reverse every flag in _current_ directory. Flagged files in other
     /// directory aren't affected.
     pub fn reverse_flags(status: &mut Status) -> Result<()> {
@@ -230,6 +482,22 @@ impl EventAction {
         Ok(())
     }
 
+    /// Open a terminal pane at the bottom of the screen, running a shell in the current directory.
+    /// If it's already opened, toggle its focus. While it's focused, every key is sent to the shell.
+    /// The pane is closed when the shell exits.
+    pub fn toggle_terminal_pane(status: &mut Status) -> Result<()> {
+        status.toggle_terminal_pane()
+    }
+
+    /// Enter or leave the visual mode.
+    /// While it's active, moving the selection flags every visible file between the anchor and the selected file.
+    pub fn toggle_visual(status: &mut Status) -> Result<()> {
+        if status.focus.is_file() {
+            status.toggle_visual();
+        }
+        Ok(())
+    }
+
     /// Enter the rename mode.
     /// Keep a track of the current mode to ensure we rename the correct file.
     /// When we enter rename from a "tree" mode, we'll need to rename the selected file in the tree,
@@ -257,6 +525,55 @@ impl EventAction {
         Ok(())
     }
 
+    /// Enter the split mode, asking the size of the parts of the selected file.
+    pub fn split_file(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(InputSimple::SplitFile)
+        ) {
+            status.reset_menu_mode()?;
+            return Ok(());
+        };
+        if !status.current_tab().current_file()?.path.is_file() {
+            log_line!("Only files can be split");
+            return Ok(());
+        }
+        status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::SplitFile))
+    }
+
+    /// Join the parts of a split file, the selected file being one of them.
+    pub fn join_parts(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        status.join_parts()
+    }
+
+    /// Enter the copy as mode, asking the name of a copy of the selected file.
+    /// The input is filled with the current name.
+    pub fn copy_as(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(InputSimple::CopyAs)
+        ) {
+            status.reset_menu_mode()?;
+            return Ok(());
+        };
+        let selected = status.current_tab().current_file()?;
+        if matches!(selected.filename.as_ref(), "." | "..") {
+            return Ok(());
+        }
+        status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::CopyAs))?;
+        status.menu.input.replace(&selected.filename);
+        Ok(())
+    }
+
     /// Enter a copy paste mode.
     /// A confirmation is asked before copying all flagged files to
     /// the current directory.
@@ -289,6 +606,24 @@ impl EventAction {
         Ok(())
     }
 
+    /// Copy the flagged files, or the selected one if none is flagged, to the directory of the other tab.
+    /// The current tab isn't modified. Does nothing if the other tab isn't displayed.
+    pub fn copy_to_other_tab(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.cut_or_copy_to_other_tab(CopyMove::Copy)
+    }
+
+    /// Move the flagged files, or the selected one if none is flagged, to the directory of the other tab.
+    /// The current tab isn't modified. Does nothing if the other tab isn't displayed.
+    pub fn move_to_other_tab(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.cut_or_copy_to_other_tab(CopyMove::Move)
+    }
+
     fn set_copy_paste(status: &mut Status, copy_or_move: NeedConfirmation) -> Result<()> {
         if status.menu.flagged.is_empty() {
             return Ok(());
@@ -317,10 +652,28 @@ impl EventAction {
         status.clear_flags_and_reset_view()
     }
 
-    /// Enter the delete mode.
+    /// Enter the delete mode, or move the files to the trash if `delete_to_trash` is set in the config file.
     /// A confirmation is then asked before deleting all the flagged files.
     /// If no file is flagged, flag the selected one before entering the mode.
     pub fn delete_file(status: &mut Status) -> Result<()> {
+        if delete_to_trash() {
+            return Self::trash_move_file(status);
+        }
+        Self::delete_file_permanently(status)
+    }
+
+    /// Delete the flagged files permanently, whatever the config, after a confirmation.
+    pub fn delete_permanently(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        Self::delete_file_permanently(status)
+    }
+
+    /// Enter the delete mode.
+    /// A confirmation is then asked before deleting all the flagged files.
+    /// If no file is flagged, flag the selected one before entering the mode.
+    fn delete_file_permanently(status: &mut Status) -> Result<()> {
         if status.menu.flagged.is_empty() {
             Self::toggle_flag(status)?;
         }
@@ -345,12 +698,17 @@ impl EventAction {
 
     /// Enter a new node mode.
     fn new_node(status: &mut Status, input_kind: InputSimple) -> Result<()> {
-        if !matches!(input_kind, InputSimple::Newdir | InputSimple::Newfile) {
+        if !matches!(
+            input_kind,
+            InputSimple::Newdir | InputSimple::NewdirAndEnter | InputSimple::Newfile
+        ) {
             return Ok(());
         }
         if matches!(
             status.current_tab().menu_mode,
-            Menu::InputSimple(InputSimple::Newdir | InputSimple::Newfile)
+            Menu::InputSimple(
+                InputSimple::Newdir | InputSimple::NewdirAndEnter | InputSimple::Newfile
+            )
         ) {
             status.reset_menu_mode()?;
             return Ok(());
@@ -369,6 +727,11 @@ impl EventAction {
         Self::new_node(status, InputSimple::Newdir)
     }
 
+    /// Enter the new dir mode, the created directory is entered.
+    pub fn new_dir_and_enter(status: &mut Status) -> Result<()> {
+        Self::new_node(status, InputSimple::NewdirAndEnter)
+    }
+
     /// Enter the new file mode.
     pub fn new_file(status: &mut Status) -> Result<()> {
         Self::new_node(status, InputSimple::Newfile)
@@ -396,7 +759,21 @@ impl EventAction {
             status.thumbnail_directory_video();
             Ok(())
         } else {
-            EventAction::open_file(status)
+            Self::enter_archive_or_open(status)
+        }
+    }
+
+    /// Browse or extract the selected archive according to `archive_enter` in the config file.
+    /// Other files, and archives fm can't extract, are opened.
+    fn enter_archive_or_open(status: &mut Status) -> Result<()> {
+        let path = status.current_tab().current_file()?.path;
+        let Some(extractor) = Internal::extractor(&path) else {
+            return EventAction::open_file(status);
+        };
+        match archive_enter() {
+            ArchiveEnter::Open => EventAction::open_file(status),
+            ArchiveEnter::Browse => status.current_tab_mut().make_preview(),
+            ArchiveEnter::Extract => status.extract_archive(&path, extractor),
         }
     }
 
@@ -406,7 +783,7 @@ impl EventAction {
         if path.is_dir() {
             status.current_tab_mut().tree_enter_dir(path)
         } else {
-            EventAction::open_file(status)
+            Self::enter_archive_or_open(status)
         }
     }
 
@@ -432,6 +809,22 @@ impl EventAction {
         status.open_flagged_files()
     }
 
+    /// Extract the selected file of the previewed archive into a temporary folder and open it.
+    pub fn open_archive_entry(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.open_archive_entry()
+    }
+
+    /// Open the notes file of the current directory, creating it if needed.
+    pub fn open_notes(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.open_notes()
+    }
+
     /// Enter the execute mode. Most commands must be executed to allow for
     /// a confirmation.
     pub fn exec(status: &mut Status) -> Result<()> {
@@ -497,6 +890,167 @@ impl EventAction {
         Ok(())
     }
 
+    /// Compute the total size of the flagged files, walking flagged directories recursively.
+    /// The size is computed in a thread, displayed once done and in the flagged files menu.
+    pub fn flagged_total_size(status: &mut Status) -> Result<()> {
+        status.flagged_total_size();
+        Ok(())
+    }
+
+    /// Display every information about the selected file in full screen:
+    /// path, type, size, permissions, owner, timestamps, MIME type, opener...
+    pub fn properties(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        status.properties()
+    }
+
+    /// Set the selected image as wallpaper.
+    pub fn set_wallpaper(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        status.set_wallpaper()
+    }
+
+    /// Run the selected script and preview its output.
+    /// A confirmation is asked first since it may run arbitrary code.
+    pub fn preview_script_output(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.ask_run_script()
+    }
+
+    /// Compute the size of every child of the current directory in a thread
+    /// and display them sorted by size in the preview.
+    pub fn disk_usage(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.disk_usage()
+    }
+
+    /// Flag the files of the current tab which are missing or different in the other tab.
+    pub fn compare_tabs(status: &mut Status) -> Result<()> {
+        if status.focus.is_file() {
+            status.compare_tabs();
+        }
+        Ok(())
+    }
+
+    /// Flag the files of the current directory modified before the selected file.
+    pub fn flag_older(status: &mut Status) -> Result<()> {
+        status.flag_by_modification(Ordering::Less);
+        Ok(())
+    }
+
+    /// Flag the files of the current directory modified after the selected file.
+    pub fn flag_newer(status: &mut Status) -> Result<()> {
+        status.flag_by_modification(Ordering::Greater);
+        Ok(())
+    }
+
+    /// Rename the flagged images of the current directory to their capture date,
+    /// read from EXIF, like `2023-05-01_14-30-22.jpg`.
+    /// The new names are displayed and a confirmation is asked.
+    pub fn exif_rename(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.exif_rename()
+    }
+
+    /// Lowercase the flagged filenames of the current directory, keeping their extensions.
+    pub fn rename_lowercase(status: &mut Status) -> Result<()> {
+        Self::case_rename(status, CaseTransform::Lowercase)
+    }
+
+    /// Uppercase the flagged filenames of the current directory, keeping their extensions.
+    pub fn rename_uppercase(status: &mut Status) -> Result<()> {
+        Self::case_rename(status, CaseTransform::Uppercase)
+    }
+
+    /// Convert the flagged filenames of the current directory to snake_case, keeping their extensions.
+    pub fn rename_snake_case(status: &mut Status) -> Result<()> {
+        Self::case_rename(status, CaseTransform::SnakeCase)
+    }
+
+    /// URL decode the flagged filenames of the current directory, like `My%20File.pdf` -> `My File.pdf`.
+    /// The new names are displayed and a confirmation is asked.
+    pub fn rename_url_decode(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.url_decode_rename()
+    }
+
+    /// Prefix the flagged filenames of the current directory with a numbered sequence, like `01_`, `02_`...
+    /// The first number and the width are asked, then the new names are displayed and a confirmation is asked.
+    pub fn numbered_rename(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        if status.flagged_in_display_order().is_empty() {
+            log_line!("Numbered rename: flag some files first");
+            return Ok(());
+        }
+        status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::NumberedRename))
+    }
+
+    /// Replace the extension of the flagged filenames of the current directory, keeping their base names.
+    /// The extension is asked, then the new names are displayed and a confirmation is asked.
+    pub fn rename_extension(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        if status.flagged_in_current_dir().is_empty() {
+            log_line!("Extension rename: flag some files first");
+            return Ok(());
+        }
+        status.set_menu_mode(
+            status.index,
+            Menu::InputSimple(InputSimple::ExtensionRename),
+        )
+    }
+
+    /// Enter the mode setting an extended attribute of the selected file.
+    pub fn set_xattr(status: &mut Status) -> Result<()> {
+        if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(InputSimple::SetXattr)
+        ) {
+            return status.reset_menu_mode().map(|_| ());
+        }
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::SetXattr))
+    }
+
+    /// Enter the mode removing an extended attribute of the selected file.
+    pub fn remove_xattr(status: &mut Status) -> Result<()> {
+        if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(InputSimple::RemoveXattr)
+        ) {
+            return status.reset_menu_mode().map(|_| ());
+        }
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::RemoveXattr))
+    }
+
+    /// The new names are displayed and a confirmation is asked.
+    fn case_rename(status: &mut Status, case_transform: CaseTransform) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.case_rename(case_transform)
+    }
+
     /// Enter the search mode.
     /// Matching items are displayed as you type them.
     pub fn search(status: &mut Status) -> Result<()> {
@@ -508,9 +1062,17 @@ impl EventAction {
         }
         let tab = status.current_tab_mut();
         tab.search = Search::empty();
+        status.menu.search_error = None;
+        status.menu.search_flagged.clear();
         status.set_menu_mode(status.index, Menu::InputCompleted(InputCompleted::Search))
     }
 
+    /// Switch the search between substring and regex matching.
+    /// In regex mode, every matching file is flagged while searching in a directory.
+    pub fn toggle_search_regex(status: &mut Status) -> Result<()> {
+        status.toggle_search_kind()
+    }
+
     /// Enter the regex mode.
     /// Every file matching the typed regex will be flagged.
     pub fn regex_match(status: &mut Status) -> Result<()> {
@@ -530,6 +1092,26 @@ impl EventAction {
         }
     }
 
+    /// Enter the glob mode.
+    /// Every file matching the typed glob will be flagged when the input is validated.
+    pub fn glob_match(status: &mut Status) -> Result<()> {
+        if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(InputSimple::GlobMatch)
+        ) {
+            status.reset_menu_mode()?;
+            return Ok(());
+        }
+        if matches!(
+            status.current_tab().display_mode,
+            Display::Tree | Display::Directory
+        ) {
+            status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::GlobMatch))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Display the help which can be navigated and displays the configrable
     /// binds.
     pub fn help(status: &mut Status, binds: &Bindings) -> Result<()> {
@@ -579,16 +1161,55 @@ impl EventAction {
     /// Open a new terminal in current directory and current window.
     /// The shell is a fork of current process and will exit if the application
     /// is terminated first.
+    /// Inside tmux, the shell may be opened in a new pane, a new window or a new terminal,
+    /// see `shell_in_tmux` in the config file.
     pub fn shell(status: &mut Status) -> Result<()> {
         if !status.focus.is_file() {
             return Ok(());
         }
+        let path = status.current_tab().directory_of_selected()?.to_owned();
+        if open_shell_in_tmux(&path, &status.internal_settings.opener.terminal)? {
+            return Ok(());
+        }
         status.internal_settings.disable_display();
         open_shell_in_window()?;
         status.internal_settings.enable_display();
         Ok(())
     }
 
+    /// Read the selected file in a pager (`less` by default), full screen.
+    /// The display is given back once the pager exits.
+    pub fn pager(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        let file = status.current_tab().current_file()?;
+        if file.is_dir() {
+            return Ok(());
+        }
+        status.internal_settings.disable_display();
+        open_in_pager(&file.path)?;
+        status.internal_settings.enable_display();
+        Ok(())
+    }
+
+    /// Set the modification time of the flagged files, or the selected one if nothing is flagged, to now.
+    pub fn touch_flagged(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        status.touch_flagged()
+    }
+
+    /// Display a large preview of the selected file over the panes, without entering the preview mode.
+    /// The next key press hides it.
+    pub fn quick_look(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() || status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        status.toggle_quick_look()
+    }
+
     /// Enter the shell input command mode. The user can type a command which
     /// will be parsed and run.
     pub fn shell_command(status: &mut Status) -> Result<()> {
@@ -601,6 +1222,31 @@ impl EventAction {
         status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::ShellCommand))
     }
 
+    /// Enter the streamed shell input command mode. The user can type a command which
+    /// will be parsed and run. Its output is displayed live in the preview.
+    pub fn shell_stream(status: &mut Status) -> Result<()> {
+        if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(InputSimple::ShellStream)
+        ) {
+            status.reset_menu_mode()?;
+        }
+        status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::ShellStream))
+    }
+
+    /// Enter the shell dry run mode. The typed command is expanded and displayed,
+    /// it's only run after a confirmation.
+    pub fn shell_dry_run(status: &mut Status) -> Result<()> {
+        if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(InputSimple::ShellDryRun)
+        ) {
+            status.reset_menu_mode()?;
+            return Ok(());
+        }
+        status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::ShellDryRun))
+    }
+
     /// Enter the shell menu mode. You can pick a TUI application to be run
     pub fn tui_menu(status: &mut Status) -> Result<()> {
         if matches!(
@@ -670,6 +1316,15 @@ impl EventAction {
         Ok(())
     }
 
+    /// Import ranger bookmarks into the marks. Chars already used are skipped.
+    pub fn marks_import_ranger(status: &mut Status) -> Result<()> {
+        status.menu.marks.setup();
+        if let Err(error) = status.menu.marks.import_ranger_bookmarks() {
+            log_line!("{error}");
+        }
+        Ok(())
+    }
+
     /// Enter Marks jump mode, allowing to jump to a marked file.
     pub fn marks_jump(status: &mut Status) -> Result<()> {
         if matches!(
@@ -743,7 +1398,7 @@ impl EventAction {
 
     /// Send a signal to parent NVIM process, picking files.
     /// If there's no flagged file, it picks the selected one.
-    /// otherwise, flagged files are picked.
+    /// otherwise, flagged files are picked and become the argument list of neovim.
     /// If no RPC server were provided at launch time - which may happen for
     /// reasons unknow to me - it does nothing.
     /// It requires the "nvim-send" application to be in $PATH.
@@ -762,10 +1417,7 @@ impl EventAction {
             };
             open_in_current_neovim(&fileinfo.path, &nvim_server);
         } else {
-            let flagged = status.menu.flagged.content.clone();
-            for file_path in flagged.iter() {
-                open_in_current_neovim(file_path, &nvim_server)
-            }
+            open_multiple_in_current_neovim(&status.menu.flagged.content, &nvim_server);
         }
 
         Ok(())
@@ -886,6 +1538,26 @@ impl EventAction {
         Ok(())
     }
 
+    /// Move the selected file one row up in the displayed order, overriding the sort.
+    /// Files aren't modified on disk, the order is reset by a refresh or a sort.
+    pub fn reorder_up(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.current_tab_mut().reorder_selected(true);
+        Ok(())
+    }
+
+    /// Move the selected file one row down in the displayed order, overriding the sort.
+    /// Files aren't modified on disk, the order is reset by a refresh or a sort.
+    pub fn reorder_down(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.current_tab_mut().reorder_selected(false);
+        Ok(())
+    }
+
     fn move_display_up(status: &mut Status) -> Result<()> {
         let tab = status.current_tab_mut();
         match tab.display_mode {
@@ -894,6 +1566,7 @@ impl EventAction {
             Display::Tree => tab.tree_select_prev(),
             Display::Fuzzy => status.fuzzy_navigate(FuzzyDirection::Up)?,
         }
+        status.update_visual_flags();
         Ok(())
     }
 
@@ -905,6 +1578,7 @@ impl EventAction {
             Display::Tree => tab.tree_select_next(),
             Display::Fuzzy => status.fuzzy_navigate(FuzzyDirection::Down)?,
         }
+        status.update_visual_flags();
         Ok(())
     }
     /// Move down one row in modes allowing movements.
@@ -973,6 +1647,38 @@ impl EventAction {
         }
     }
 
+    /// Move the cursor one word left in mode requiring text input.
+    /// Does nothing if no text is being typed.
+    pub fn move_word_left(status: &mut Status) -> Result<()> {
+        if status.focus.is_file() {
+            if let Some(fuzzy) = &mut status.fuzzy {
+                fuzzy.input.cursor_word_left();
+            }
+        } else if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(_) | Menu::InputCompleted(_)
+        ) {
+            status.menu.input.cursor_word_left();
+        }
+        Ok(())
+    }
+
+    /// Move the cursor one word right in mode requiring text input.
+    /// Does nothing if no text is being typed.
+    pub fn move_word_right(status: &mut Status) -> Result<()> {
+        if status.focus.is_file() {
+            if let Some(fuzzy) = &mut status.fuzzy {
+                fuzzy.input.cursor_word_right();
+            }
+        } else if matches!(
+            status.current_tab().menu_mode,
+            Menu::InputSimple(_) | Menu::InputCompleted(_)
+        ) {
+            status.menu.input.cursor_word_right();
+        }
+        Ok(())
+    }
+
     pub fn focus_follow_mouse(status: &mut Status, row: u16, col: u16) -> Result<()> {
         status.set_focus_from_pos(row, col)?;
         Ok(())
@@ -988,6 +1694,16 @@ impl EventAction {
         Self::click(status, binds, row, col)
     }
 
+    /// Dragging the mouse with the left button flags the files it passes over.
+    pub fn left_drag(status: &mut Status, row: u16) -> Result<()> {
+        status.mouse_drag(row)
+    }
+
+    /// Releasing the left button ends the flagging started by a drag.
+    pub fn left_release(status: &mut Status) -> Result<()> {
+        status.mouse_release()
+    }
+
     /// Right click gives focus to the window and open the context menu
     pub fn right_click(status: &mut Status, binds: &Bindings, row: u16, col: u16) -> Result<()> {
         Self::click(status, binds, row, col)?;
@@ -1081,6 +1797,7 @@ impl EventAction {
                 Display::Tree => tab.tree_go_to_root()?,
                 Display::Fuzzy => status.fuzzy_start()?,
             };
+            status.update_visual_flags();
             status.update_second_pane_for_preview()
         } else {
             match status.current_tab().menu_mode {
@@ -1102,6 +1819,7 @@ impl EventAction {
                 Display::Tree => tab.tree_go_to_bottom_leaf(),
                 Display::Fuzzy => status.fuzzy_end()?,
             };
+            status.update_visual_flags();
             status.update_second_pane_for_preview()?;
         } else {
             match status.current_tab().menu_mode {
@@ -1147,6 +1865,7 @@ impl EventAction {
             }
             Display::Fuzzy => status.fuzzy_navigate(FuzzyDirection::PageUp)?,
         };
+        status.update_visual_flags();
         Ok(())
     }
 
@@ -1184,6 +1903,7 @@ impl EventAction {
             }
             Display::Fuzzy => status.fuzzy_navigate(FuzzyDirection::PageDown)?,
         };
+        status.update_visual_flags();
         Ok(())
     }
 
@@ -1211,12 +1931,36 @@ impl EventAction {
         Ok(())
     }
 
+    /// Cycle the number of bytes per line (8, 16, 32) of the previewed binary files.
+    pub fn binary_cycle_width(status: &mut Status) -> Result<()> {
+        status.cycle_binary_bytes_per_row();
+        Ok(())
+    }
+
+    /// Display only the flagged files of the current directory. Call it again to display every file.
+    pub fn toggle_flagged_only(status: &mut Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        status.toggle_flagged_only()
+    }
+
     /// Start a fuzzy find with skim.
+    /// Files ignored by git are skipped unless `fuzzy_respect_gitignore` is false in the config file.
     pub fn fuzzyfind(status: &mut Status) -> Result<()> {
+        Self::fuzzyfind_files(status, fuzzy_respect_gitignore())
+    }
+
+    /// Start a fuzzy find with skim, files ignored by git included.
+    pub fn fuzzyfind_all(status: &mut Status) -> Result<()> {
+        Self::fuzzyfind_files(status, false)
+    }
+
+    fn fuzzyfind_files(status: &mut Status, respect_gitignore: bool) -> Result<()> {
         status.force_clear();
         status.fuzzy_init(FuzzyKind::File);
         status.current_tab_mut().set_display_mode(Display::Fuzzy);
-        status.fuzzy_find_files()?;
+        status.fuzzy_find_files(respect_gitignore)?;
         status.update_second_pane_for_preview()
     }
 
@@ -1237,6 +1981,13 @@ impl EventAction {
         Ok(())
     }
 
+    /// Start a fuzzy find for a previous command: shell command, exec or action.
+    pub fn fuzzyfind_command(status: &mut Status) -> Result<()> {
+        status.fuzzy_init(FuzzyKind::Command);
+        status.current_tab_mut().set_display_mode(Display::Fuzzy);
+        status.fuzzy_command_history()
+    }
+
     /// Copy the filename of the selected file in normal mode.
     pub fn copy_filename(status: &Status) -> Result<()> {
         if !status.focus.is_file() {
@@ -1271,12 +2022,70 @@ impl EventAction {
         Ok(())
     }
 
+    /// Copy the path of the current directory to the clipboard.
+    /// It works in every display mode since the tab always knows its directory.
+    pub fn copy_current_dir(status: &Status) -> Result<()> {
+        filepath_to_clipboard(&status.current_tab().directory.path);
+        Ok(())
+    }
+
+    /// Copy the visible lines of the preview to the clipboard, without colors.
+    pub fn copy_preview_visible(status: &Status) -> Result<()> {
+        let window = &status.current_tab().window;
+        Self::copy_preview(status, window.top, window.bottom)
+    }
+
+    /// Copy the whole preview to the clipboard, without colors.
+    pub fn copy_preview_all(status: &Status) -> Result<()> {
+        Self::copy_preview(status, 0, usize::MAX)
+    }
+
+    fn copy_preview(status: &Status, top: usize, bottom: usize) -> Result<()> {
+        if !status.focus.is_file() || !status.current_tab().display_mode.is_preview() {
+            return Ok(());
+        }
+        let Some(text) = status.current_tab().preview.text(top, bottom) else {
+            log_line!("Nothing to copy from this preview");
+            return Ok(());
+        };
+        set_clipboard(text);
+        log_line!("Copied the preview to the clipboard");
+        Ok(())
+    }
+
+    /// Copy the selected image to the clipboard as image data.
+    /// Does nothing but logging if the selected file isn't an image.
+    pub fn copy_image_to_clipboard(status: &Status) -> Result<()> {
+        if !status.focus.is_file() {
+            return Ok(());
+        }
+        match status.current_tab().display_mode {
+            Display::Tree | Display::Directory => {
+                let Ok(file_info) = status.current_tab().current_file() else {
+                    return Ok(());
+                };
+                if !matches!(
+                    Extension::matcher(&file_info.extension.to_lowercase()),
+                    Extension::Bitmap
+                ) {
+                    log_line!("{path} isn't an image", path = file_info.path.display());
+                    return Ok(());
+                }
+                image_to_clipboard(&file_info.path)?;
+            }
+            _ => return Ok(()),
+        }
+        Ok(())
+    }
+
     /// Move flagged files to the trash directory.
     /// If no file is flagged, flag the selected file.
     /// More information in the trash crate itself.
     /// If the file is mounted on the $topdir of the trash (aka the $HOME mount point),
     /// it is moved there.
-    /// Else, nothing is done.
+    /// Else, it's moved to the `.Trash-<uid>` folder of its mount point or,
+    /// if it can't be used, copied to the home trash and deleted.
+    /// Files are moved in a thread, displaying a progress bar.
     pub fn trash_move_file(status: &mut Status) -> Result<()> {
         if !status.focus.is_file() {
             return Ok(());
@@ -1284,14 +2093,7 @@ impl EventAction {
         if status.menu.flagged.is_empty() {
             Self::toggle_flag(status)?;
         }
-
-        status.menu.trash.update()?;
-        for flagged in status.menu.flagged.content.iter() {
-            let _ = status.menu.trash.trash(flagged);
-        }
-        status.menu.flagged.clear();
-        status.current_tab_mut().refresh_view()?;
-        Ok(())
+        status.trash_flagged()
     }
 
     /// Ask the user if he wants to empty the trash.
@@ -1404,6 +2206,22 @@ impl EventAction {
         Ok(())
     }
 
+    /// Enter the image conversion mode, where the user picks the format of the converted images.
+    pub fn convert_images(status: &mut Status) -> Result<()> {
+        if matches!(
+            status.current_tab().menu_mode,
+            Menu::Navigate(Navigate::ConvertImages)
+        ) {
+            status.reset_menu_mode()?;
+        } else {
+            if status.menu.image_converter.is_empty() {
+                status.menu.image_converter.setup();
+            }
+            status.set_menu_mode(status.index, Menu::Navigate(Navigate::ConvertImages))?;
+        }
+        Ok(())
+    }
+
     /// Enter the context menu mode where the user can choose a basic file action.
     pub fn context(status: &mut Status) -> Result<()> {
         if matches!(
@@ -1442,6 +2260,16 @@ impl EventAction {
         status.run_custom_command(input_string)
     }
 
+    /// Move to the parent directory of `path` and select it.
+    /// It's used by the breadcrumb of the header: clicking an ancestor reveals the directory we came from.
+    pub fn reveal(status: &mut Status, path: &str) -> Result<()> {
+        if path.is_empty() {
+            return Ok(());
+        }
+        status.current_tab_mut().cd_to_file(path::Path::new(path))?;
+        status.update_second_pane_for_preview()
+    }
+
     /// Enter the remote mount mode where the user can provide an username, an adress and
     /// a mount point to mount a remote device through SSHFS.
     pub fn remote_mount(status: &mut Status) -> Result<()> {
@@ -1534,10 +2362,117 @@ impl EventAction {
         Ok(())
     }
 
+    /// Append a line of output of the streamed shell command to its preview.
+    pub fn streamed_line(status: &mut Status, pid: u32, line: String) -> Result<()> {
+        status.append_streamed_line(pid, line);
+        Ok(())
+    }
+
+    /// The streamed shell command exited.
+    pub fn stream_finished(status: &mut Status, pid: u32, code: Option<i32>) -> Result<()> {
+        status.finish_streamed_command(pid, code);
+        Ok(())
+    }
+
     pub fn bulk_confirm(status: &mut Status) -> Result<()> {
         status.bulk_execute()
     }
 
+    /// The total size of the flagged files was computed.
+    pub fn flagged_total_size_computed(
+        status: &mut Status,
+        flagged: Vec<path::PathBuf>,
+        size: u64,
+    ) -> Result<()> {
+        status.set_flagged_total_size(flagged, size);
+        Ok(())
+    }
+
+    /// A child of the directory whose disk usage is computed was measured.
+    pub fn disk_usage_progress(
+        status: &mut Status,
+        path: path::PathBuf,
+        done: usize,
+        total: usize,
+        current: String,
+    ) -> Result<()> {
+        status.set_disk_usage_progress(&path, done, total, &current);
+        Ok(())
+    }
+
+    /// The size of every child of the directory was computed.
+    pub fn disk_usage_computed(
+        status: &mut Status,
+        path: path::PathBuf,
+        sizes: Vec<(path::PathBuf, u64)>,
+    ) -> Result<()> {
+        status.set_disk_usage(&path, sizes);
+        Ok(())
+    }
+
+    /// Some files of a huge directory were read in the background.
+    pub fn directory_chunk(status: &mut Status, id: u64, files: Vec<FileInfo>) -> Result<()> {
+        status.extend_directory(id, files);
+        Ok(())
+    }
+
+    /// Every file of a huge directory was read in the background.
+    pub fn directory_loaded(status: &mut Status, id: u64) -> Result<()> {
+        status.finish_directory_loading(id);
+        Ok(())
+    }
+
+    /// Files matching the grep flag pattern were found in the background, flag them.
+    pub fn files_with_matches(
+        status: &mut Status,
+        pattern: String,
+        paths: Vec<path::PathBuf>,
+    ) -> Result<()> {
+        status.flag_files_with_matches(&pattern, paths);
+        Ok(())
+    }
+
+    /// The git status of a directory was read in the background.
+    pub fn git_statuses(
+        status: &mut Status,
+        path: path::PathBuf,
+        statuses: Option<HashMap<path::PathBuf, GitFileStatus>>,
+    ) -> Result<()> {
+        status.set_git_statuses(&path, statuses);
+        Ok(())
+    }
+
+    pub fn removal_finished(status: &mut Status) -> Result<()> {
+        status.removal_finished()
+    }
+
+    /// The images were converted, flag them.
+    pub fn conversion_finished(status: &mut Status, converted: Vec<path::PathBuf>) -> Result<()> {
+        status.conversion_finished(converted)
+    }
+
+    /// A file is split or joined, update its progress.
+    pub fn split_join_progress(status: &mut Status, done: u64, total: u64) -> Result<()> {
+        if let Some((_, written, size)) = &mut status.internal_settings.split_join {
+            *written = done;
+            *size = total;
+        }
+        Ok(())
+    }
+
+    /// The file was split or joined.
+    pub fn split_join_finished(status: &mut Status) -> Result<()> {
+        status.internal_settings.split_join = None;
+        Ok(())
+    }
+
+    /// Stop the running deletion or trash move after the current file.
+    /// Files already removed aren't restored.
+    pub fn cancel_removal(status: &mut Status) -> Result<()> {
+        status.cancel_removal();
+        Ok(())
+    }
+
     pub fn file_copied(status: &mut Status) -> Result<()> {
         log_info!(
             "file copied - pool: {pool:?}",
@@ -1559,6 +2494,9 @@ impl EventAction {
 
     pub fn check_preview_fuzzy_tick(status: &mut Status) -> Result<()> {
         status.fuzzy_tick();
+        status.session.save_if_idle();
+        status.check_followed_selection()?;
+        status.check_pending_selection();
         status.check_preview()
     }
 }
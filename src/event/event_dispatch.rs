@@ -5,7 +5,7 @@ use crossterm::event::{
 
 use crate::app::Status;
 use crate::config::Bindings;
-use crate::event::{EventAction, FmEvents};
+use crate::event::{ActionMap, EventAction, FmEvents};
 use crate::modes::{
     Direction as FuzzyDirection, Display, InputSimple, LeaveMenu, MarkAction, Menu, Navigate,
 };
@@ -38,13 +38,47 @@ impl EventDispatcher {
             FmEvents::BulkExecute => EventAction::bulk_confirm(status),
             FmEvents::Refresh => EventAction::refresh_if_needed(status),
             FmEvents::FileCopied => EventAction::file_copied(status),
+            FmEvents::RemovalFinished => EventAction::removal_finished(status),
+            FmEvents::ConversionFinished(converted) => {
+                EventAction::conversion_finished(status, converted)
+            }
+            FmEvents::SplitJoinProgress(done, total) => {
+                EventAction::split_join_progress(status, done, total)
+            }
+            FmEvents::SplitJoinFinished => EventAction::split_join_finished(status),
             FmEvents::UpdateTick => EventAction::check_preview_fuzzy_tick(status),
             FmEvents::Action(action) => action.matcher(status, &self.binds),
+            FmEvents::StreamedLine(pid, line) => EventAction::streamed_line(status, pid, line),
+            FmEvents::StreamFinished(pid, code) => EventAction::stream_finished(status, pid, code),
+            FmEvents::TerminalPaneExited => status.close_terminal_pane(),
+            FmEvents::FlaggedTotalSize(flagged, size) => {
+                EventAction::flagged_total_size_computed(status, flagged, size)
+            }
+            FmEvents::DiskUsageProgress(path, done, total, current) => {
+                EventAction::disk_usage_progress(status, path, done, total, current)
+            }
+            FmEvents::DiskUsageComputed(path, sizes) => {
+                EventAction::disk_usage_computed(status, path, sizes)
+            }
+            FmEvents::DirectoryChunk(id, files) => EventAction::directory_chunk(status, id, files),
+            FmEvents::DirectoryLoaded(id) => EventAction::directory_loaded(status, id),
+            FmEvents::FilesWithMatches(pattern, paths) => {
+                EventAction::files_with_matches(status, pattern, paths)
+            }
+            FmEvents::GitStatuses(path, statuses) => {
+                EventAction::git_statuses(status, path, statuses)
+            }
             _ => Ok(()),
         }
     }
 
     fn match_key_event(&self, status: &mut Status, key: KeyEvent) -> Result<()> {
+        if status.close_quick_look() {
+            return Ok(());
+        }
+        if status.is_terminal_pane_focused() {
+            return self.terminal_pane_key_matcher(status, key);
+        }
         match key {
             KeyEvent {
                 code: KeyCode::Char(c),
@@ -59,6 +93,18 @@ impl EventDispatcher {
         Ok(())
     }
 
+    /// Forward the key to the shell of the terminal pane.
+    /// The key bound to `ToggleTerminalPane` gives the focus back to the files.
+    fn terminal_pane_key_matcher(&self, status: &mut Status, key: KeyEvent) -> Result<()> {
+        if matches!(self.binds.get(&key), Some(ActionMap::ToggleTerminalPane)) {
+            return status.toggle_terminal_pane();
+        }
+        let Some(terminal_pane) = &mut status.terminal_pane else {
+            return Ok(());
+        };
+        terminal_pane.write_key(key)
+    }
+
     fn match_mouse_event(&self, status: &mut Status, mouse_event: MouseEvent) -> Result<()> {
         match mouse_event.kind {
             MouseEventKind::ScrollUp => {
@@ -70,6 +116,10 @@ impl EventDispatcher {
             MouseEventKind::Down(MouseButton::Left) => {
                 EventAction::left_click(status, &self.binds, mouse_event.row, mouse_event.column)
             }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                EventAction::left_drag(status, mouse_event.row)
+            }
+            MouseEventKind::Up(MouseButton::Left) => EventAction::left_release(status),
             MouseEventKind::Down(MouseButton::Middle) => {
                 EventAction::middle_click(status, &self.binds, mouse_event.row, mouse_event.column)
             }
@@ -79,7 +129,10 @@ impl EventDispatcher {
             MouseEventKind::Moved => {
                 EventAction::focus_follow_mouse(status, mouse_event.row, mouse_event.column)
             }
-            _ => Ok(()),
+            kind => match self.binds.get_mouse(&kind) {
+                Some(action) => action.matcher(status, &self.binds),
+                None => Ok(()),
+            },
         }
     }
 
@@ -179,6 +232,10 @@ impl EventDispatcher {
             Navigate::RemovableDevices if c == 'm' => status.mount_removable(),
             Navigate::RemovableDevices if c == 'g' => status.go_to_removable(),
             Navigate::RemovableDevices if c == 'u' => status.umount_removable(),
+            Navigate::RemovableDevices if c == 'p' => status.power_off_removable(),
+            Navigate::RemovableDevices if c == 'l' => {
+                status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::MountByLabel))
+            }
 
             Navigate::Marks(MarkAction::Jump) => status.marks_jump_char(c),
             Navigate::Marks(MarkAction::New) => status.marks_new(c),
@@ -1,6 +1,11 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use crossterm::event::Event;
 
 use crate::event::ActionMap;
+use crate::io::GitFileStatus;
+use crate::modes::FileInfo;
 
 /// Internal and terminal events.
 /// Most of events are sent from the terminal emulator.
@@ -17,6 +22,36 @@ pub enum FmEvents {
     Term(Event),
     /// Action sent directly to be dispatched and executed
     Action(ActionMap),
+    /// A line of output from a streamed shell command, tagged with its pid
+    StreamedLine(u32, String),
+    /// The streamed shell command with this pid exited, holding its exit code if any
+    StreamFinished(u32, Option<i32>),
+    /// The shell of the terminal pane exited
+    TerminalPaneExited,
+    /// Total size in bytes of those flagged files, computed in a thread
+    FlaggedTotalSize(Vec<PathBuf>, u64),
+    /// Disk usage of a directory: number of children already measured, total number of children
+    /// and the child currently measured.
+    DiskUsageProgress(PathBuf, usize, usize, String),
+    /// Disk usage of a directory: size in bytes of each of its children, computed in a thread
+    DiskUsageComputed(PathBuf, Vec<(PathBuf, u64)>),
+    /// Files of a huge directory read in a background thread, tagged with the id of the loading
+    DirectoryChunk(u64, Vec<FileInfo>),
+    /// Every file of the directory loaded in the background with this id was read
+    DirectoryLoaded(u64),
+    /// Files whose content matches the pattern, found by grep in a thread
+    FilesWithMatches(String, Vec<PathBuf>),
+    /// Git status of the modified files of a directory, computed in a thread.
+    /// None outside of a git repository.
+    GitStatuses(PathBuf, Option<HashMap<PathBuf, GitFileStatus>>),
+    /// The flagged files were deleted or trashed in a thread, or it was canceled
+    RemovalFinished,
+    /// The flagged images were converted in a thread, holding the paths of the converted images
+    ConversionFinished(Vec<PathBuf>),
+    /// A file is split or joined in a thread: bytes already written and total bytes
+    SplitJoinProgress(u64, u64),
+    /// The file was split or joined in a thread, or it failed
+    SplitJoinFinished,
     /// Empty events. Used to:
     /// - to check if a new preview should be attached
     /// - to send a "tick" to the fuzzy matcher if it's set
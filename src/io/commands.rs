@@ -1,24 +1,150 @@
 use std::env;
+use std::ffi::OsStr;
 use std::fmt;
-use std::io::{stdout, Write};
-use std::path::Path;
-use std::process::{Command, Stdio};
+use std::io::{stdout, BufRead, BufReader, Read, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, mpsc::Sender, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
 };
+use filetime::{set_file_mtime, FileTime};
 use nucleo::Injector;
+use parking_lot::Mutex;
 use tokio::{
     io::AsyncBufReadExt, io::BufReader as TokioBufReader, process::Command as TokioCommand,
 };
 
-use crate::common::{current_username, is_in_path, GREP_EXECUTABLE, RG_EXECUTABLE, SETSID};
+use crate::common::{
+    current_username, is_in_path, GREP_EXECUTABLE, GREP_FILES_WITH_MATCHES, RG_EXECUTABLE,
+    RG_FILES_WITH_MATCHES, SETSID, TMUX, WAYLAND_WALLPAPER_COMMANDS, X11_WALLPAPER_COMMANDS,
+};
+use crate::config::{
+    on_cd_command, on_select_command, pager_command, shell_in_tmux, wallpaper_command, ShellInTmux,
+};
+use crate::event::FmEvents;
 use crate::modes::PasswordHolder;
 use crate::{log_info, log_line};
 
+/// Command run when the current directory changes.
+static ON_CD_HOOK: Hook = Hook::new("on_cd_command", on_cd_command);
+
+/// Command run when the selected file changes.
+static ON_SELECT_HOOK: Hook = Hook::new("on_select_command", on_select_command);
+
+/// Run the `on_cd_command` of the config file with the new current directory as last argument.
+/// It doesn't wait for the command nor capture its output.
+/// A new command isn't started while the previous one is still running,
+/// so a slow command or a command changing directories itself can't pile up.
+/// The last directory is run once the previous command exits.
+pub fn run_on_cd_command(path: &Path) {
+    ON_CD_HOOK.run(path);
+}
+
+/// Run the `on_select_command` of the config file with the selected path as last argument.
+/// Like [`run_on_cd_command`], it's not waited for and a new command isn't started
+/// while the previous one is still running, the last selection is queued instead.
+/// Returns false if the command couldn't be spawned.
+pub fn run_on_select_command(path: &Path) -> bool {
+    ON_SELECT_HOOK.run(path)
+}
+
+/// A command of the config file run with a path as last argument, discarding its output.
+/// Only one instance runs at once. A path received meanwhile is queued, replacing
+/// the previously queued one, and is run once the command exits.
+struct Hook {
+    name: &'static str,
+    command: fn() -> &'static [String],
+    /// True while the command is running.
+    running: AtomicBool,
+    /// Last path received while the command was running.
+    queued: Mutex<Option<PathBuf>>,
+}
+
+impl Hook {
+    const fn new(name: &'static str, command: fn() -> &'static [String]) -> Self {
+        Self {
+            name,
+            command,
+            running: AtomicBool::new(false),
+            queued: Mutex::new(None),
+        }
+    }
+
+    /// Spawn the command with `path`, or queue `path` if the command is already running.
+    /// Returns false if the command couldn't be spawned.
+    fn run(&'static self, path: &Path) -> bool {
+        if (self.command)().is_empty() {
+            return false;
+        }
+        let mut queued = self.queued.lock();
+        if self.running.swap(true, Ordering::AcqRel) {
+            log_info!(
+                "{name}: previous command still running, {path} queued",
+                name = self.name,
+                path = path.display()
+            );
+            *queued = Some(path.to_owned());
+            return true;
+        }
+        drop(queued);
+        self.spawn(path)
+    }
+
+    fn spawn(&'static self, path: &Path) -> bool {
+        let Some((exe, args)) = (self.command)().split_first() else {
+            self.running.store(false, Ordering::Release);
+            return false;
+        };
+        let mut args: Vec<&OsStr> = args.iter().map(OsStr::new).collect();
+        args.push(path.as_os_str());
+        log_info!(
+            "{name}. executable: {exe:?}, arguments: {args:?}",
+            name = self.name
+        );
+        match Command::new(exe)
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(mut child) => {
+                thread::spawn(move || {
+                    let _ = child.wait();
+                    self.finish();
+                });
+                true
+            }
+            Err(error) => {
+                log_info!("{name}: couldn't run {exe}: {error}", name = self.name);
+                self.running.store(false, Ordering::Release);
+                false
+            }
+        }
+    }
+
+    /// The command exited, run the queued path if any.
+    fn finish(&'static self) {
+        let mut queued = self.queued.lock();
+        match queued.take() {
+            Some(path) => {
+                drop(queued);
+                self.spawn(&path);
+            }
+            None => self.running.store(false, Ordering::Release),
+        }
+    }
+}
+
 /// Execute a command with options in a fork with setsid.
 /// If the `SETSID` application isn't there, call the program directly.
 /// but the program may be closed if the parent (fm) is stopped.
@@ -125,6 +251,53 @@ pub fn execute_and_capture_output<S: AsRef<std::ffi::OsStr> + fmt::Debug>(
     }
 }
 
+/// Execute a command like [`execute_and_capture_output`], killing it if it runs longer than `timeout`.
+///
+/// # Errors
+///
+/// Fails if the command can't be spawned, if it times out or if its status code isn't 0.
+pub fn execute_and_capture_output_with_timeout<S: AsRef<std::ffi::OsStr> + fmt::Debug>(
+    exe: S,
+    args: &[&str],
+    timeout: Duration,
+) -> Result<String> {
+    log_info!("execute_and_capture_output_with_timeout. executable: {exe:?}, arguments: {args:?}",);
+    let deadline = Instant::now() + timeout;
+    let mut child = Command::new(exe)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let mut stdout = child
+        .stdout
+        .take()
+        .context("execute_and_capture_output_with_timeout: stdout should be piped")?;
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = vec![];
+        let _ = stdout.read_to_end(&mut output);
+        let _ = tx.send(output);
+    });
+    let output = rx.recv_timeout(timeout);
+    let status = loop {
+        match child.try_wait()? {
+            Some(status) => break Some(status),
+            None if Instant::now() >= deadline => break None,
+            None => thread::sleep(Duration::from_millis(10)),
+        }
+    };
+    let (Ok(output), Some(status)) = (output, status) else {
+        let _ = child.kill();
+        let _ = child.wait();
+        bail!("execute_and_capture_output_with_timeout: command timed out after {timeout:?}");
+    };
+    if !status.success() {
+        bail!("execute_and_capture_output_with_timeout: command didn't finish properly");
+    }
+    Ok(String::from_utf8(output)?)
+}
+
 /// Execute a command with options in a fork.
 /// Wait for termination and return either :
 /// `Ok(stdout)` if the status code is 0
@@ -174,6 +347,66 @@ where
     Ok(String::from_utf8(output.stdout)?)
 }
 
+/// Execute a command and stream its stdout line by line.
+/// A thread reads the output and sends every line with [`FmEvents::StreamedLine`].
+/// Once the output is closed, it waits for the process and sends [`FmEvents::StreamFinished`]
+/// with the exit code, if any.
+/// Events are tagged with the pid of the process, so lines of an old command are never mixed with a new one.
+/// The command runs in `current_dir`.
+/// Branch stdin and stderr to /dev/null
+/// Returns an handle to the child process, used to kill it.
+///
+/// # Errors
+///
+/// May fail if the command can't be spawned.
+pub fn execute_and_stream_output<S>(
+    exe: S,
+    args: &[&str],
+    current_dir: &Path,
+    fm_sender: Arc<Sender<FmEvents>>,
+) -> Result<Arc<Mutex<Child>>>
+where
+    S: AsRef<std::ffi::OsStr> + fmt::Debug,
+{
+    log_info!("execute_and_stream_output. executable: {exe:?}, arguments: {args:?}",);
+    let mut child = Command::new(exe)
+        .args(args)
+        .current_dir(current_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let stdout = child
+        .stdout
+        .take()
+        .context("execute_and_stream_output: stdout should be piped")?;
+    let pid = child.id();
+    let child = Arc::new(Mutex::new(child));
+    let waited_child = Arc::clone(&child);
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if fm_sender.send(FmEvents::StreamedLine(pid, line)).is_err() {
+                break;
+            }
+        }
+        let code = loop {
+            match waited_child.lock().try_wait() {
+                Ok(Some(status)) => break status.code(),
+                Ok(None) => (),
+                Err(_) => break None,
+            }
+            thread::sleep(Duration::from_millis(50));
+        };
+        fm_sender
+            .send(FmEvents::StreamFinished(pid, code))
+            .unwrap_or_default();
+    });
+    Ok(child)
+}
+
 pub fn execute_and_output<S, I>(exe: S, args: I) -> Result<std::process::Output>
 where
     S: AsRef<std::ffi::OsStr> + fmt::Debug,
@@ -187,6 +420,97 @@ where
         .output()?)
 }
 
+/// Command running a script: the interpreter of its shebang, the script itself if it's executable,
+/// or an interpreter guessed from its extension.
+/// The path of the script is the last argument.
+/// Returns `None` if the script can't be run.
+pub fn script_command(path: &Path) -> Option<Vec<String>> {
+    let path_str = path.to_str()?.to_owned();
+    if let Some(mut interpreter) = read_shebang(path) {
+        interpreter.push(path_str);
+        return Some(interpreter);
+    }
+    if path.metadata().ok()?.permissions().mode() & 0o111 != 0 {
+        return Some(vec![path_str]);
+    }
+    let interpreter = match path.extension()?.to_str()? {
+        "sh" => "sh",
+        "bash" => "bash",
+        "zsh" => "zsh",
+        "fish" => "fish",
+        "py" => "python3",
+        "pl" => "perl",
+        "rb" => "ruby",
+        "lua" => "lua",
+        "js" => "node",
+        _ => return None,
+    };
+    if !is_in_path(interpreter) {
+        return None;
+    }
+    Some(vec![interpreter.to_owned(), path_str])
+}
+
+/// Interpreter and its arguments read from the shebang of a file, like `#!/usr/bin/env python3`.
+fn read_shebang(path: &Path) -> Option<Vec<String>> {
+    let mut first_line = String::new();
+    BufReader::new(std::fs::File::open(path).ok()?)
+        .take(1024)
+        .read_line(&mut first_line)
+        .ok()?;
+    let interpreter: Vec<String> = first_line
+        .strip_prefix("#!")?
+        .split_whitespace()
+        .map(|word| word.to_owned())
+        .collect();
+    if interpreter.is_empty() {
+        None
+    } else {
+        Some(interpreter)
+    }
+}
+
+/// Command setting the image as wallpaper: the configured `wallpaper_command`
+/// or the first installed setter for the current display server.
+/// `{path}` is replaced by the path of the image, which is appended if there's no placeholder.
+/// Returns `None` if no setter is configured nor installed.
+pub fn wallpaper_setter(image: &Path) -> Option<Vec<String>> {
+    let configured = wallpaper_command();
+    let template: Vec<String> = if configured.is_empty() {
+        let candidates = if env::var_os("WAYLAND_DISPLAY").is_some() {
+            WAYLAND_WALLPAPER_COMMANDS
+        } else {
+            X11_WALLPAPER_COMMANDS
+        };
+        candidates
+            .iter()
+            .find(|command| is_in_path(command.split_whitespace().next().unwrap_or_default()))?
+            .split_whitespace()
+            .map(str::to_owned)
+            .collect()
+    } else {
+        configured.to_vec()
+    };
+    let path = image.to_string_lossy();
+    let mut command: Vec<String> = template
+        .iter()
+        .map(|arg| arg.replace("{path}", &path))
+        .collect();
+    if !template.iter().any(|arg| arg.contains("{path}")) {
+        command.push(path.into_owned());
+    }
+    Some(command)
+}
+
+/// Set the modification time of the file to now, creating an empty file if it doesn't exist, like `touch`.
+pub fn touch(path: &Path) -> Result<()> {
+    if !path.exists() {
+        std::fs::File::create(path)?;
+    }
+    set_file_mtime(path, FileTime::now())?;
+    Ok(())
+}
+
 pub fn execute_and_output_no_log<S, I>(exe: S, args: I) -> Result<std::process::Output>
 where
     S: AsRef<std::ffi::OsStr> + fmt::Debug,
@@ -381,6 +705,10 @@ pub fn build_tokio_greper() -> Option<TokioCommand> {
     } else {
         return None;
     };
+    tokio_command(shell_command)
+}
+
+fn tokio_command(shell_command: &str) -> Option<TokioCommand> {
     let mut args: Vec<_> = shell_command.split_whitespace().collect();
     if args.is_empty() {
         return None;
@@ -391,6 +719,98 @@ pub fn build_tokio_greper() -> Option<TokioCommand> {
     Some(tokio_greper)
 }
 
+/// Command listing the files of `directory` whose content matches `pattern`, with ripgrep or grep.
+/// Only the files directly in `directory` are searched unless `recursive` is set.
+/// It runs in `directory` and prints paths relative to it.
+/// Returns None if neither ripgrep nor grep is installed.
+pub fn build_tokio_files_greper(
+    pattern: &str,
+    directory: &Path,
+    recursive: bool,
+) -> Option<TokioCommand> {
+    let mut command = if is_in_path(RG_EXECUTABLE) {
+        let mut command = tokio_command(RG_FILES_WITH_MATCHES)?;
+        if !recursive {
+            command.args(["--max-depth", "1"]);
+        }
+        command
+    } else if is_in_path(GREP_EXECUTABLE) {
+        let mut command = tokio_command(GREP_FILES_WITH_MATCHES)?;
+        command.arg("-r");
+        if !recursive {
+            // Skip every subdirectory, hidden or not, but not `.` itself.
+            command.args(["--exclude-dir=[!.]*", "--exclude-dir=.?*"]);
+        }
+        command
+    } else {
+        return None;
+    };
+    command
+        .arg("-e")
+        .arg(pattern)
+        .arg(".")
+        .current_dir(directory);
+    Some(command)
+}
+
+/// Run the files greper in a thread and send the files it found with [`FmEvents::FilesWithMatches`].
+pub fn files_with_matches(
+    command: TokioCommand,
+    pattern: String,
+    directory: PathBuf,
+    fm_sender: Arc<Sender<FmEvents>>,
+) {
+    thread::spawn(move || {
+        let paths = output_lines(command)
+            .iter()
+            .map(|line| directory.join(line.strip_prefix("./").unwrap_or(line)))
+            .collect();
+        fm_sender
+            .send(FmEvents::FilesWithMatches(pattern, paths))
+            .unwrap_or_default();
+    });
+}
+
+#[tokio::main]
+async fn output_lines(mut command: TokioCommand) -> Vec<String> {
+    let Ok(output) = command
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .await
+    else {
+        log_info!("Cannot spawn command");
+        return vec![];
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Open a shell in `path` without leaving fm, according to `shell_in_tmux`.
+/// Returns false if the shell should replace fm: fm isn't running inside tmux or
+/// the setting says so.
+pub fn open_shell_in_tmux(path: &Path, terminal: &str) -> Result<bool> {
+    if env::var_os("TMUX").is_none() {
+        return Ok(false);
+    }
+    let path_str = path.to_string_lossy();
+    match shell_in_tmux() {
+        ShellInTmux::Replace => return Ok(false),
+        ShellInTmux::Split => {
+            execute_without_output(TMUX, &["split-window", "-c", &path_str])?;
+        }
+        ShellInTmux::Window => {
+            execute_without_output(TMUX, &["new-window", "-c", &path_str])?;
+        }
+        ShellInTmux::NewTerminal => {
+            execute_without_output_with_path(terminal, path, None)?;
+        }
+    }
+    Ok(true)
+}
+
 /// Open a new shell in current window.
 /// Disable raw mode, clear the screen, start a new shell ($SHELL, default to bash).
 /// Wait...
@@ -417,6 +837,40 @@ pub fn open_shell_in_window() -> Result<()> {
     Ok(())
 }
 
+/// Read a file in a pager, in current window: the configured `pager_command`, `$PAGER` or `less`.
+/// Disable raw mode, clear the screen and wait for the pager to exit.
+/// Then clear the screen and renable raw mode.
+///
+/// It's the responsability of the caller to ensure displayer doesn't try to override the display.
+pub fn open_in_pager(path: &Path) -> Result<()> {
+    let pager: Vec<String> = if pager_command().is_empty() {
+        env::var("PAGER")
+            .unwrap_or_else(|_| "less".to_owned())
+            .split_whitespace()
+            .map(str::to_owned)
+            .collect()
+    } else {
+        pager_command().to_vec()
+    };
+    let (exe, args) = pager.split_first().context("pager shouldn't be empty")?;
+
+    disable_raw_mode()?;
+    execute!(stdout(), DisableMouseCapture, Clear(ClearType::All))?;
+
+    let pager_status = Command::new(exe).args(args).arg(path).status();
+    match &pager_status {
+        Ok(pager_status) if !pager_status.success() => {
+            log_info!("Pager {exe} exited with non-zero status: {pager_status:?}")
+        }
+        Err(error) => log_line!("Couldn't run the pager {exe}: {error}"),
+        _ => (),
+    }
+
+    enable_raw_mode()?;
+    execute!(std::io::stdout(), EnableMouseCapture, Clear(ClearType::All))?;
+    Ok(())
+}
+
 pub fn open_command_in_window(args: &[&str]) -> Result<()> {
     disable_raw_mode()?;
     execute!(stdout(), DisableMouseCapture, Clear(ClearType::All))?;
@@ -435,3 +889,364 @@ pub fn open_command_in_window(args: &[&str]) -> Result<()> {
     execute!(std::io::stdout(), EnableMouseCapture, Clear(ClearType::All))?;
     Ok(())
 }
+
+/// Size of the buffer used to split or join files.
+const SPLIT_JOIN_BUFFER_SIZE: usize = 1 << 20;
+
+/// Parse a size typed by the user like `100M`, `1.5G`, `512k` or `4096` into bytes.
+/// Units are powers of 1024 and case insensitive, a trailing `B` or `iB` is ignored.
+pub fn parse_size(size: &str) -> Result<u64> {
+    let lowercase = size.trim().to_lowercase();
+    let trimmed = lowercase
+        .strip_suffix("ib")
+        .or_else(|| lowercase.strip_suffix('b'))
+        .unwrap_or(&lowercase);
+    let (number, multiplier) = match trimmed.chars().last() {
+        Some('k') => (&trimmed[..trimmed.len() - 1], 1_u64 << 10),
+        Some('m') => (&trimmed[..trimmed.len() - 1], 1 << 20),
+        Some('g') => (&trimmed[..trimmed.len() - 1], 1 << 30),
+        Some('t') => (&trimmed[..trimmed.len() - 1], 1 << 40),
+        _ => (trimmed, 1),
+    };
+    let number: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("{size} isn't a valid size, like 100M or 4G"))?;
+    let bytes = (number * multiplier as f64) as u64;
+    if !number.is_finite() || bytes == 0 {
+        bail!("{size} isn't a valid size, like 100M or 4G");
+    }
+    Ok(bytes)
+}
+
+/// Split `source` into parts of `chunk_size` bytes in a thread: `file.part001`, `file.part002`...
+/// The numbers are padded so the parts are listed in order. Existing files are never overwritten.
+/// The progress is sent with [`FmEvents::SplitJoinProgress`] and [`FmEvents::SplitJoinFinished`]
+/// is sent when it's done.
+///
+/// # Errors
+///
+/// Fails if `source` is smaller than a part or if a part already exists.
+pub fn split_file(
+    source: PathBuf,
+    chunk_size: u64,
+    fm_sender: Arc<Sender<FmEvents>>,
+) -> Result<()> {
+    let total = source.metadata()?.len();
+    if total <= chunk_size {
+        bail!("{source} is smaller than a part", source = source.display());
+    }
+    let nb_parts = total.div_ceil(chunk_size);
+    let width = nb_parts.to_string().len().max(3);
+    let parts: Vec<PathBuf> = (1..=nb_parts)
+        .map(|index| part_path(&source, index, width))
+        .collect();
+    if let Some(existing) = parts.iter().find(|part| part.exists()) {
+        bail!("{existing} already exists", existing = existing.display());
+    }
+    thread::spawn(move || {
+        match split_into_parts(&source, &parts, chunk_size, total, &fm_sender) {
+            Ok(()) => log_line!(
+                "Split {source} into {nb_parts} parts",
+                source = source.display()
+            ),
+            Err(error) => {
+                log_line!("split {source}: {error}", source = source.display());
+                for part in parts.iter() {
+                    let _ = std::fs::remove_file(part);
+                }
+            }
+        }
+        fm_sender.send(FmEvents::Refresh).unwrap_or_default();
+        fm_sender
+            .send(FmEvents::SplitJoinFinished)
+            .unwrap_or_default();
+    });
+    Ok(())
+}
+
+/// Path of the part `index` of `source`, its number padded to `width` digits.
+fn part_path(source: &Path, index: u64, width: usize) -> PathBuf {
+    let mut path = source.as_os_str().to_owned();
+    path.push(format!(".part{index:0width$}"));
+    PathBuf::from(path)
+}
+
+fn split_into_parts(
+    source: &Path,
+    parts: &[PathBuf],
+    chunk_size: u64,
+    total: u64,
+    fm_sender: &Sender<FmEvents>,
+) -> Result<()> {
+    let mut reader = BufReader::with_capacity(SPLIT_JOIN_BUFFER_SIZE, std::fs::File::open(source)?);
+    let mut progress = SplitJoinProgress::new(total, fm_sender);
+    for part in parts {
+        let mut writer = std::fs::File::create_new(part)?;
+        copy_with_progress(
+            &mut (&mut reader).take(chunk_size),
+            &mut writer,
+            &mut progress,
+        )?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+/// Join the parts of a split file in a thread, `part` being any of them.
+/// The parts are its siblings with the same name and another number: `file.part001`, `file.part002`...
+/// They're joined in numeric order into `file`.
+/// The progress is sent with [`FmEvents::SplitJoinProgress`] and [`FmEvents::SplitJoinFinished`]
+/// is sent when it's done.
+///
+/// # Errors
+///
+/// Fails if `part` isn't named like a part, if a part is missing or if the joined file already exists.
+pub fn join_parts(part: &Path, fm_sender: Arc<Sender<FmEvents>>) -> Result<()> {
+    let (target, parts) = split_parts(part)?;
+    if target.exists() {
+        bail!("{target} already exists", target = target.display());
+    }
+    let mut total = 0;
+    for part in parts.iter() {
+        total += part.metadata()?.len();
+    }
+    thread::spawn(move || {
+        match join_into(&parts, &target, total, &fm_sender) {
+            Ok(()) => log_line!(
+                "Joined {nb} parts into {target}",
+                nb = parts.len(),
+                target = target.display()
+            ),
+            Err(error) => {
+                log_line!("join {target}: {error}", target = target.display());
+                let _ = std::fs::remove_file(&target);
+            }
+        }
+        fm_sender.send(FmEvents::Refresh).unwrap_or_default();
+        fm_sender
+            .send(FmEvents::SplitJoinFinished)
+            .unwrap_or_default();
+    });
+    Ok(())
+}
+
+/// Joined file and every part of the split file `part` belongs to, sorted by number.
+/// The numbers must follow each other from 1.
+fn split_parts(part: &Path) -> Result<(PathBuf, Vec<PathBuf>)> {
+    let filename = part
+        .file_name()
+        .and_then(OsStr::to_str)
+        .context("join: invalid filename")?;
+    let Some((stem, number)) = parse_part_name(filename) else {
+        bail!("{filename} isn't a part, like file.part001");
+    };
+    let parent = part.parent().context("join: no parent directory")?;
+    let mut numbered = vec![];
+    for entry in std::fs::read_dir(parent)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(OsStr::to_str) else {
+            continue;
+        };
+        let Some((other_stem, other_number)) = parse_part_name(name) else {
+            continue;
+        };
+        if other_stem != stem || other_number.len() != number.len() {
+            continue;
+        }
+        numbered.push((other_number.parse::<u64>()?, path));
+    }
+    numbered.sort_unstable_by_key(|(index, _)| *index);
+    for (expected, (index, _)) in (1..).zip(numbered.iter()) {
+        if *index != expected {
+            bail!("part {expected} of {stem} is missing");
+        }
+    }
+    Ok((
+        parent.join(stem),
+        numbered.into_iter().map(|(_, path)| path).collect(),
+    ))
+}
+
+/// Name of the joined file and number of a part: `file.part001` -> `("file", "001")`.
+fn parse_part_name(filename: &str) -> Option<(&str, &str)> {
+    let (stem, number) = filename.rsplit_once(".part")?;
+    if stem.is_empty() || number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((stem, number))
+}
+
+fn join_into(
+    parts: &[PathBuf],
+    target: &Path,
+    total: u64,
+    fm_sender: &Sender<FmEvents>,
+) -> Result<()> {
+    let mut writer = std::fs::File::create_new(target)?;
+    let mut progress = SplitJoinProgress::new(total, fm_sender);
+    for part in parts {
+        let mut reader = std::fs::File::open(part)?;
+        copy_with_progress(&mut reader, &mut writer, &mut progress)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Copy everything from `reader` to `writer`, reporting the written bytes to `progress`.
+fn copy_with_progress(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    progress: &mut SplitJoinProgress,
+) -> Result<()> {
+    let mut buffer = vec![0; SPLIT_JOIN_BUFFER_SIZE];
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(read) => read,
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error.into()),
+        };
+        writer.write_all(&buffer[..read])?;
+        progress.advance(read as u64);
+    }
+}
+
+/// Bytes written by a split or a join, sent every time another percent is done.
+struct SplitJoinProgress<'a> {
+    done: u64,
+    total: u64,
+    percent: u64,
+    fm_sender: &'a Sender<FmEvents>,
+}
+
+impl<'a> SplitJoinProgress<'a> {
+    fn new(total: u64, fm_sender: &'a Sender<FmEvents>) -> Self {
+        Self {
+            done: 0,
+            total,
+            percent: 0,
+            fm_sender,
+        }
+    }
+
+    fn advance(&mut self, bytes: u64) {
+        self.done += bytes;
+        let percent = self.done * 100 / self.total.max(1);
+        if percent != self.percent {
+            self.percent = percent;
+            self.fm_sender
+                .send(FmEvents::SplitJoinProgress(self.done, self.total))
+                .unwrap_or_default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::write;
+
+    use super::*;
+
+    /// A directory holding the files `names`.
+    fn directory_with(names: &[&str]) -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        for name in names {
+            write(root.path().join(name), name).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_parse_size_units() {
+        assert_eq!(parse_size("4096").unwrap(), 4096);
+        assert_eq!(parse_size("512k").unwrap(), 512 << 10);
+        assert_eq!(parse_size("100M").unwrap(), 100 << 20);
+        assert_eq!(parse_size("1.5G").unwrap(), 3 << 29);
+        assert_eq!(parse_size("2T").unwrap(), 2 << 40);
+    }
+
+    #[test]
+    fn test_parse_size_suffixes() {
+        assert_eq!(parse_size("100MB").unwrap(), 100 << 20);
+        assert_eq!(parse_size("100MiB").unwrap(), 100 << 20);
+        assert_eq!(parse_size("100mb").unwrap(), 100 << 20);
+        assert_eq!(parse_size("100Mib").unwrap(), 100 << 20);
+        assert_eq!(parse_size("100mib").unwrap(), 100 << 20);
+        assert_eq!(parse_size("100MIB").unwrap(), 100 << 20);
+        assert_eq!(parse_size("1KIB").unwrap(), 1 << 10);
+        assert_eq!(parse_size(" 100 M ").unwrap(), 100 << 20);
+        assert_eq!(parse_size("10B").unwrap(), 10);
+    }
+
+    #[test]
+    fn test_parse_size_rejects_invalid_sizes() {
+        assert!(parse_size("0").is_err());
+        assert!(parse_size("0M").is_err());
+        assert!(parse_size("-1M").is_err());
+        assert!(parse_size("-4096").is_err());
+        assert!(parse_size("").is_err());
+        assert!(parse_size("M").is_err());
+        assert!(parse_size("ten").is_err());
+        assert!(parse_size("infG").is_err());
+    }
+
+    #[test]
+    fn test_parse_part_name() {
+        assert_eq!(parse_part_name("file.part001"), Some(("file", "001")));
+        assert_eq!(
+            parse_part_name("archive.tar.gz.part12"),
+            Some(("archive.tar.gz", "12"))
+        );
+        assert_eq!(parse_part_name("a.part1.part002"), Some(("a.part1", "002")));
+        assert_eq!(parse_part_name("file.part"), None);
+        assert_eq!(parse_part_name(".part001"), None);
+        assert_eq!(parse_part_name("file.part01a"), None);
+        assert_eq!(parse_part_name("file.txt"), None);
+    }
+
+    #[test]
+    fn test_split_parts_are_sorted_by_number() {
+        let root = directory_with(&["file.part003", "file.part001", "file.part002", "other"]);
+        let (target, parts) = split_parts(&root.path().join("file.part002")).unwrap();
+        assert_eq!(target, root.path().join("file"));
+        assert_eq!(
+            parts,
+            vec![
+                root.path().join("file.part001"),
+                root.path().join("file.part002"),
+                root.path().join("file.part003"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_parts_missing_part() {
+        let root = directory_with(&["file.part001", "file.part003"]);
+        assert!(split_parts(&root.path().join("file.part001")).is_err());
+        let root = directory_with(&["file.part002", "file.part003"]);
+        assert!(split_parts(&root.path().join("file.part002")).is_err());
+    }
+
+    #[test]
+    fn test_split_parts_ignore_other_number_widths_and_files() {
+        let root = directory_with(&["file.part001", "file.part002", "file.part03", "doc.part003"]);
+        let (_, parts) = split_parts(&root.path().join("file.part001")).unwrap();
+        assert_eq!(
+            parts,
+            vec![
+                root.path().join("file.part001"),
+                root.path().join("file.part002"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_parts_rejects_files_which_arent_parts() {
+        let root = directory_with(&["file.txt"]);
+        assert!(split_parts(&root.path().join("file.txt")).is_err());
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     rc::Rc,
 };
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, LeaveAlternateScreen},
@@ -17,19 +17,22 @@ use ratatui::{
     prelude::*,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
     Frame, Terminal,
 };
 
 use crate::app::{ClickableLine, Footer, Header, PreviewHeader, Status, Tab};
-use crate::common::path_to_string;
-use crate::config::{with_icon, with_icon_metadata, ColorG, Gradient, MATCHER, MENU_STYLES};
-use crate::io::{read_last_log_line, DrawMenu};
+use crate::common::{collapse_home_in_text, path_to_string, UtfWidth};
+use crate::config::{
+    binary_ascii, with_icon, with_icon_metadata, ColorG, Gradient, MATCHER, MENU_STYLES,
+};
+use crate::io::{read_last_log_line, DrawMenu, GitFileStatus, TerminalPane};
 use crate::modes::{
-    highlighted_text, parse_input_permission, AnsiString, BinLine, BinaryContent, Content,
-    ContentWindow, Display as DisplayMode, FileInfo, FuzzyFinder, HLContent, Input, InputSimple,
-    LineDisplay, Menu as MenuMode, MoreInfos, Navigate, NeedConfirmation, Preview, SecondLine,
-    Selectable, TLine, TakeSkip, TakeSkipEnum, Text, TextKind, Trash, Tree, Ueber,
+    highlighted_text, human_size, parse_input_permission, AnsiString, BinLine, BinaryContent,
+    Content, ContentWindow, DirectoryListing, Display as DisplayMode, FileInfo, FuzzyFinder,
+    GroupedRow, HLContent, Input, InputCompleted, InputSimple, LineDisplay, Menu as MenuMode,
+    MoreInfos, Navigate, NeedConfirmation, Preview, SecondLine, Selectable, TLine, TakeSkip,
+    TakeSkipEnum, Text, TextKind, Trash, Tree, Ueber,
 };
 use crate::{colored_skip_take, log_info};
 
@@ -49,6 +52,22 @@ impl Offseted for Rect {
     }
 }
 
+/// Paints the background of every other row of a list of files with the configured `stripe` color.
+/// `top` is the index of the first displayed row, so the stripes follow the files when scrolling.
+/// Does nothing if no stripe color is configured.
+fn stripe_rows(f: &mut Frame, rect: &Rect, top: usize, nb_rows: usize) {
+    let Some(stripe) = MENU_STYLES.get().and_then(|styles| styles.stripe) else {
+        return;
+    };
+    let style = Style::new().bg(stripe);
+    for row in 0..nb_rows.min(rect.height as usize) {
+        if (top + row) % 2 == 1 {
+            let row_rect = Rect::new(rect.x, rect.y + row as u16, rect.width, 1);
+            f.buffer_mut().set_style(row_rect, style);
+        }
+    }
+}
+
 /// Common trait all "window" should implement.
 /// It's mostly used as an entry point for the rendering and should call another method.
 trait Draw {
@@ -257,7 +276,7 @@ impl<'a> Files<'a> {
     }
 
     fn log_line(&self, f: &mut Frame, rect: &Rect) {
-        LogLine.draw(f, rect);
+        LogLine::new(self.status.session.tilde_path()).draw(f, rect);
     }
 
     fn footer(&self, f: &mut Frame, rect: &Rect) {
@@ -271,7 +290,13 @@ struct CopyProgressBar<'a> {
 
 impl<'a> Draw for CopyProgressBar<'a> {
     fn draw(&self, f: &mut Frame, rect: &Rect) {
-        let Some(content) = self.status.internal_settings.format_copy_progress() else {
+        let settings = &self.status.internal_settings;
+        let Some(content) = settings
+            .format_copy_progress()
+            .or_else(|| settings.format_removal_progress())
+            .or_else(|| settings.format_conversion_progress())
+            .or_else(|| settings.format_split_join_progress())
+        else {
             return;
         };
         let p_rect = rect.offseted(1, 0);
@@ -452,6 +477,10 @@ impl<'a> DirectoryDisplay<'a> {
     /// When we display a simpler version, the menu line is used to display the
     /// metadata of the selected file.
     fn files(&self, f: &mut Frame, rect: &Rect) {
+        if self.status.session.group_by_extension() {
+            self.files_grouped(f, rect);
+            return;
+        }
         let group_owner_sizes = self.group_owner_size();
         let p_rect = rect.offseted(2, 0);
         let formater = self.pick_formater();
@@ -460,6 +489,39 @@ impl<'a> DirectoryDisplay<'a> {
             .dir_enum_skip_take()
             .map(|(index, file)| self.files_line(group_owner_sizes, index, file, &formater))
             .collect();
+        stripe_rows(f, &p_rect, self.tab.window.top, lines.len());
+        Paragraph::new(lines).render(p_rect, f.buffer_mut());
+    }
+
+    /// Displays the files grouped by extension.
+    /// Every group is preceded by a header line with its extension.
+    /// Headers aren't files and can't be selected.
+    fn files_grouped(&self, f: &mut Frame, rect: &Rect) {
+        let group_owner_sizes = self.group_owner_size();
+        let p_rect = rect.offseted(2, 0);
+        let formater = self.pick_formater();
+        let directory = &self.tab.directory;
+        let height = self.tab.window.height;
+        let rows = directory.grouped_rows();
+        let top_row = directory.grouped_top_row(&rows, self.tab.window.top, height);
+        let header_style = MENU_STYLES.get().expect("Menu colors should be set").first;
+        let lines: Vec<_> = rows
+            .iter()
+            .skip(top_row)
+            .take(height)
+            .map(|row| match row {
+                GroupedRow::Header(group) => {
+                    Line::from(Span::styled(format!("── {group} ──"), header_style))
+                }
+                GroupedRow::File(index) => self.files_line(
+                    group_owner_sizes,
+                    *index,
+                    &directory.content[*index],
+                    &formater,
+                ),
+            })
+            .collect();
+        stripe_rows(f, &p_rect, top_row, lines.len());
         Paragraph::new(lines).render(p_rect, f.buffer_mut());
     }
 
@@ -501,10 +563,32 @@ impl<'a> DirectoryDisplay<'a> {
         let content = formater(file, group_owner_sizes);
         Line::from(vec![
             self.span_flagged_symbol(file, &mut style),
+            self.span_git_status(file),
             Span::styled(content, style),
         ])
     }
 
+    /// Colored git status marker of the file, like `M` or `??`.
+    /// Blank if the file isn't modified, empty outside of a git repository.
+    fn span_git_status<'b>(&self, file: &FileInfo) -> Span<'b> {
+        let directory = &self.tab.directory;
+        if !directory.is_in_git_repo() {
+            return Span::raw("");
+        }
+        let Some(git_status) = directory.git_status(&file.path) else {
+            return Span::raw("   ");
+        };
+        let color = match git_status {
+            GitFileStatus::Modified => Color::Yellow,
+            GitFileStatus::Added => Color::Green,
+            GitFileStatus::Deleted => Color::Red,
+            GitFileStatus::Renamed => Color::Blue,
+            GitFileStatus::Unmerged => Color::Magenta,
+            GitFileStatus::Untracked => Color::LightRed,
+        };
+        Span::styled(format!("{} ", git_status.marker()), Style::new().fg(color))
+    }
+
     fn reverse_selected(&self, index: usize, style: &mut Style) {
         if index == self.tab.directory.index {
             style.add_modifier |= Modifier::REVERSED;
@@ -587,6 +671,7 @@ impl<'a> TreeDisplay<'a> {
                 Self::tree_line(status, index == 0, line_builder, with_metadata, with_icon)
             })
             .collect();
+        stripe_rows(f, &p_rect, window.top, lines.len());
         Paragraph::new(lines).render(p_rect, f.buffer_mut());
     }
 
@@ -680,10 +765,12 @@ impl<'a> PreviewDisplay<'a> {
     }
 
     fn preview(&self, f: &mut Frame, rect: &Rect) {
-        let tab = self.tab;
-        let window = &tab.window;
-        let length = tab.preview.len();
-        match &tab.preview {
+        self.draw_preview(f, rect, &self.tab.preview, &self.tab.window)
+    }
+
+    fn draw_preview(&self, f: &mut Frame, rect: &Rect, preview: &Preview, window: &ContentWindow) {
+        let length = preview.len();
+        match preview {
             Preview::Syntaxed(syntaxed) => {
                 let number_col_width = Self::number_width(length);
                 self.syntaxed(f, syntaxed, length, rect, number_col_width, window)
@@ -691,7 +778,13 @@ impl<'a> PreviewDisplay<'a> {
             Preview::Binary(bin) => self.binary(f, bin, length, rect, window),
             Preview::Ueberzug(image) => self.ueberzug(image, rect),
             Preview::Tree(tree_preview) => self.tree_preview(f, tree_preview, window, rect),
-            Preview::Text(ansi_text) if matches!(ansi_text.kind, TextKind::CommandStdout) => {
+            Preview::Directory(listing) => self.directory_listing(f, listing, length, rect, window),
+            Preview::Text(ansi_text)
+                if matches!(
+                    ansi_text.kind,
+                    TextKind::CommandStdout | TextKind::CommandStream
+                ) =>
+            {
                 self.ansi_text(f, ansi_text, length, rect, window)
             }
             Preview::Text(text) => self.normal_text(f, text, length, rect, window),
@@ -700,6 +793,17 @@ impl<'a> PreviewDisplay<'a> {
         };
     }
 
+    /// Number printed in the gutter of a text preview line.
+    /// With relative line numbers, the top line keeps its number and the others
+    /// display their distance to it.
+    fn gutter_number(&self, index: usize, window: &ContentWindow) -> usize {
+        if self.status.session.relative_line_numbers() && index != window.top {
+            index.saturating_sub(window.top)
+        } else {
+            index
+        }
+    }
+
     fn line_number_span<'b>(
         line_number_to_print: &usize,
         number_col_width: usize,
@@ -721,7 +825,8 @@ impl<'a> PreviewDisplay<'a> {
         width
     }
 
-    /// Draw every line of the text
+    /// Draw every line of the text.
+    /// A gutter of line numbers is displayed if they're relative.
     fn normal_text(
         &self,
         f: &mut Frame,
@@ -730,10 +835,30 @@ impl<'a> PreviewDisplay<'a> {
         rect: &Rect,
         window: &ContentWindow,
     ) {
-        let p_rect = rect.offseted(2, 0);
+        if !self.status.session.relative_line_numbers() {
+            let p_rect = rect.offseted(2, 0);
+            let lines: Vec<_> = text
+                .take_skip(window.top, window.bottom, length)
+                .map(Line::raw)
+                .collect();
+            Paragraph::new(lines).render(p_rect, f.buffer_mut());
+            return;
+        }
+        let p_rect = rect.offseted(3, 0);
+        let number_col_width = Self::number_width(length);
+        let number_col_style = MENU_STYLES.get().expect("Menu colors should be set").first;
         let lines: Vec<_> = text
-            .take_skip(window.top, window.bottom, length)
-            .map(Line::raw)
+            .take_skip_enum(window.top, window.bottom, length)
+            .map(|(index, line)| {
+                Line::from(vec![
+                    Self::line_number_span(
+                        &self.gutter_number(index, window),
+                        number_col_width,
+                        number_col_style,
+                    ),
+                    Span::raw(line),
+                ])
+            })
             .collect();
         Paragraph::new(lines).render(p_rect, f.buffer_mut());
     }
@@ -748,12 +873,12 @@ impl<'a> PreviewDisplay<'a> {
         window: &ContentWindow,
     ) {
         let p_rect = rect.offseted(3, 0);
-        let number_col_style = MENU_STYLES.get().expect("").first;
+        let number_col_style = MENU_STYLES.get().expect("Menu colors should be set").first;
         let lines: Vec<_> = syntaxed
             .take_skip_enum(window.top, window.bottom, length)
             .map(|(index, vec_line)| {
                 let mut line = vec![Self::line_number_span(
-                    &index,
+                    &self.gutter_number(index, window),
                     number_col_width,
                     number_col_style,
                 )];
@@ -779,6 +904,9 @@ impl<'a> PreviewDisplay<'a> {
     ) {
         let p_rect = rect.offseted(3, 0);
         let line_number_width_hex = bin.number_width_hex();
+        // 2 chars per byte and a space every 2 bytes, so the ASCII column of the last line is aligned.
+        let hex_width = bin.bytes_per_row() * 5 / 2;
+        let with_ascii = binary_ascii();
         let (style_number, style_ascii) = {
             let ms = MENU_STYLES.get().expect("Menu colors should be set");
             (ms.first, ms.second)
@@ -786,15 +914,18 @@ impl<'a> PreviewDisplay<'a> {
         let lines: Vec<_> = (*bin)
             .take_skip_enum(window.top, window.bottom, length)
             .map(|(index, bin_line)| {
-                Line::from(vec![
+                let mut spans = vec![
                     Span::styled(
                         BinLine::format_line_nr_hex(index + 1 + window.top, line_number_width_hex),
                         style_number,
                     ),
-                    Span::raw(bin_line.format_hex()),
-                    Span::raw(" "),
-                    Span::styled(bin_line.format_as_ascii(), style_ascii),
-                ])
+                    Span::raw(format!("{hex:hex_width$}", hex = bin_line.format_hex())),
+                ];
+                if with_ascii {
+                    spans.push(Span::raw(" "));
+                    spans.push(Span::styled(bin_line.format_as_ascii(), style_ascii));
+                }
+                Line::from(spans)
             })
             .collect();
         Paragraph::new(lines).render(p_rect, f.buffer_mut());
@@ -815,6 +946,31 @@ impl<'a> PreviewDisplay<'a> {
         TreeDisplay::tree_content(self.status, tree, window, false, f, rect)
     }
 
+    fn directory_listing(
+        &self,
+        f: &mut Frame,
+        listing: &DirectoryListing,
+        length: usize,
+        rect: &Rect,
+        window: &ContentWindow,
+    ) {
+        let p_rect = rect.offseted(3, 0);
+        let lines: Vec<_> = listing
+            .take_skip(window.top, window.bottom, length)
+            .map(|file| {
+                Line::from(Span::styled(
+                    format!(
+                        "{size}  {name}",
+                        size = file.size_column,
+                        name = file.filename
+                    ),
+                    file.style(),
+                ))
+            })
+            .collect();
+        Paragraph::new(lines).render(p_rect, f.buffer_mut());
+    }
+
     fn ansi_text(
         &self,
         f: &mut Frame,
@@ -915,12 +1071,20 @@ impl FilesSecondLine {
     }
 }
 
-struct LogLine;
+struct LogLine {
+    /// Display the home directory as `~` in the paths of the message.
+    tilde_path: bool,
+}
 
 impl Draw for LogLine {
     fn draw(&self, f: &mut Frame, rect: &Rect) {
         let p_rect = rect.offseted(4, 0);
-        let log = &read_last_log_line();
+        let log = read_last_log_line();
+        let log = if self.tilde_path {
+            collapse_home_in_text(&log).into_owned()
+        } else {
+            log
+        };
         Span::styled(
             log,
             MENU_STYLES.get().expect("Menu colors should be set").second,
@@ -929,6 +1093,12 @@ impl Draw for LogLine {
     }
 }
 
+impl LogLine {
+    fn new(tilde_path: bool) -> Self {
+        Self { tilde_path }
+    }
+}
+
 struct FilesFooter<'a> {
     status: &'a Status,
     tab: &'a Tab,
@@ -1033,6 +1203,9 @@ impl<'a> Menu<'a> {
                 let first = MENU_STYLES.get().expect("Menu colors should be set").first;
                 self.menu_line_chmod(f, rect, first, menu);
             }
+            MenuMode::InputCompleted(InputCompleted::Search) => {
+                self.menu_line_search(f, rect, menu)
+            }
             // edit => rect.print_with_style(f, 1, 2, edit.second_line(), menu),
             edit => {
                 let rect = rect.offseted(2, 1);
@@ -1041,6 +1214,23 @@ impl<'a> Menu<'a> {
         };
     }
 
+    /// The kind of search and, if the typed regex is invalid, its error.
+    fn menu_line_search(&self, f: &mut Frame, rect: &Rect, menu: Style) {
+        let mut spans = vec![Span::styled(
+            format!(
+                "{second_line} ({kind})",
+                second_line = self.tab.menu_mode.second_line(),
+                kind = self.status.menu.search_kind
+            ),
+            menu,
+        )];
+        if let Some(error) = &self.status.menu.search_error {
+            let first = MENU_STYLES.get().expect("Menu colors should be set").first;
+            spans.push(Span::styled(format!("  invalid regex: {error}"), first));
+        }
+        Line::from(spans).render(rect.offseted(2, 1), f.buffer_mut());
+    }
+
     fn menu_line_chmod(&self, f: &mut Frame, rect: &Rect, first: Style, menu: Style) {
         let input = self.status.menu.input.string();
         let spans: Vec<_> = parse_input_permission(&input)
@@ -1202,6 +1392,7 @@ impl<'a> Menu<'a> {
             &self.tab.current_file().unwrap(),
             &self.status.internal_settings.opener,
         )
+        .with_home_collapsed(self.status.session.tilde_path())
         .to_lines();
         Self::render_content(&more_info, f, rect, 4, 3 + space_used);
     }
@@ -1209,6 +1400,21 @@ impl<'a> Menu<'a> {
     fn flagged(&self, f: &mut Frame, rect: &Rect) {
         self.flagged_files(f, rect);
         self.flagged_selected(f, rect);
+        self.flagged_total_size(f, rect);
+    }
+
+    /// Total size of the flagged files, after the second line, if it was computed.
+    fn flagged_total_size(&self, f: &mut Frame, rect: &Rect) {
+        let Some(size) = self.status.menu.flagged.total_size() else {
+            return;
+        };
+        let offset = Navigate::Flagged.second_line().utf_width_u16() + 4;
+        let p_rect = rect.offseted(offset, 1);
+        Span::styled(
+            format!("Total size: {size}", size = human_size(size).trim()),
+            MENU_STYLES.get().expect("Menu colors should be set").first,
+        )
+        .render(p_rect, f.buffer_mut());
     }
 
     fn flagged_files(&self, f: &mut Frame, rect: &Rect) {
@@ -1235,10 +1441,12 @@ impl<'a> Menu<'a> {
     /// Display the possible completion items. The currently selected one is
     /// reversed.
     fn completion(&self, f: &mut Frame, rect: &Rect) {
-        self.status
-            .menu
-            .completion
-            .draw_menu(f, rect, &self.status.menu.window)
+        self.status.menu.completion.draw_highlighted(
+            f,
+            rect,
+            &self.status.menu.window,
+            &self.status.menu.input.string(),
+        )
     }
 
     /// Display a list of edited (deleted, copied, moved, trashed) files for confirmation
@@ -1256,6 +1464,11 @@ impl<'a> Menu<'a> {
             NeedConfirmation::EmptyTrash => self.confirm_empty_trash(f, rect),
             NeedConfirmation::BulkAction => self.confirm_bulk(f, rect),
             NeedConfirmation::DeleteCloud => self.confirm_delete_cloud(f, rect),
+            NeedConfirmation::RunScript => self.confirm_run_script(f, rect),
+            NeedConfirmation::CopyAs => self.confirm_copy_as(f, rect),
+            NeedConfirmation::SaveFlagged => self.confirm_save_flagged(f, rect),
+            NeedConfirmation::ShellCommand => self.confirm_shell_command(f, rect),
+            NeedConfirmation::Conflict => self.confirm_conflict(f, rect),
             _ => self.confirm_default(f, rect),
         };
     }
@@ -1306,8 +1519,84 @@ impl<'a> Menu<'a> {
             line,
             MENU_STYLES
                 .get()
-                .context("MENU_STYLES should be set")
-                .unwrap()
+                .expect("Menu colors should be set")
+                .palette_4,
+        );
+    }
+
+    fn confirm_run_script(&self, f: &mut Frame, rect: &Rect) {
+        let line = match &self.status.menu.script_command {
+            Some(command) => command.join(" "),
+            None => "This file can't be run".to_owned(),
+        };
+        Self::content_line(
+            f,
+            rect,
+            3,
+            &line,
+            MENU_STYLES
+                .get()
+                .expect("Menu colors should be set")
+                .palette_4,
+        );
+    }
+
+    fn confirm_conflict(&self, f: &mut Frame, rect: &Rect) {
+        let Some(conflicts) = &self.status.menu.conflicts else {
+            return;
+        };
+        Self::render_content(
+            &conflicts.describe(),
+            f,
+            rect,
+            3,
+            2 + ContentWindow::WINDOW_MARGIN_TOP_U16,
+        );
+    }
+
+    fn confirm_copy_as(&self, f: &mut Frame, rect: &Rect) {
+        let line = match self.status.copy_as_target() {
+            Ok(target) => target.display().to_string(),
+            Err(_) => "No selected file".to_owned(),
+        };
+        Self::content_line(
+            f,
+            rect,
+            3,
+            &line,
+            MENU_STYLES
+                .get()
+                .expect("Menu colors should be set")
+                .palette_4,
+        );
+    }
+
+    fn confirm_save_flagged(&self, f: &mut Frame, rect: &Rect) {
+        Self::content_line(
+            f,
+            rect,
+            3,
+            &self.status.typed_path().display().to_string(),
+            MENU_STYLES
+                .get()
+                .expect("Menu colors should be set")
+                .palette_4,
+        );
+    }
+
+    fn confirm_shell_command(&self, f: &mut Frame, rect: &Rect) {
+        let line = self
+            .status
+            .expanded_shell_command()
+            .unwrap_or_else(|| "This command can't be parsed".to_owned());
+        Self::content_line(
+            f,
+            rect,
+            3,
+            &line,
+            MENU_STYLES
+                .get()
+                .expect("Menu colors should be set")
                 .palette_4,
         );
     }
@@ -1370,6 +1659,134 @@ impl MenuFirstLine {
     }
 }
 
+/// Preview of the selected file displayed over the panes, like a modal window.
+/// It's dismissed by the next key press.
+struct QuickLookWindow<'a> {
+    status: &'a Status,
+    path: &'a std::path::Path,
+    preview: &'a Preview,
+}
+
+impl<'a> Draw for QuickLookWindow<'a> {
+    fn draw(&self, f: &mut Frame, rect: &Rect) {
+        let rect = Rects::centered(*rect, Self::PERCENT);
+        let menu_styles = MENU_STYLES.get().expect("Menu colors should be set");
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(menu_styles.selected_border)
+            .title(format!(" {path} ", path = self.path.display()));
+        let inner = block.inner(rect);
+        f.render_widget(Clear, rect);
+        f.render_widget(block, rect);
+        if let Preview::Ueberzug(image) = self.preview {
+            image.draw(inner.x, inner.y, inner.width, inner.height);
+            return;
+        }
+        let window = ContentWindow::new(self.preview.len(), inner.height as usize);
+        let attributes = FilesAttributes::new(inner.x, TabPosition::Left, true, false);
+        PreviewDisplay::new_with_args(self.status, self.status.current_tab(), &attributes)
+            .draw_preview(f, &inner, self.preview, &window);
+    }
+}
+
+impl<'a> QuickLookWindow<'a> {
+    /// Percentage of the terminal width and height covered by the window.
+    const PERCENT: u16 = 80;
+
+    fn new(status: &'a Status) -> Option<Self> {
+        let (path, preview) = status.quick_look()?;
+        Some(Self {
+            status,
+            path,
+            preview,
+        })
+    }
+}
+
+/// The screen of the shell running in the terminal pane, below the files.
+/// Its border is highlighted when it's focused.
+struct TerminalPaneWindow<'a> {
+    terminal_pane: &'a TerminalPane,
+}
+
+impl<'a> Draw for TerminalPaneWindow<'a> {
+    fn draw(&self, f: &mut Frame, rect: &Rect) {
+        let menu_styles = MENU_STYLES.get().expect("Menu colors should be set");
+        let border_style = if self.terminal_pane.is_focused() {
+            menu_styles.selected_border
+        } else {
+            menu_styles.inert_border
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let inner = block.inner(*rect);
+        f.render_widget(block, *rect);
+        let parser = self.terminal_pane.parser().lock();
+        let screen = parser.screen();
+        Self::draw_screen(screen, f, &inner);
+        if self.terminal_pane.is_focused() && !screen.hide_cursor() {
+            let (row, col) = screen.cursor_position();
+            f.set_cursor_position(Position::new(inner.x + col, inner.y + row));
+        }
+    }
+}
+
+impl<'a> TerminalPaneWindow<'a> {
+    fn new(terminal_pane: &'a TerminalPane) -> Self {
+        Self { terminal_pane }
+    }
+
+    fn draw_screen(screen: &vt100::Screen, f: &mut Frame, rect: &Rect) {
+        let buffer = f.buffer_mut();
+        for row in 0..rect.height {
+            for col in 0..rect.width {
+                let Some(cell) = screen.cell(row, col) else {
+                    continue;
+                };
+                if cell.is_wide_continuation() {
+                    continue;
+                }
+                let Some(buffer_cell) = buffer.cell_mut((rect.x + col, rect.y + row)) else {
+                    continue;
+                };
+                let contents = cell.contents();
+                let symbol = if contents.is_empty() { " " } else { &contents };
+                buffer_cell
+                    .set_symbol(symbol)
+                    .set_style(Self::cell_style(cell));
+            }
+        }
+    }
+
+    fn cell_style(cell: &vt100::Cell) -> Style {
+        let mut style = Style::default()
+            .fg(Self::color(cell.fgcolor()))
+            .bg(Self::color(cell.bgcolor()));
+        if cell.bold() {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if cell.italic() {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        if cell.underline() {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+        if cell.inverse() {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        style
+    }
+
+    fn color(color: vt100::Color) -> Color {
+        match color {
+            vt100::Color::Default => Color::Reset,
+            vt100::Color::Idx(index) => Color::Indexed(index),
+            vt100::Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
+        }
+    }
+}
+
 /// Methods used to create the various rects
 struct Rects;
 
@@ -1394,6 +1811,31 @@ impl Rects {
         Rect::new(0, 0, width, height)
     }
 
+    /// Height left for the files and the rect of the terminal pane, if it's opened.
+    fn terminal_pane(status: &Status, width: u16, height: u16) -> (u16, Option<Rect>) {
+        if status.terminal_pane.is_none() {
+            return (height, None);
+        }
+        let pane_height = TerminalPane::height(height);
+        let files_height = height - pane_height;
+        (
+            files_height,
+            Some(Rect::new(0, files_height, width, pane_height)),
+        )
+    }
+
+    /// Rect centered in `rect`, covering `percent` of its width and height.
+    fn centered(rect: Rect, percent: u16) -> Rect {
+        let width = (u32::from(rect.width) * u32::from(percent) / 100) as u16;
+        let height = (u32::from(rect.height) * u32::from(percent) / 100) as u16;
+        Rect::new(
+            rect.x + (rect.width - width) / 2,
+            rect.y + (rect.height - height) / 2,
+            width,
+            height,
+        )
+    }
+
     /// Main rect but inside its border
     fn inside_border_rect(width: u16, height: u16) -> Rect {
         Rect::new(1, 1, width.saturating_sub(2), height.saturating_sub(2))
@@ -1497,6 +1939,12 @@ impl Rects {
     }
 }
 
+/// Windows drawn over the panes: the terminal pane and its rect, the quick look window.
+type Overlays<'a> = (
+    Option<(TerminalPaneWindow<'a>, Rect)>,
+    Option<QuickLookWindow<'a>>,
+);
+
 /// Is responsible for displaying content in the terminal.
 /// It uses an already created terminal.
 pub struct Display {
@@ -1537,13 +1985,32 @@ impl Display {
         let Ok(Size { width, height }) = self.term.size() else {
             return;
         };
+        let (height, terminal_pane_rect) = Rects::terminal_pane(status, width, height);
+        let terminal_pane = status
+            .terminal_pane
+            .as_ref()
+            .zip(terminal_pane_rect)
+            .map(|(terminal_pane, rect)| (TerminalPaneWindow::new(terminal_pane), rect));
         let full_rect = Rects::full_rect(width, height);
         let inside_border_rect = Rects::inside_border_rect(width, height);
         let borders = Self::borders(status);
+        let quick_look = QuickLookWindow::new(status);
         if Self::use_dual_pane(status, width) {
-            self.draw_dual(full_rect, inside_border_rect, borders, status);
+            self.draw_dual(
+                full_rect,
+                inside_border_rect,
+                borders,
+                status,
+                (terminal_pane, quick_look),
+            );
         } else {
-            self.draw_single(full_rect, inside_border_rect, borders, status);
+            self.draw_single(
+                full_rect,
+                inside_border_rect,
+                borders,
+                status,
+                (terminal_pane, quick_look),
+            );
         };
     }
 
@@ -1567,6 +2034,7 @@ impl Display {
         inside_border_rect: Rect,
         borders: [Style; 4],
         status: &Status,
+        overlays: Overlays,
     ) {
         let (file_left, file_right) = FilesBuilder::dual(status, full_rect.width);
         let menu_left = Menu::new(status, 0);
@@ -1583,6 +2051,7 @@ impl Display {
             inside_wins,
             (file_left, file_right),
             (menu_left, menu_right),
+            overlays,
         );
     }
 
@@ -1593,6 +2062,7 @@ impl Display {
         inside_wins: Vec<Rect>,
         files: (Files, Files),
         menus: (Menu, Menu),
+        (terminal_pane, quick_look): Overlays,
     ) {
         self.term
             .draw(|f| {
@@ -1604,6 +2074,13 @@ impl Display {
                 menus.0.draw(f, &inside_wins[2]);
                 files.1.draw(f, &inside_wins[3]);
                 menus.1.draw(f, &inside_wins[5]);
+                if let Some((terminal_pane, rect)) = &terminal_pane {
+                    terminal_pane.draw(f, rect);
+                }
+                if let Some(quick_look) = &quick_look {
+                    let area = f.area();
+                    quick_look.draw(f, &area);
+                }
             })
             .unwrap();
     }
@@ -1614,13 +2091,21 @@ impl Display {
         inside_border_rect: Rect,
         borders: [Style; 4],
         status: &Status,
+        overlays: Overlays,
     ) {
         let file_left = FilesBuilder::single(status);
         let menu_left = Menu::new(status, 0);
         let need_menu = status.tabs[0].need_menu_window();
         let bordered_wins = Rects::vertical_split_border(rect, need_menu);
         let inside_wins = Rects::vertical_split_inner(inside_border_rect, need_menu);
-        self.render_single(borders, bordered_wins, inside_wins, file_left, menu_left)
+        self.render_single(
+            borders,
+            bordered_wins,
+            inside_wins,
+            file_left,
+            menu_left,
+            overlays,
+        )
     }
 
     fn render_single(
@@ -1630,12 +2115,20 @@ impl Display {
         inside_wins: Rc<[Rect]>,
         file_left: Files,
         menu_left: Menu,
+        (terminal_pane, quick_look): Overlays,
     ) {
         self.term
             .draw(|f| {
                 Self::draw_single_borders(borders, f, &bordered_wins);
                 file_left.draw(f, &inside_wins[0]);
                 menu_left.draw(f, &inside_wins[2]);
+                if let Some((terminal_pane, rect)) = &terminal_pane {
+                    terminal_pane.draw(f, rect);
+                }
+                if let Some(quick_look) = &quick_look {
+                    let area = f.area();
+                    quick_look.draw(f, &area);
+                }
             })
             .unwrap();
     }
@@ -4,8 +4,9 @@
 // Copied and modified from https://github.com/9ary/gitprompt-rs/blob/master/src/main.rs
 // Couldn't use without forking and I'm lazy.
 
+use std::collections::HashMap;
 use std::fmt::Write as _;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context, Result};
 
@@ -150,18 +151,238 @@ pub fn git(path: &Path) -> Result<String> {
     }
     let porcerlain_output = String::from_utf8(output.stdout)?;
 
-    GitStatus::parse_porcelain2(porcerlain_output)
+    let mut git_string = GitStatus::parse_porcelain2(porcerlain_output)
         .context("Error while parsing Git output")?
-        .format_git_string()
+        .format_git_string()?;
+    if nearest_git_root(path).is_some_and(|root| is_submodule(&root)) {
+        git_string.push_str(" submodule");
+    }
+    Ok(git_string)
 }
 
 /// Returns the git root.
+/// It's the nearest ancestor of the current directory holding a `.git` entry.
+/// Submodules use a `.git` file instead of a directory, so their root is returned
+/// instead of the root of the superproject.
 /// Returns an error outside of a git repository.
 pub fn git_root() -> Result<String> {
-    let output = execute_and_output_no_log("git", ["rev-parse", "--show-toplevel"])?;
+    let current_dir = std::env::current_dir()?;
+    let root = nearest_git_root(&current_dir).context("git root: not in a git repository")?;
+    Ok(root.to_string_lossy().into_owned())
+}
+
+/// Nearest ancestor of `path` (itself included) holding a `.git` file or directory.
+fn nearest_git_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.join(".git").exists())
+        .map(|ancestor| ancestor.to_path_buf())
+}
+
+/// True if the repository at `root` is a submodule.
+/// A submodule `.git` is a file pointing to the `modules` directory of its superproject.
+/// Worktrees also use a `.git` file, pointing to a `worktrees` directory, and aren't submodules.
+fn is_submodule(root: &Path) -> bool {
+    let dot_git = root.join(".git");
+    if !dot_git.is_file() {
+        return false;
+    }
+    std::fs::read_to_string(dot_git).is_ok_and(|content| {
+        content
+            .trim()
+            .strip_prefix("gitdir:")
+            .is_some_and(|gitdir| {
+                Path::new(gitdir.trim())
+                    .components()
+                    .any(|c| c.as_os_str() == "modules")
+            })
+    })
+}
+
+/// Colored diff of a file against its version in HEAD, as printed by `git diff HEAD -- file`.
+/// The output keeps the ANSI colors of git.
+/// An empty string means the file is unchanged since HEAD.
+/// Returns an error if git isn't installed, if the file isn't in a git repository or isn't tracked.
+pub fn git_diff_head(path: &Path) -> Result<String> {
+    if !is_in_path("git") {
+        return Err(anyhow!("git diff: git isn't installed"));
+    }
+    let parent = path
+        .parent()
+        .context("git diff: the file should have a parent")?
+        .to_string_lossy();
+    let filename = path
+        .file_name()
+        .context("git diff: the file should have a name")?
+        .to_string_lossy();
+    let output = execute_and_output_no_log(
+        "git",
+        [
+            "-C",
+            &parent,
+            "ls-files",
+            "--error-unmatch",
+            "--",
+            &filename,
+        ],
+    )?;
     if !output.status.success() {
-        // We're most likely not in a Git repo
-        return Err(anyhow!("git root: git command returned an error"));
+        return Err(anyhow!(
+            "git diff: {filename} isn't tracked by a git repository"
+        ));
+    }
+    let output = execute_and_output_no_log(
+        "git",
+        [
+            "-C",
+            &parent,
+            "diff",
+            "--color=always",
+            "HEAD",
+            "--",
+            &filename,
+        ],
+    )?;
+    if !output.status.success() {
+        return Err(anyhow!("git diff: git command returned an error"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Last 50 commits affecting a directory, one per line, as printed by `git log --oneline -- dir`.
+/// The output keeps the ANSI colors of git.
+/// Returns an error if git isn't installed or if the directory isn't in a git repository.
+pub fn git_log(directory: &Path) -> Result<String> {
+    if !is_in_path("git") {
+        return Err(anyhow!("git log: git isn't installed"));
+    }
+    let directory = directory.to_string_lossy();
+    let output = execute_and_output_no_log(
+        "git",
+        ["-C", &directory, "rev-parse", "--is-inside-work-tree"],
+    )?;
+    if !output.status.success() {
+        return Err(anyhow!("git log: {directory} isn't in a git repository"));
+    }
+    let output = execute_and_output_no_log(
+        "git",
+        [
+            "-C",
+            &directory,
+            "log",
+            "--color=always",
+            "--oneline",
+            "-n",
+            "50",
+            "--",
+            ".",
+        ],
+    )?;
+    if !output.status.success() {
+        return Err(anyhow!("git log: git command returned an error"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Status of a single file in a git repository, as displayed next to its name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GitFileStatus {
+    Modified,
+    Added,
+    Deleted,
+    Renamed,
+    Unmerged,
+    Untracked,
+}
+
+impl GitFileStatus {
+    /// Parse the `XY` code of a `git status --porcelain` entry.
+    fn from_xy(xy: &str) -> Option<Self> {
+        match xy {
+            "??" => Some(Self::Untracked),
+            "DD" | "AU" | "UD" | "UA" | "DU" | "AA" | "UU" => Some(Self::Unmerged),
+            _ if xy.contains('R') || xy.contains('C') => Some(Self::Renamed),
+            _ if xy.contains('A') => Some(Self::Added),
+            _ if xy.contains('D') => Some(Self::Deleted),
+            _ if xy.contains('M') || xy.contains('T') => Some(Self::Modified),
+            _ => None,
+        }
+    }
+
+    /// Marker displayed before the filename.
+    pub fn marker(&self) -> &'static str {
+        match self {
+            Self::Modified => "M ",
+            Self::Added => "A ",
+            Self::Deleted => "D ",
+            Self::Renamed => "R ",
+            Self::Unmerged => "U ",
+            Self::Untracked => "??",
+        }
+    }
+}
+
+/// Git status of every modified file below `path`, the rest of the repository isn't read.
+/// Keys are absolute paths. A directory holding a modified file gets its status too.
+/// Returns None if git isn't installed or if `path` isn't in a git repository.
+/// It may be slow in a huge repository, it should be run in a thread.
+pub fn git_file_statuses(path: &Path) -> Option<HashMap<PathBuf, GitFileStatus>> {
+    if !is_in_path("git") {
+        return None;
+    }
+    let path_str = path.to_string_lossy();
+    let output =
+        execute_and_output_no_log("git", ["-C", &path_str, "rev-parse", "--show-toplevel"]).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let root = PathBuf::from(String::from_utf8(output.stdout).ok()?.trim());
+    let output = execute_and_output_no_log(
+        "git",
+        [
+            "-C",
+            &path_str,
+            "status",
+            "--porcelain=v1",
+            "-z",
+            "--untracked-files=all",
+            "--",
+            ".",
+        ],
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_porcelain1(
+        &String::from_utf8_lossy(&output.stdout),
+        &root,
+    ))
+}
+
+/// Parse the output of `git status --porcelain=v1 -z`.
+/// Entries are `XY path` separated by NUL. Renamed & copied entries are followed by their original path.
+fn parse_porcelain1(porcelain1_output: &str, root: &Path) -> HashMap<PathBuf, GitFileStatus> {
+    let mut statuses = HashMap::new();
+    let mut entries = porcelain1_output.split('\0');
+    while let Some(entry) = entries.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+        let (xy, relative_path) = (&entry[..2], &entry[3..]);
+        if xy.contains('R') || xy.contains('C') {
+            entries.next();
+        }
+        let Some(status) = GitFileStatus::from_xy(xy) else {
+            continue;
+        };
+        let path = root.join(relative_path);
+        for ancestor in path.ancestors().skip(1) {
+            if ancestor == root || !ancestor.starts_with(root) {
+                break;
+            }
+            statuses.entry(ancestor.to_owned()).or_insert(status);
+        }
+        statuses.insert(path, status);
     }
-    Ok(String::from_utf8(output.stdout)?.trim().to_owned())
+    statuses
 }
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::fs::{File, OpenOptions};
 use std::io::{Error as IoError, Write};
@@ -13,7 +14,8 @@ use crate::modes::{InputCompleted, InputSimple, Menu};
 
 /// The whole input history, read and written from and to a file.
 /// It's filtered by content.
-/// If the flag "log_are_enabled" is set to false, it will not be updated in the logs.
+/// If the flag "log_are_enabled" is set to false, only the commands (shell commands, exec and actions)
+/// are saved in the file. Other inputs are only kept during the current session.
 pub struct InputHistory {
     file_path: PathBuf,
     content: Vec<HistoryElement>,
@@ -98,23 +100,47 @@ impl InputHistory {
         }
     }
 
-    /// If logs are disabled, nothing is saved on disk, only during current session
+    /// Record a new input. It's ignored if it's the same as the last input of the same kind.
+    /// Commands are always saved on disk, other inputs only if logs are enabled.
     pub fn update(&mut self, mode: Menu, input_string: &str) -> Result<()> {
         let Some(elem) = HistoryElement::from_mode_input_string(mode, input_string) else {
             return Ok(());
         };
-        if let Some(last) = self.filtered.last() {
-            if *last == elem {
-                return Ok(());
-            }
+        if self
+            .content
+            .iter()
+            .rev()
+            .find(|last| last.kind == elem.kind)
+            .is_some_and(|last| *last == elem)
+        {
+            return Ok(());
         }
-        if self.log_are_enabled {
+        if self.log_are_enabled || elem.kind.is_command() {
             self.write_elem(&elem)?;
         }
         self.content.push(elem);
         Ok(())
     }
 
+    /// The recorded commands, most recent first, one per line.
+    /// A command typed many times is only listed once.
+    pub fn commands(&self) -> String {
+        let mut seen = HashSet::new();
+        self.content
+            .iter()
+            .rev()
+            .filter(|elem| elem.kind.is_command())
+            .map(|elem| elem.to_string())
+            .filter(|line| seen.insert(line.to_owned()))
+            .collect()
+    }
+
+    /// Parse a line of [`Self::commands`] into the menu of the command and its content.
+    pub fn recall(line: &str) -> Option<(Menu, String)> {
+        let elem = HistoryElement::from_str(Ok(line.to_owned())).ok()?;
+        Some((elem.kind.to_menu(), elem.content))
+    }
+
     /// True iff the mode is logged.
     /// It's almost always the case, only password mode isn't saved.
     /// This method is usefull to check if an input should be replaced when the user want to.
@@ -151,14 +177,31 @@ impl Display for HistoryKind {
                 InputSimple::Chmod => "Chmod",
                 InputSimple::Newfile => "Newfile",
                 InputSimple::Newdir => "Newdir",
+                InputSimple::NewdirAndEnter => "NewdirAndEnter",
                 InputSimple::RegexMatch => "RegexMatch",
+                InputSimple::GlobMatch => "GlobMatch",
                 InputSimple::Sort => "Sort",
                 InputSimple::Filter => "Filter",
                 InputSimple::SetNvimAddr => "SetNvimAddr",
                 InputSimple::ShellCommand => "ShellCommand",
+                InputSimple::ShellStream => "ShellStream",
                 InputSimple::Remote => "Remote",
+                InputSimple::MountByLabel => "MountByLabel",
+                InputSimple::ArchiveName => "ArchiveName",
+                InputSimple::NumberedRename => "NumberedRename",
+                InputSimple::ExtensionRename => "ExtensionRename",
+                InputSimple::SetXattr => "SetXattr",
+                InputSimple::RemoveXattr => "RemoveXattr",
+                InputSimple::SaveFlagged => "SaveFlagged",
+                InputSimple::LoadFlagged => "LoadFlagged",
+                InputSimple::GrepFlag => "GrepFlag",
+                InputSimple::CopyAs => "CopyAs",
+                InputSimple::ShellDryRun => "ShellDryRun",
+                InputSimple::SplitFile => "SplitFile",
                 InputSimple::CloudNewdir => "xxx",
                 InputSimple::Password(_, _) => "xxx",
+                InputSimple::ConfirmProtected => "xxx",
+                InputSimple::ConflictRename => "xxx",
             },
         };
         write!(f, "{menu}")
@@ -173,24 +216,61 @@ impl HistoryKind {
             "Exec" => Self::InputCompleted(InputCompleted::Exec),
             "Action" => Self::InputCompleted(InputCompleted::Action),
 
-            "Shell" => Self::InputSimple(InputSimple::ShellCommand),
+            "Shell" | "ShellCommand" => Self::InputSimple(InputSimple::ShellCommand),
+            "ShellStream" => Self::InputSimple(InputSimple::ShellStream),
             "Chmod" => Self::InputSimple(InputSimple::Chmod),
             "Sort" => Self::InputSimple(InputSimple::Sort),
             "Rename" => Self::InputSimple(InputSimple::Rename),
             "Newfile" => Self::InputSimple(InputSimple::Newfile),
             "Newdir" => Self::InputSimple(InputSimple::Newdir),
+            "NewdirAndEnter" => Self::InputSimple(InputSimple::NewdirAndEnter),
             "RegexMatch" => Self::InputSimple(InputSimple::RegexMatch),
+            "GlobMatch" => Self::InputSimple(InputSimple::GlobMatch),
             "Filter" => Self::InputSimple(InputSimple::Filter),
             "SetNvimAddr" => Self::InputSimple(InputSimple::SetNvimAddr),
             "Remote" => Self::InputSimple(InputSimple::Remote),
+            "MountByLabel" => Self::InputSimple(InputSimple::MountByLabel),
+            "ArchiveName" => Self::InputSimple(InputSimple::ArchiveName),
+            "NumberedRename" => Self::InputSimple(InputSimple::NumberedRename),
+            "ExtensionRename" => Self::InputSimple(InputSimple::ExtensionRename),
+            "SetXattr" => Self::InputSimple(InputSimple::SetXattr),
+            "RemoveXattr" => Self::InputSimple(InputSimple::RemoveXattr),
+            "SaveFlagged" => Self::InputSimple(InputSimple::SaveFlagged),
+            "LoadFlagged" => Self::InputSimple(InputSimple::LoadFlagged),
+            "GrepFlag" => Self::InputSimple(InputSimple::GrepFlag),
+            "CopyAs" => Self::InputSimple(InputSimple::CopyAs),
+            "ShellDryRun" => Self::InputSimple(InputSimple::ShellDryRun),
+            "SplitFile" => Self::InputSimple(InputSimple::SplitFile),
 
             _ => bail!("{kind} isn't a valid HistoryKind"),
         })
     }
 
+    /// True iff the input is a command: a shell command, an exec or an action.
+    fn is_command(&self) -> bool {
+        matches!(
+            self,
+            Self::InputSimple(
+                InputSimple::ShellCommand | InputSimple::ShellStream | InputSimple::ShellDryRun
+            ) | Self::InputCompleted(InputCompleted::Exec | InputCompleted::Action)
+        )
+    }
+
+    fn to_menu(&self) -> Menu {
+        match self {
+            Self::InputSimple(input_simple) => Menu::InputSimple(*input_simple),
+            Self::InputCompleted(input_completed) => Menu::InputCompleted(*input_completed),
+        }
+    }
+
     fn from_mode(menu_mode: Menu) -> Option<Self> {
         match menu_mode {
-            Menu::InputSimple(InputSimple::Password(_, _) | InputSimple::CloudNewdir) => None,
+            Menu::InputSimple(
+                InputSimple::Password(_, _)
+                | InputSimple::CloudNewdir
+                | InputSimple::ConfirmProtected
+                | InputSimple::ConflictRename,
+            ) => None,
             Menu::InputSimple(input_simple) => Some(Self::InputSimple(input_simple)),
             Menu::InputCompleted(input_completed) => Some(Self::InputCompleted(input_completed)),
             _ => None,
@@ -6,10 +6,11 @@
 //! - [`display::Display`] the displayer itself. All terminal display is made there. It's a single file, since why not ? with a single entry point. It then displays one to four windows after splitting the screen. This struct changed a lot after migration from tuikit to ratatui and is subject to a lot of internal changement.
 //! - [`draw_menu::DrawMenu`] is a trait used to display most of the menus. It's implemented directly most of the time.
 //! - [`git::git`] & [`git::git_root`] are function related to.. git. They're used to display the git porcelain v2 infos at the bottom and move to the git root of current folder.
-//! - [`input_history::InputHistory`] is a basic history of text inputs, filtered by menu mode. It's used to allow moving back to a previous input without remembering it. Don't forget that logs are disabled by default and require the argument flag `-l` to be enabled.
+//! - [`input_history::InputHistory`] is a basic history of text inputs, filtered by menu mode. It's used to allow moving back to a previous input without remembering it. Commands (shell commands, exec and actions) are always saved on disk, other inputs require logs, which are disabled by default and enabled by the argument flag `-l`.
 //! - `log` contains a few functions to setup, read & write to logs. They're used everywhere in the application for debugging (obviously) but also to display what the last action did.
 //! - [`opendal::OpendalContainer`] is the central struct dealing the google drive files, once the connection is established.
 //! - [`opener::Opener`] and other structs of this file are used to open files. The opener are configurable in the config files.
+//! - [`terminal_pane::TerminalPane`] is a shell running in a pseudo terminal, displayed at the bottom of the screen.
 
 mod args;
 mod commands;
@@ -20,13 +21,15 @@ mod input_history;
 mod log;
 mod opendal;
 mod opener;
+mod terminal_pane;
 
 pub use args::Args;
 pub use commands::*;
 pub use display::{color_to_style, Display, Offseted, MIN_WIDTH_FOR_DUAL_PANE};
 pub use draw_menu::*;
-pub use git::{git, git_root};
+pub use git::{git, git_diff_head, git_file_statuses, git_log, git_root, GitFileStatus};
 pub use input_history::*;
 pub use log::{read_last_log_line, read_log, set_loggers, write_log_info_once, write_log_line};
 pub use opendal::*;
 pub use opener::*;
+pub use terminal_pane::TerminalPane;
@@ -1,8 +1,12 @@
 use std::collections::HashMap;
 use std::fmt;
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 use anyhow::{anyhow, Context, Result};
+use content_inspector::inspect;
 use serde_yml::from_reader;
 use serde_yml::Value;
 use strum::IntoEnumIterator;
@@ -10,9 +14,13 @@ use strum_macros::{Display, EnumIter, EnumString};
 
 use crate::common::{
     is_in_path, tilde, OPENER_AUDIO, OPENER_DEFAULT, OPENER_IMAGE, OPENER_OFFICE, OPENER_PATH,
-    OPENER_READABLE, OPENER_TEXT, OPENER_VECT, OPENER_VIDEO,
+    OPENER_READABLE, OPENER_TEXT, OPENER_VECT, OPENER_VIDEO, XDOTOOL,
+};
+use crate::config::LazyMutex;
+use crate::io::{
+    execute, execute_and_capture_output_without_check, execute_and_output_no_log,
+    open_command_in_window,
 };
-use crate::io::{execute, open_command_in_window};
 use crate::log_info;
 use crate::modes::{
     decompress_7z, decompress_gz, decompress_xz, decompress_zip, extract_extension,
@@ -81,6 +89,51 @@ impl Extension {
         }
     }
 
+    /// Kind of files with this MIME type. Used when the extension isn't known.
+    pub fn from_mime(mime: &str) -> Self {
+        match mime {
+            "image/svg+xml" => Self::Vectorial,
+
+            "application/pdf" | "application/epub+zip" => Self::Readable,
+
+            "application/json"
+            | "application/javascript"
+            | "application/toml"
+            | "application/x-shellscript"
+            | "application/xml" => Self::Text,
+
+            "application/vnd.oasis.opendocument.text"
+            | "application/vnd.oasis.opendocument.spreadsheet"
+            | "application/vnd.oasis.opendocument.presentation"
+            | "application/msword"
+            | "application/vnd.ms-excel"
+            | "application/vnd.ms-powerpoint" => Self::Office,
+
+            "application/zip" => Self::Zip,
+
+            "application/x-xz" => Self::Xz,
+
+            "application/x-7z-compressed" => Self::Sevenz,
+
+            "application/gzip"
+            | "application/x-bzip2"
+            | "application/x-lzip"
+            | "application/x-lzma"
+            | "application/vnd.rar"
+            | "application/x-rar" => Self::Gz,
+
+            "application/x-iso9660-image" => Self::Iso,
+
+            _ => match mime.split_once('/') {
+                Some(("text", _)) => Self::Text,
+                Some(("image", _)) => Self::Bitmap,
+                Some(("audio", _)) => Self::Audio,
+                Some(("video", _)) => Self::Video,
+                _ => Self::Default,
+            },
+        }
+    }
+
     pub fn icon(&self) -> &'static str {
         match self {
             Self::Zip | Self::Xz | Self::Gz => "󰗄 ",
@@ -111,9 +164,12 @@ macro_rules! open_file_with {
 
 /// Holds an association map between `Extension` and `Info`.
 /// It's used to know how to open a kind of file.
+/// Files whose extension isn't known are matched by their MIME type,
+/// first against the `mime` section of the config file, then against the kinds of extensions.
 #[derive(Clone)]
 pub struct Association {
     association: HashMap<Extension, Kind>,
+    mime: HashMap<String, Kind>,
 }
 
 impl Default for Association {
@@ -135,6 +191,7 @@ impl Default for Association {
                 (Extension::Zip,        Kind::Internal(Internal::Zip)),
                 (Extension::Iso,        Kind::Internal(Internal::NotSupported)),
             ]),
+            mime: HashMap::new(),
         }
     }
 }
@@ -174,10 +231,25 @@ See https://raw.githubusercontent.com/qkzk/fm/master/config_files/fm/opener.yaml
         open_file_with!(self, "default", Default, yaml);
         open_file_with!(self, "vectorial_image", Vectorial, yaml);
         open_file_with!(self, "video", Video, yaml);
+        self.update_mime(&yaml["mime"]);
+    }
+
+    /// Read the `mime` section: a map of MIME types like `text/x-shellscript` or `image/*` to openers.
+    fn update_mime(&mut self, yaml: &Value) {
+        let Some(mapping) = yaml.as_mapping() else {
+            return;
+        };
+        for (mime, opener) in mapping.iter() {
+            let (Some(mime), Some(opener)) = (mime.as_str(), Kind::from_yaml(opener)) else {
+                continue;
+            };
+            self.mime.insert(mime.to_lowercase(), opener);
+        }
     }
 
     fn validate(&mut self) {
         self.association.retain(|_, info| info.is_valid());
+        self.mime.retain(|_, info| info.is_valid());
     }
 
     /// Converts itself into an hashmap of strings.
@@ -200,6 +272,57 @@ See https://raw.githubusercontent.com/qkzk/fm/master/config_files/fm/opener.yaml
         self.association
             .get(&Extension::matcher(&ext.to_lowercase()))
     }
+
+    /// Opener of a file whose extension isn't known.
+    /// The configured MIME types are tried first: exact match, then `family/*`.
+    /// Otherwise, the opener of the kind of files with this MIME type is used.
+    fn associate_mime(&self, mime: &str) -> Option<&Kind> {
+        if let Some(kind) = self.mime.get(mime) {
+            return Some(kind);
+        }
+        if let Some((family, _)) = mime.split_once('/') {
+            if let Some(kind) = self.mime.get(&format!("{family}/*")) {
+                return Some(kind);
+            }
+        }
+        self.association.get(&Extension::from_mime(mime))
+    }
+}
+
+/// MIME type of a file and its modification time when it was read.
+type CachedMime = (Option<SystemTime>, Option<String>);
+
+/// MIME types already read.
+static MIME_TYPES: LazyMutex<HashMap<PathBuf, CachedMime>> = LazyMutex::new(HashMap::new);
+
+/// MIME type of a file, read from its magic bytes.
+/// Text files are recognized from their content.
+/// It's cached per path until the file is modified since it's displayed at every frame.
+pub fn mime_type(path: &Path) -> Option<String> {
+    let modified = path.metadata().ok()?.modified().ok();
+    if let Some((read_at, mime)) = MIME_TYPES.lock().get(path) {
+        if *read_at == modified {
+            return mime.clone();
+        }
+    }
+    let mime = read_mime_type(path);
+    MIME_TYPES
+        .lock()
+        .insert(path.to_path_buf(), (modified, mime.clone()));
+    mime
+}
+
+fn read_mime_type(path: &Path) -> Option<String> {
+    let mut buffer = vec![];
+    std::fs::File::open(path)
+        .ok()?
+        .take(8192)
+        .read_to_end(&mut buffer)
+        .ok()?;
+    if let Some(kind) = infer::get(&buffer) {
+        return Some(kind.mime_type().to_owned());
+    }
+    inspect(&buffer).is_text().then(|| "text/plain".to_owned())
 }
 
 /// Some kind of files are "opened" using internal methods.
@@ -216,7 +339,19 @@ pub enum Internal {
 }
 
 impl Internal {
-    fn open(&self, path: &Path) -> Result<()> {
+    /// The internal decompressor of this archive, if fm can extract it itself : zip, gz, xz and 7z.
+    pub fn extractor(path: &Path) -> Option<Self> {
+        match Extension::matcher(&extract_extension(path).to_lowercase()) {
+            Extension::Zip => Some(Self::Zip),
+            Extension::Xz => Some(Self::Xz),
+            Extension::Gz => Some(Self::Gz),
+            Extension::Sevenz => Some(Self::Sevenz),
+            _ => None,
+        }
+    }
+
+    /// Extract the archive next to it.
+    pub fn open(&self, path: &Path) -> Result<()> {
         match self {
             Self::Sevenz => decompress_7z(path),
             Self::Zip => decompress_zip(path),
@@ -231,16 +366,22 @@ impl Internal {
 /// Most of the files are "opened" this way, only archives which could be
 /// decompressed interally aren't.
 ///
-/// It holds a path to the file (as a string, for convernience) and a
-/// flag set to true if the file is opened in a terminal.
+/// It holds a path to the file (as a string, for convernience), a
+/// flag set to true if the file is opened in a terminal and a flag set to true if it's opened in background.
 /// - without a terminal, the file is opened by its application,
 /// - with a terminal, it starts a new terminal (from configuration) and then the program.
+/// - in background, the focus is given back to the window of fm once the program is started.
 #[derive(Clone, Hash, PartialEq, Eq, Debug)]
-pub struct External(String, bool);
+pub struct External(String, bool, bool);
 
 impl External {
     fn new(opener_pair: (&str, bool)) -> Self {
-        Self(opener_pair.0.to_owned(), opener_pair.1)
+        Self(opener_pair.0.to_owned(), opener_pair.1, false)
+    }
+
+    fn in_background(mut self, background: bool) -> Self {
+        self.2 = background;
+        self
     }
 
     fn program(&self) -> &str {
@@ -251,7 +392,16 @@ impl External {
         self.1
     }
 
+    pub fn background(&self) -> bool {
+        self.2
+    }
+
     fn open(&self, paths: &[&str], term: &str, term_flag: &str) -> Result<()> {
+        let fm_window = if self.background() {
+            fm_window_id()
+        } else {
+            None
+        };
         let mut args: Vec<&str> = vec![self.program()];
         args.extend(paths);
         if self.use_term() {
@@ -259,6 +409,9 @@ impl External {
         } else {
             Self::without_term(args)?;
         }
+        if let Some(fm_window) = fm_window {
+            refocus_window(fm_window);
+        }
         Ok(())
     }
 
@@ -294,6 +447,32 @@ impl External {
     }
 }
 
+/// Id of the X11 window of fm, read from `$WINDOWID` (set by most terminal emulators) or asked to `xdotool`.
+/// None outside of X11 or if `xdotool` isn't installed.
+fn fm_window_id() -> Option<String> {
+    if std::env::var_os("DISPLAY").is_none() || !is_in_path(XDOTOOL) {
+        return None;
+    }
+    if let Some(window_id) = std::env::var("WINDOWID").ok().filter(|id| !id.is_empty()) {
+        return Some(window_id);
+    }
+    let window_id = execute_and_capture_output_without_check(XDOTOOL, &["getactivewindow"]).ok()?;
+    let window_id = window_id.trim();
+    (!window_id.is_empty()).then(|| window_id.to_owned())
+}
+
+/// Give the focus back to the window of fm, once the opened program had time to raise its own window.
+/// Slow programs may raise their window later, so it's done a few times, in a thread.
+/// The window manager may refuse it.
+fn refocus_window(window_id: String) {
+    thread::spawn(move || {
+        for delay in [250, 750, 1500] {
+            thread::sleep(Duration::from_millis(delay));
+            let _ = execute_and_output_no_log(XDOTOOL, ["windowactivate", window_id.as_str()]);
+        }
+    });
+}
+
 /// A way to open one kind of files.
 /// It's either an internal method or an external program.
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
@@ -314,10 +493,17 @@ impl Kind {
     }
 
     fn from_yaml(yaml: &Value) -> Option<Self> {
-        Some(Self::external((
-            yaml.get("opener")?.as_str()?,
-            yaml.get("use_term")?.as_bool()?,
-        )))
+        let background = yaml
+            .get("background")
+            .and_then(|background| background.as_bool())
+            .unwrap_or(false);
+        Some(Self::External(
+            External::new((
+                yaml.get("opener")?.as_str()?,
+                yaml.get("use_term")?.as_bool()?,
+            ))
+            .in_background(background),
+        ))
     }
 
     fn is_external(&self) -> bool {
@@ -329,7 +515,7 @@ impl Kind {
     }
 
     fn external_program(&self) -> Result<(&str, bool)> {
-        let Self::External(External(program, use_term)) = self else {
+        let Self::External(External(program, use_term, _)) = self else {
             return Err(anyhow!("not an external opener"));
         };
         Ok((program, *use_term))
@@ -338,7 +524,7 @@ impl Kind {
 
 impl fmt::Display for Kind {
     fn fmt(&self, f: &mut fmt::Formatter) -> std::fmt::Result {
-        let s = if let Self::External(External(program, _)) = &self {
+        let s = if let Self::External(External(program, _, _)) = &self {
             program
         } else {
             "internal"
@@ -383,11 +569,22 @@ impl Opener {
     /// It's used to check if the file can be opened without specific actions or not.
     /// This opener can't mutate the status and can't ask for a sudo password.
     /// Some files requires root to be opened (ie. ISO files which are mounted).
+    /// Files without a known extension are matched by their MIME type.
     pub fn kind(&self, path: &Path) -> Option<&Kind> {
         if path.is_dir() {
             return None;
         }
-        self.association.associate(extract_extension(path))
+        let extension = extract_extension(path);
+        if !matches!(
+            Extension::matcher(&extension.to_lowercase()),
+            Extension::Default
+        ) {
+            return self.association.associate(extension);
+        }
+        match mime_type(path) {
+            Some(mime) => self.association.associate_mime(&mime),
+            None => self.association.associate(extension),
+        }
     }
 
     /// Does this extension requires a terminal ?
@@ -442,11 +639,11 @@ impl Opener {
     pub fn regroup_per_opener(&self, paths: &[PathBuf]) -> HashMap<External, Vec<PathBuf>> {
         let mut openers: HashMap<External, Vec<PathBuf>> = HashMap::new();
         for path in paths {
-            let Some(Kind::External(pair)) = self.kind(path) else {
+            let Some(Kind::External(external)) = self.kind(path) else {
                 continue;
             };
             openers
-                .entry(External(pair.0.to_owned(), pair.1).to_owned())
+                .entry(external.to_owned())
                 .and_modify(|files| files.push((*path).to_owned()))
                 .or_insert(vec![(*path).to_owned()]);
         }
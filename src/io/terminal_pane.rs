@@ -0,0 +1,157 @@
+use std::env;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use parking_lot::Mutex;
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+
+use crate::event::FmEvents;
+use crate::log_info;
+
+/// A shell running in a pseudo terminal, displayed in a pane at the bottom of the screen.
+///
+/// The output of the shell is parsed by a terminal emulator (`vt100`) in a thread
+/// and the display reads its screen.
+/// When the pane is focused, every key is forwarded to the shell.
+/// When the shell exits, an event is sent and the pane is closed.
+pub struct TerminalPane {
+    parser: Arc<Mutex<vt100::Parser>>,
+    master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+    focused: bool,
+}
+
+impl TerminalPane {
+    /// Start a new shell ($SHELL, default to bash) in `cwd`.
+    /// `rows` and `cols` are the size of the pane, inside its borders.
+    pub fn new(cwd: &Path, rows: u16, cols: u16, fm_sender: Arc<Sender<FmEvents>>) -> Result<Self> {
+        let pair = native_pty_system().openpty(Self::pty_size(rows, cols))?;
+        let shell = env::var("SHELL").unwrap_or_else(|_| "bash".to_string());
+        let mut command = CommandBuilder::new(shell);
+        command.cwd(cwd);
+        let child = pair.slave.spawn_command(command)?;
+        drop(pair.slave);
+        let reader = pair.master.try_clone_reader()?;
+        let writer = pair.master.take_writer()?;
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, 0)));
+        Self::read_in_thread(reader, Arc::clone(&parser), fm_sender);
+        Ok(Self {
+            parser,
+            master: pair.master,
+            writer,
+            child,
+            focused: true,
+        })
+    }
+
+    fn pty_size(rows: u16, cols: u16) -> PtySize {
+        PtySize {
+            rows,
+            cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        }
+    }
+
+    /// Feed the terminal emulator with the output of the shell until it exits.
+    fn read_in_thread(
+        mut reader: Box<dyn Read + Send>,
+        parser: Arc<Mutex<vt100::Parser>>,
+        fm_sender: Arc<Sender<FmEvents>>,
+    ) {
+        thread::spawn(move || {
+            let mut buffer = [0; 4096];
+            loop {
+                match reader.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => parser.lock().process(&buffer[..read]),
+                }
+            }
+            log_info!("terminal pane: shell exited");
+            let _ = fm_sender.send(FmEvents::TerminalPaneExited);
+        });
+    }
+
+    /// Height of the pane, borders included, for a terminal of this height.
+    pub fn height(term_height: u16) -> u16 {
+        term_height / 3
+    }
+
+    /// Resize the pseudo terminal and its emulator.
+    /// `rows` and `cols` are the size of the pane, inside its borders.
+    pub fn resize(&mut self, rows: u16, cols: u16) -> Result<()> {
+        self.master.resize(Self::pty_size(rows, cols))?;
+        self.parser.lock().set_size(rows, cols);
+        Ok(())
+    }
+
+    /// True if the keys are forwarded to the shell.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    pub fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// The terminal emulator, holding the screen of the shell.
+    pub fn parser(&self) -> &Arc<Mutex<vt100::Parser>> {
+        &self.parser
+    }
+
+    /// Forward a key to the shell.
+    /// Keys without a known escape sequence are ignored.
+    pub fn write_key(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(bytes) = key_to_bytes(key) else {
+            return Ok(());
+        };
+        self.writer.write_all(&bytes)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for TerminalPane {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+    }
+}
+
+/// Bytes sent by a terminal emulator for this key.
+fn key_to_bytes(key: KeyEvent) -> Option<Vec<u8>> {
+    let bytes = match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            vec![(c.to_ascii_lowercase() as u8) & 0x1f]
+        }
+        KeyCode::Char(c) => c.to_string().into_bytes(),
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => b"\x1b[A".to_vec(),
+        KeyCode::Down => b"\x1b[B".to_vec(),
+        KeyCode::Right => b"\x1b[C".to_vec(),
+        KeyCode::Left => b"\x1b[D".to_vec(),
+        KeyCode::Home => b"\x1b[H".to_vec(),
+        KeyCode::End => b"\x1b[F".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        _ => return None,
+    };
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        let mut with_escape = vec![0x1b];
+        with_escape.extend(bytes);
+        Some(with_escape)
+    } else {
+        Some(bytes)
+    }
+}
@@ -1,16 +1,45 @@
 use std::borrow::Borrow;
-use std::collections::{BTreeSet, VecDeque};
-use std::fs::read_dir;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fs::{read_dir, DirEntry};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::Sender;
 use std::sync::Arc;
+use std::thread;
 
 use anyhow::{Context, Result};
 
 use crate::app::TabSettings;
-use crate::io::git;
+use crate::config::with_binary_size_units;
+use crate::event::FmEvents;
+use crate::io::{git, git_file_statuses, GitFileStatus};
 use crate::modes::{is_not_hidden, path_is_video, FileInfo, FileKind, FilterKind, SortKind, Users};
 use crate::{impl_content, impl_index_to_index, impl_selectable, log_info};
 
+/// Directories with more entries than this are read in a background thread.
+const BACKGROUND_LOADING_THRESHOLD: usize = 10_000;
+/// Number of files read before displaying a directory read in the background.
+const FIRST_CHUNK_SIZE: usize = 1_000;
+/// Number of files sent at once by the background thread.
+const CHUNK_SIZE: usize = 5_000;
+
+/// Identifies every background loading, so late chunks of a previous one are ignored.
+static NEXT_LOADING_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A directory whose files are still read in a background thread.
+struct Loading {
+    /// Sent with every chunk of files.
+    id: u64,
+    /// Set to stop the thread when the directory is changed or refreshed before it's loaded.
+    cancel: Arc<AtomicBool>,
+}
+
+impl Drop for Loading {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
 /// Holds the information about file in the current directory.
 /// We know about the current path, the files themselves, the selected index,
 /// the "display all files including hidden" flag and the key to sort files.
@@ -22,26 +51,53 @@ pub struct Directory {
     /// The index of the selected file.
     pub index: usize,
     used_space: u64,
+    /// Git status of modified files, None outside of a git repository.
+    /// Read in a thread when the directory is changed or refreshed, see [`Directory::update_git_statuses`].
+    git_statuses: Option<HashMap<PathBuf, GitFileStatus>>,
+    /// Last git status read of every visited directory, displayed until the new one is read.
+    git_cache: HashMap<PathBuf, Option<HashMap<PathBuf, GitFileStatus>>>,
+    /// Manual display order of the visited directories, set by moving files up or down.
+    /// It overrides the sort until the directory is refreshed or sorted again.
+    manual_orders: HashMap<PathBuf, Vec<PathBuf>>,
+    /// Set while the files of a huge directory are read in the background.
+    /// Files are appended as they're read and sorted once every file is read.
+    loading: Option<Loading>,
+    /// File reselected while loading before it was read, with the index selected meanwhile.
+    /// It's selected once every file is read, unless another file was selected since.
+    pending_selection: Option<(PathBuf, usize)>,
+    fm_sender: Arc<Sender<FmEvents>>,
 }
 
 impl Directory {
     /// Reads the paths and creates a new `PathContent`.
     /// Files are sorted by filename by default.
     /// Selects the first file if any.
-    pub fn new(path: &Path, users: &Users, filter: &FilterKind, show_hidden: bool) -> Result<Self> {
-        let path = Arc::from(path);
-        let mut content = Self::files(&path, show_hidden, filter, users)?;
-        let sort_kind = SortKind::default();
-        sort_kind.sort(&mut content);
-        let index: usize = 0;
-        let used_space = get_used_space(&content);
-
-        Ok(Self {
-            path,
-            content,
-            index,
-            used_space,
-        })
+    /// Huge directories are read in the background, see [`Directory::is_loading`].
+    pub fn new(
+        path: &Path,
+        users: &Users,
+        filter: &FilterKind,
+        show_hidden: bool,
+        fm_sender: Arc<Sender<FmEvents>>,
+    ) -> Result<Self> {
+        let path: Arc<Path> = Arc::from(path);
+        let mut directory = Self {
+            path: path.clone(),
+            content: vec![],
+            index: 0,
+            used_space: 0,
+            git_statuses: None,
+            git_cache: HashMap::new(),
+            manual_orders: HashMap::new(),
+            loading: None,
+            pending_selection: None,
+            fm_sender,
+        };
+        directory.load_files(&path, show_hidden, filter, users)?;
+        SortKind::default().sort(&mut directory.content);
+        directory.used_space = get_used_space(&directory.content);
+        directory.update_git_statuses();
+        Ok(directory)
     }
 
     pub fn change_directory(
@@ -50,25 +106,111 @@ impl Directory {
         settings: &TabSettings,
         users: &Users,
     ) -> Result<()> {
-        self.content = Self::files(path, settings.show_hidden, &settings.filter, users)?;
-        settings.sort_kind.sort(&mut self.content);
+        self.load_files(
+            path,
+            settings.shows_hidden_in(path),
+            &settings.filter,
+            users,
+        )?;
+        self.sort_content(&settings.sort_kind);
+        if settings.group_by_extension {
+            self.group_by_extension();
+        }
         self.index = 0;
         self.used_space = get_used_space(&self.content);
         self.path = Arc::from(path);
+        self.update_git_statuses();
+        self.apply_manual_order();
         Ok(())
     }
 
-    fn files(
+    /// Replace the content by the files of `path`, cancelling any background loading.
+    /// If the directory holds too many files, only the first ones are read now and
+    /// the others are read in a thread, sent by chunks with [`FmEvents::DirectoryChunk`].
+    fn load_files(
+        &mut self,
         path: &Path,
         show_hidden: bool,
         filter_kind: &FilterKind,
         users: &Users,
-    ) -> Result<Vec<FileInfo>> {
-        let mut files: Vec<FileInfo> = Self::create_dot_dotdot(path, users)?;
-        if let Some(true_files) = files_collection(path, users, show_hidden, filter_kind, false) {
-            files.extend(true_files);
+    ) -> Result<()> {
+        self.loading = None;
+        self.pending_selection = None;
+        self.content = Self::create_dot_dotdot(path, users)?;
+        let Some(mut entries) = visible_entries(path, show_hidden) else {
+            return Ok(());
+        };
+        if entries.len() <= BACKGROUND_LOADING_THRESHOLD {
+            self.content
+                .extend(entries_to_files(entries, users, filter_kind, false));
+            return Ok(());
+        }
+        let rest = entries.split_off(FIRST_CHUNK_SIZE);
+        self.content
+            .extend(entries_to_files(entries, users, filter_kind, false));
+        self.load_in_background(rest, users.clone(), filter_kind.clone());
+        Ok(())
+    }
+
+    fn load_in_background(
+        &mut self,
+        entries: Vec<DirEntry>,
+        users: Users,
+        filter_kind: FilterKind,
+    ) {
+        let id = NEXT_LOADING_ID.fetch_add(1, Ordering::Relaxed);
+        let cancel = Arc::new(AtomicBool::new(false));
+        let thread_cancel = cancel.clone();
+        let fm_sender = self.fm_sender.clone();
+        thread::spawn(move || {
+            send_chunks(
+                id,
+                entries,
+                &users,
+                &filter_kind,
+                &thread_cancel,
+                &fm_sender,
+            )
+        });
+        self.loading = Some(Loading { id, cancel });
+    }
+
+    /// True iff some files of this directory are still read in the background.
+    pub fn is_loading(&self) -> bool {
+        self.loading.is_some()
+    }
+
+    /// True iff the files of this directory are read by the background loading `id`.
+    pub fn is_loaded_by(&self, id: u64) -> bool {
+        self.loading
+            .as_ref()
+            .is_some_and(|loading| loading.id == id)
+    }
+
+    /// Append files read in the background. They're sorted once every file is read.
+    pub fn extend_loaded(&mut self, files: Vec<FileInfo>) {
+        self.used_space += get_used_space(&files);
+        self.content.extend(files);
+    }
+
+    /// Every file was read in the background: sort them and select the same file as before,
+    /// or the file reselected before it was read, see [`Directory::reselect_file`].
+    pub fn finish_loading(&mut self, settings: &TabSettings) {
+        self.loading = None;
+        let selected = self
+            .pending_selection
+            .take()
+            .filter(|(_, index)| *index == self.index)
+            .map(|(path, _)| path)
+            .or_else(|| self.selected().map(|file| file.path.to_path_buf()));
+        self.sort_content(&settings.sort_kind);
+        if settings.group_by_extension {
+            self.group_by_extension();
+        }
+        self.apply_manual_order();
+        if let Some(selected) = selected {
+            self.select_file(&selected);
         }
-        Ok(files)
     }
 
     fn create_dot_dotdot(path: &Path, users: &Users) -> Result<Vec<FileInfo>> {
@@ -81,10 +223,64 @@ impl Directory {
     }
 
     /// Sort the file with current key.
+    /// The manual order of this directory is dropped.
     pub fn sort(&mut self, sort_kind: &SortKind) {
+        self.manual_orders.remove(self.path.as_ref());
+        self.sort_content(sort_kind)
+    }
+
+    /// Sort the files with the key of the tab, every reading of the files uses it.
+    fn sort_content(&mut self, sort_kind: &SortKind) {
         sort_kind.sort(&mut self.content)
     }
 
+    /// Move the selected file one row up or down in the displayed order, without modifying anything on disk.
+    /// `.` and `..` are never moved and the other files can't be moved above them.
+    /// The new order is kept for this directory until it's refreshed or sorted again.
+    pub fn move_selected(&mut self, up: bool) {
+        let index = self.index;
+        let Some(other) = (if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1)
+        }) else {
+            return;
+        };
+        if other >= self.content.len()
+            || is_dot_or_dotdot(&self.content[index])
+            || is_dot_or_dotdot(&self.content[other])
+        {
+            return;
+        }
+        self.content.swap(index, other);
+        self.index = other;
+        let order = self
+            .content
+            .iter()
+            .map(|file| file.path.to_path_buf())
+            .collect();
+        self.manual_orders.insert(self.path.to_path_buf(), order);
+    }
+
+    /// Reorder the files of this directory as they were manually ordered, if they were.
+    /// Files created since then are displayed after the others, in the current sort order.
+    fn apply_manual_order(&mut self) {
+        let Some(order) = self.manual_orders.get(self.path.as_ref()) else {
+            return;
+        };
+        let positions: HashMap<&Path, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(position, path)| (path.as_path(), position))
+            .collect();
+        self.content.sort_by_key(|file| {
+            positions
+                .get(file.path.as_ref())
+                .copied()
+                .unwrap_or(usize::MAX)
+        });
+    }
+
     /// Calculates the size of the owner column.
     pub fn owner_column_width(&self) -> usize {
         let owner_size_btreeset: BTreeSet<usize> =
@@ -107,15 +303,72 @@ impl Directory {
     }
 
     /// Reset the current file content.
-    /// Reads and sort the content with current key.
+    /// Reads and sort the content with current key, the manual order is dropped.
     /// Select the first file if any.
     pub fn reset_files(&mut self, settings: &TabSettings, users: &Users) -> Result<()> {
-        self.content = Self::files(&self.path, settings.show_hidden, &settings.filter, users)?;
-        self.sort(&SortKind::default());
+        let path = self.path.clone();
+        self.load_files(
+            &path,
+            settings.shows_hidden_in(&path),
+            &settings.filter,
+            users,
+        )?;
+        self.sort(&settings.sort_kind);
+        if settings.group_by_extension {
+            self.group_by_extension();
+        }
         self.index = 0;
+        self.used_space = get_used_space(&self.content);
+        self.update_git_statuses();
         Ok(())
     }
 
+    /// Partition the files by extension, directories first.
+    /// The sort is stable, the order inside a group is kept.
+    pub fn group_by_extension(&mut self) {
+        self.content.sort_by(|a, b| {
+            b.is_dir()
+                .cmp(&a.is_dir())
+                .then_with(|| a.extension.cmp(&b.extension))
+        });
+    }
+
+    /// Rows of the grouped view : a header before every group of files sharing the same extension.
+    /// Headers aren't selectable, files rows hold their index in content.
+    pub fn grouped_rows(&self) -> Vec<GroupedRow<'_>> {
+        let mut rows = vec![];
+        let mut current_group = None;
+        for (index, file) in self.enumerate() {
+            let group = group_name(file);
+            if current_group != Some(group) {
+                rows.push(GroupedRow::Header(group));
+                current_group = Some(group);
+            }
+            rows.push(GroupedRow::File(index));
+        }
+        rows
+    }
+
+    /// Index of the first displayed row in the grouped view.
+    /// We start from the top of the window (and its header if it's the first of its group)
+    /// and make sure the selected file is displayed.
+    pub fn grouped_top_row(&self, rows: &[GroupedRow], window_top: usize, height: usize) -> usize {
+        let row_of = |target: usize| {
+            rows.iter()
+                .position(|row| matches!(row, GroupedRow::File(index) if *index == target))
+                .unwrap_or_default()
+        };
+        let mut top_row = row_of(window_top);
+        if top_row > 0 && matches!(rows[top_row - 1], GroupedRow::Header(_)) {
+            top_row -= 1;
+        }
+        let selected_row = row_of(self.index);
+        if selected_row >= top_row + height {
+            top_row = selected_row + 1 - height;
+        }
+        top_row
+    }
+
     /// Is the selected file a directory ?
     /// It may fails if the current path is empty, aka if nothing is selected.
     pub fn is_selected_dir(&self) -> Result<bool> {
@@ -142,6 +395,42 @@ impl Directory {
         git(&self.path)
     }
 
+    /// True iff the current path is in a git repository.
+    pub fn is_in_git_repo(&self) -> bool {
+        self.git_statuses.is_some()
+    }
+
+    /// Read the git status of the current directory in a thread, sent with [`FmEvents::GitStatuses`].
+    /// The last status read for this directory is displayed meanwhile.
+    fn update_git_statuses(&mut self) {
+        self.git_statuses = self.git_cache.get(self.path.as_ref()).cloned().flatten();
+        let path = self.path.to_path_buf();
+        let fm_sender = self.fm_sender.clone();
+        thread::spawn(move || {
+            let statuses = git_file_statuses(&path);
+            fm_sender
+                .send(FmEvents::GitStatuses(path, statuses))
+                .unwrap_or_default();
+        });
+    }
+
+    /// Git status of `path` was read in a thread. It's displayed if it's still the current directory.
+    pub fn set_git_statuses(
+        &mut self,
+        path: &Path,
+        statuses: Option<HashMap<PathBuf, GitFileStatus>>,
+    ) {
+        if self.path.as_ref() == path {
+            self.git_statuses = statuses.clone();
+        }
+        self.git_cache.insert(path.to_path_buf(), statuses);
+    }
+
+    /// Git status of a file, None if it's not modified or outside of a git repository.
+    pub fn git_status(&self, path: &Path) -> Option<GitFileStatus> {
+        self.git_statuses.as_ref()?.get(path).copied()
+    }
+
     /// Returns an iterator of the files (`FileInfo`) in content.
     #[inline]
     pub fn iter(&self) -> std::slice::Iter<'_, FileInfo> {
@@ -168,6 +457,16 @@ impl Directory {
         index
     }
 
+    /// Select the file from its path, like [`Directory::select_file`].
+    /// While loading, a file which isn't read yet is selected once every file is read.
+    pub fn reselect_file(&mut self, path: &Path) -> usize {
+        let index = self.select_file(path);
+        if self.is_loading() && self.find_jump_index(path).is_none() {
+            self.pending_selection = Some((path.to_path_buf(), index));
+        }
+        index
+    }
+
     /// Returns a vector of paths from content
     pub fn paths(&self) -> Vec<&Path> {
         self.content
@@ -187,6 +486,49 @@ impl Directory {
         selected.path.as_ref() == parent
     }
 
+    /// Size of every file, used to describe what changed since, with [`Directory::changes_since`].
+    /// `.` and `..` are ignored.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            path: self.path.clone(),
+            sizes: self
+                .iter()
+                .filter(|file| !is_dot_or_dotdot(file))
+                .map(|file| (file.path.clone(), file.true_size))
+                .collect(),
+        }
+    }
+
+    /// Describe the files added and removed since the snapshot and the variation of their size.
+    /// Returns None if nothing changed, if the snapshot was taken in another directory
+    /// or if the directory is still loading.
+    pub fn changes_since(&self, previous: &Snapshot) -> Option<String> {
+        if previous.path != self.path || self.is_loading() {
+            return None;
+        }
+        let current = self.snapshot();
+        let added = current
+            .sizes
+            .keys()
+            .filter(|path| !previous.sizes.contains_key(*path))
+            .count();
+        let removed = previous
+            .sizes
+            .keys()
+            .filter(|path| !current.sizes.contains_key(*path))
+            .count();
+        let before: u64 = previous.sizes.values().sum();
+        let after: u64 = current.sizes.values().sum();
+        if added == 0 && removed == 0 && before == after {
+            return None;
+        }
+        let sign = if after >= before { '+' } else { '-' };
+        Some(format!(
+            "{added} added, {removed} removed, size {sign}{delta}",
+            delta = human_size(after.abs_diff(before)).trim()
+        ))
+    }
+
     pub fn videos(&self) -> VecDeque<PathBuf> {
         self.content()
             .iter()
@@ -196,6 +538,30 @@ impl Directory {
     }
 }
 
+/// Files of a directory and their sizes at some point.
+pub struct Snapshot {
+    path: Arc<Path>,
+    sizes: HashMap<Arc<Path>, u64>,
+}
+
+/// A row of the grouped view, either a group header or a file.
+pub enum GroupedRow<'a> {
+    Header(&'a str),
+    File(usize),
+}
+
+/// Name of the group of a file in the grouped view.
+/// Directories are grouped together, files without extension too.
+fn group_name(file: &FileInfo) -> &str {
+    if file.is_dir() {
+        "directories"
+    } else if file.extension.is_empty() {
+        "no extension"
+    } else {
+        &file.extension
+    }
+}
+
 impl_index_to_index!(FileInfo, Directory);
 impl_selectable!(Directory);
 impl_content!(FileInfo, Directory);
@@ -208,23 +574,41 @@ fn get_used_space(files: &[FileInfo]) -> u64 {
         .sum()
 }
 
-/// Creates an optional vector of fileinfo contained in a file.
-/// Files are filtered by filterkind and the display hidden flag.
-/// Returns None if there's no file.
-pub fn files_collection(
-    path: &Path,
+/// Read the entries by chunks of [`CHUNK_SIZE`] files, sent with the id of their loading,
+/// then send [`FmEvents::DirectoryLoaded`].
+/// Stops as soon as the loading is cancelled or fm can't receive the chunks anymore.
+fn send_chunks(
+    id: u64,
+    entries: Vec<DirEntry>,
     users: &Users,
-    show_hidden: bool,
     filter_kind: &FilterKind,
-    keep_dir: bool,
-) -> Option<Vec<FileInfo>> {
+    cancel: &AtomicBool,
+    fm_sender: &Sender<FmEvents>,
+) {
+    let mut entries = entries.into_iter().peekable();
+    while entries.peek().is_some() {
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        let chunk: Vec<DirEntry> = entries.by_ref().take(CHUNK_SIZE).collect();
+        let files = entries_to_files(chunk, users, filter_kind, false).collect();
+        if fm_sender.send(FmEvents::DirectoryChunk(id, files)).is_err() {
+            return;
+        }
+    }
+    fm_sender
+        .send(FmEvents::DirectoryLoaded(id))
+        .unwrap_or_default();
+}
+
+/// Entries of a directory, hidden ones are skipped unless `show_hidden` is set.
+/// Returns None if the directory can't be read.
+fn visible_entries(path: &Path, show_hidden: bool) -> Option<Vec<DirEntry>> {
     match read_dir(path) {
         Ok(read_dir) => Some(
             read_dir
                 .filter_map(|direntry| direntry.ok())
                 .filter(|direntry| show_hidden || is_not_hidden(direntry).unwrap_or(true))
-                .filter_map(|direntry| FileInfo::from_direntry(&direntry, users).ok())
-                .filter(|fileinfo| filter_kind.filter_by(fileinfo, keep_dir))
                 .collect(),
         ),
         Err(error) => {
@@ -234,22 +618,230 @@ pub fn files_collection(
     }
 }
 
+/// Creates the fileinfo of those entries, filtered by filterkind.
+fn entries_to_files<'a>(
+    entries: Vec<DirEntry>,
+    users: &'a Users,
+    filter_kind: &'a FilterKind,
+    keep_dir: bool,
+) -> impl Iterator<Item = FileInfo> + 'a {
+    entries
+        .into_iter()
+        .filter_map(|direntry| FileInfo::from_direntry(&direntry, users).ok())
+        .filter(move |fileinfo| filter_kind.filter_by(fileinfo, keep_dir))
+}
+
+/// Creates an optional vector of fileinfo contained in a file.
+/// Files are filtered by filterkind and the display hidden flag.
+/// Returns None if there's no file.
+pub fn files_collection(
+    path: &Path,
+    users: &Users,
+    show_hidden: bool,
+    filter_kind: &FilterKind,
+    keep_dir: bool,
+) -> Option<Vec<FileInfo>> {
+    let entries = visible_entries(path, show_hidden)?;
+    Some(entries_to_files(entries, users, filter_kind, keep_dir).collect())
+}
+
 const SIZES: [&str; 9] = ["B", "k", "M", "G", "T", "P", "E", "Z", "Y"];
+const BINARY_SIZES: [&str; 9] = ["B", "K", "M", "G", "T", "P", "E", "Z", "Y"];
 
 /// Convert a file size from bytes to human readable string.
+/// SI units (1k = 1000 bytes) are used by default, binary units (1K = 1024 bytes) can be configured
+/// with `size_units: binary`. Like `ls -h` and `ls --si`, binary units are uppercase.
+/// The string is always 4 chars wide, a size is expressed in the next unit once it reaches 1000.
 #[inline]
 pub fn human_size(bytes: u64) -> String {
+    let (base, sizes) = if with_binary_size_units() {
+        (1024.0, &BINARY_SIZES)
+    } else {
+        (1000.0, &SIZES)
+    };
     let mut factor = 0;
     let mut size = bytes as f64;
 
-    while size >= 1000.0 && factor < SIZES.len() - 1 {
-        size /= 1000.0;
+    while size >= 1000.0 && factor < sizes.len() - 1 {
+        size /= base;
         factor += 1;
     }
 
     if size < 10.0 && factor > 0 {
-        format!("{:.1}{}", size, SIZES[factor])
+        format!("{:.1}{}", size, sizes[factor])
     } else {
-        format!("{:>3}{}", size.round() as i64, SIZES[factor])
+        format!("{:>3}{}", size.round() as i64, sizes[factor])
+    }
+}
+
+fn is_dot_or_dotdot(file: &FileInfo) -> bool {
+    file.filename.as_ref() == "." || file.filename.as_ref() == ".."
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::write;
+    use std::sync::mpsc::{channel, Receiver};
+    use std::time::Duration;
+
+    use super::*;
+
+    /// A directory with enough files to be read in the background.
+    fn huge_directory() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        for index in 0..BACKGROUND_LOADING_THRESHOLD + 10 {
+            write(root.path().join(format!("file_{index:05}")), "").unwrap();
+        }
+        root
+    }
+
+    fn settings() -> TabSettings {
+        TabSettings {
+            show_hidden: false,
+            filter: FilterKind::All,
+            sort_kind: SortKind::default(),
+            group_by_extension: false,
+            hidden_here: None,
+        }
+    }
+
+    fn open(path: &Path) -> (Directory, Receiver<FmEvents>) {
+        let (tx, rx) = channel();
+        let directory = Directory::new(
+            path,
+            &Users::default(),
+            &FilterKind::All,
+            false,
+            Arc::new(tx),
+        )
+        .unwrap();
+        (directory, rx)
+    }
+
+    fn loading_id(directory: &Directory) -> u64 {
+        directory.loading.as_ref().expect("should be loading").id
+    }
+
+    /// Files sent by the background loading `id` until it's done.
+    fn receive_loading(rx: &Receiver<FmEvents>, id: u64) -> Vec<FileInfo> {
+        let mut files = vec![];
+        loop {
+            match rx.recv_timeout(Duration::from_secs(10)).unwrap() {
+                FmEvents::DirectoryChunk(chunk_id, chunk) if chunk_id == id => files.extend(chunk),
+                FmEvents::DirectoryLoaded(loaded_id) if loaded_id == id => return files,
+                _ => (),
+            }
+        }
+    }
+
+    fn filenames(directory: &Directory) -> Vec<&str> {
+        directory
+            .content
+            .iter()
+            .filter(|file| !is_dot_or_dotdot(file))
+            .map(|file| file.filename.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_huge_directory_is_loaded_in_background_and_sorted_with_the_tab_key() {
+        let root = huge_directory();
+        let (mut directory, rx) = open(root.path());
+        assert!(directory.is_loading());
+        assert_eq!(directory.content.len(), FIRST_CHUNK_SIZE + 2);
+        let id = loading_id(&directory);
+        directory.extend_loaded(receive_loading(&rx, id));
+        let mut settings = settings();
+        settings.sort_kind.update_from_char('N');
+        directory.finish_loading(&settings);
+        assert!(!directory.is_loading());
+        let names = filenames(&directory);
+        assert_eq!(names.len(), BACKGROUND_LOADING_THRESHOLD + 10);
+        let mut descending = names.clone();
+        descending.sort_by(|a, b| b.cmp(a));
+        assert_eq!(names, descending);
+    }
+
+    #[test]
+    fn test_small_directory_is_read_at_once() {
+        let root = tempfile::tempdir().unwrap();
+        write(root.path().join("a"), "").unwrap();
+        let (directory, _rx) = open(root.path());
+        assert!(!directory.is_loading());
+        assert_eq!(filenames(&directory), vec!["a"]);
+    }
+
+    #[test]
+    fn test_reset_cancels_the_previous_loading() {
+        let root = huge_directory();
+        let (mut directory, _rx) = open(root.path());
+        let first = loading_id(&directory);
+        directory
+            .reset_files(&settings(), &Users::default())
+            .unwrap();
+        assert!(!directory.is_loaded_by(first));
+        assert!(directory.is_loaded_by(loading_id(&directory)));
+    }
+
+    #[test]
+    fn test_dropped_loading_cancels_its_thread() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        drop(Loading {
+            id: 0,
+            cancel: cancel.clone(),
+        });
+        assert!(cancel.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_cancelled_loading_sends_nothing() {
+        let root = huge_directory();
+        let entries = visible_entries(root.path(), false).unwrap();
+        let (tx, rx) = channel();
+        send_chunks(
+            0,
+            entries,
+            &Users::default(),
+            &FilterKind::All,
+            &AtomicBool::new(true),
+            &tx,
+        );
+        drop(tx);
+        assert!(rx.recv().is_err());
+    }
+
+    #[test]
+    fn test_file_reselected_before_it_is_read_is_selected_once_loaded() {
+        let root = huge_directory();
+        let (mut directory, rx) = open(root.path());
+        let missing = (0..BACKGROUND_LOADING_THRESHOLD + 10)
+            .map(|index| root.path().join(format!("file_{index:05}")))
+            .find(|path| directory.find_jump_index(path).is_none())
+            .unwrap();
+        directory.reselect_file(&missing);
+        let id = loading_id(&directory);
+        directory.extend_loaded(receive_loading(&rx, id));
+        directory.finish_loading(&settings());
+        assert_eq!(
+            directory.selected().unwrap().path.as_ref(),
+            missing.as_path()
+        );
+    }
+
+    #[test]
+    fn test_pending_selection_is_dropped_if_another_file_is_selected() {
+        let root = huge_directory();
+        let (mut directory, rx) = open(root.path());
+        let missing = (0..BACKGROUND_LOADING_THRESHOLD + 10)
+            .map(|index| root.path().join(format!("file_{index:05}")))
+            .find(|path| directory.find_jump_index(path).is_none())
+            .unwrap();
+        directory.reselect_file(&missing);
+        directory.select_index(3);
+        let selected = directory.selected().unwrap().path.clone();
+        let id = loading_id(&directory);
+        directory.extend_loaded(receive_loading(&rx, id));
+        directory.finish_loading(&settings());
+        assert_eq!(directory.selected().unwrap().path, selected);
     }
 }
@@ -6,10 +6,10 @@ mod nucleo_picker;
 mod tree;
 mod uber;
 
-pub use directory::{files_collection, human_size, Directory};
+pub use directory::{files_collection, human_size, Directory, GroupedRow};
 pub use preview::{
-    BinaryContent, ExtensionKind, HLContent, Line as BinLine, Preview, PreviewBuilder, TakeSkip,
-    TakeSkipEnum, Text, TextKind,
+    BinaryContent, DirectoryListing, ExtensionKind, HLContent, Line as BinLine, Preview,
+    PreviewBuilder, TakeSkip, TakeSkipEnum, Text, TextKind,
 };
 // pub use skim::{parse_line_output, print_ansiq_str, Skimer};
 pub use nucleo_picker::{highlighted_text, parse_line_output, Direction, FuzzyFinder, FuzzyKind};
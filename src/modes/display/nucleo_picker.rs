@@ -7,6 +7,7 @@ use std::{
 };
 
 use anyhow::Result;
+use ignore::WalkBuilder;
 use nucleo::{pattern, Config, Injector, Nucleo, Utf32String};
 use ratatui::{
     style::{Color, Modifier, Style},
@@ -14,7 +15,6 @@ use ratatui::{
 };
 use tokio::process::Command as TokioCommand;
 use unicode_segmentation::UnicodeSegmentation;
-use walkdir::WalkDir;
 
 use crate::modes::{extract_extension, ContentWindow, Icon, Input};
 use crate::{
@@ -38,11 +38,12 @@ pub enum Direction {
 
 /// What kind of content is beeing matched ?
 /// File: we match against paths,
-/// Line & Action we match against strings but actions differ.
+/// Line, Action & Command we match against strings but actions differ.
 pub enum FuzzyKind {
     File,
     Line,
     Action,
+    Command,
 }
 
 impl FuzzyKind {
@@ -52,10 +53,11 @@ impl FuzzyKind {
 }
 
 /// The fuzzy picker of file.
-/// it may be in one of 3 kinds:
+/// it may be in one of 4 kinds:
 /// - for file, it will match against paths from current folder,
 /// - for lines, it will match against any text of a text files from current folder,
 /// - for actions, it will match against any text from help, allowing to run an action when you forgot the keybind.
+/// - for commands, it will match against the command history, allowing to recall a previous command.
 ///
 /// Internally, it's just :
 /// - a [`Nucleo`] matcher,
@@ -71,6 +73,7 @@ pub struct FuzzyFinder<String: Sync + Send + 'static> {
     /// Line (match lines into text file),
     /// File (match file against their name),
     /// Action (match an action)
+    /// Command (match a command from history)
     pub kind: FuzzyKind,
     /// The fuzzy matcher
     pub matcher: Nucleo<String>,
@@ -120,6 +123,7 @@ where
             FuzzyKind::File => Self::default(),
             FuzzyKind::Line => Self::for_lines(),
             FuzzyKind::Action => Self::for_help(),
+            FuzzyKind::Command => Self::for_commands(),
         }
     }
 
@@ -145,6 +149,10 @@ where
         Self::build(Config::DEFAULT, FuzzyKind::Action)
     }
 
+    fn for_commands() -> Self {
+        Self::build(Config::DEFAULT, FuzzyKind::Command)
+    }
+
     /// Set the terminal height of the fuzzy picker.
     /// It should always be called after new
     pub fn set_height(mut self, height: usize) -> Self {
@@ -349,11 +357,21 @@ impl FuzzyFinder<String> {
         self.log();
     }
 
-    pub fn find_files(&self, current_path: PathBuf) {
+    /// Feed the finder with every file below `current_path`, hidden ones included.
+    /// If `respect_gitignore` is true, files ignored by git (`.gitignore`, global gitignore, `.git/info/exclude`)
+    /// and the `.git` folder itself are skipped. It only has an effect inside git repositories.
+    pub fn find_files(&self, current_path: PathBuf, respect_gitignore: bool) {
         let injector = self.injector();
         spawn(move || {
-            for entry in WalkDir::new(current_path)
-                .into_iter()
+            for entry in WalkBuilder::new(current_path)
+                .hidden(false)
+                .ignore(false)
+                .parents(respect_gitignore)
+                .git_ignore(respect_gitignore)
+                .git_global(respect_gitignore)
+                .git_exclude(respect_gitignore)
+                .filter_entry(move |entry| !respect_gitignore || entry.file_name() != ".git")
+                .build()
                 .filter_map(Result::ok)
             {
                 let value = entry.path().display().to_string();
@@ -1,31 +1,42 @@
 use std::cmp::min;
+use std::collections::HashMap;
 use std::convert::Into;
 use std::fmt::{Display, Write as _};
-use std::fs::symlink_metadata;
-use std::io::{BufRead, BufReader, Cursor, Read};
+use std::fs::{symlink_metadata, File};
+use std::io::{BufRead, BufReader, Read};
 use std::iter::{Enumerate, Skip, Take};
 use std::path::{Path, PathBuf};
 use std::slice::Iter;
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use content_inspector::{inspect, ContentType};
+use flate2::read::GzDecoder;
+use lofty::prelude::{Accessor, AudioFile, TaggedFileExt};
 use ratatui::style::{Color, Modifier, Style};
 use syntect::{
     easy::HighlightLines,
-    highlighting::{FontStyle, Style as SyntectStyle, Theme, ThemeSet},
+    highlighting::{FontStyle, Style as SyntectStyle},
     parsing::{SyntaxReference, SyntaxSet},
 };
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::common::{
-    clear_tmp_files, filename_from_path, is_in_path, path_to_string, BSDTAR, FFMPEG, FONTIMAGE,
-    ISOINFO, JUPYTER, LIBREOFFICE, LSBLK, MEDIAINFO, PANDOC, PDFINFO, PDFTOPPM, RSVG_CONVERT,
-    SEVENZ, SS, TRANSMISSION_SHOW, UDEVADM, UEBERZUG,
+    clear_tmp_files, filename_from_path, is_in_path, path_to_string, UtfWidth, BSDTAR, FFMPEG,
+    FONTIMAGE, ISOINFO, JUPYTER, LIBREOFFICE, LSBLK, MAN, MEDIAINFO, PANDOC, PDFINFO, PDFTOPPM,
+    RSVG_CONVERT, SEVENZ, SS, TRANSMISSION_SHOW, UDEVADM, UEBERZUG,
+};
+use crate::config::{
+    binary_bytes_per_row, max_preview_size_bytes, previewer_command, syntax_theme,
+};
+use crate::io::{
+    execute_and_capture_output_with_timeout, execute_and_capture_output_without_check,
 };
-use crate::config::MONOKAI_THEME;
-use crate::io::execute_and_capture_output_without_check;
 use crate::modes::{
-    extract_extension, list_files_tar, list_files_zip, ContentWindow, FileKind, FilterKind, TLine,
-    Tree, TreeBuilder, TreeLines, Ueber, UeberBuilder, Users,
+    extract_extension, files_collection, human_size, list_files_tar, list_files_zip,
+    list_paths_tar, read_gz_to_string, AnsiString, ContentWindow, FileInfo, FileKind, FilterKind,
+    SortKind, TLine, Tree, TreeBuilder, TreeLines, Ueber, UeberBuilder, Users,
 };
 
 /// Different kind of extension for grouped by previewers.
@@ -34,6 +45,7 @@ use crate::modes::{
 pub enum ExtensionKind {
     Archive,
     Audio,
+    Desktop,
     Epub,
     Font,
     Image,
@@ -43,6 +55,7 @@ pub enum ExtensionKind {
     Pdf,
     Sevenz,
     Svg,
+    Table,
     Torrent,
     Video,
 
@@ -61,7 +74,7 @@ impl ExtensionKind {
             => Self::Sevenz,
             "png" | "jpg" | "jpeg" | "tiff" | "heif" | "gif" | "cr2" | "nef" | "orf" | "sr2"
             => Self::Image,
-            "ogg" | "ogm" | "riff" | "mp2" | "mp3" | "wm" | "qt" | "ac3" | "dts" | "aac" | "mac" | "flac"
+            "ogg" | "ogm" | "riff" | "mp2" | "mp3" | "wm" | "qt" | "ac3" | "dts" | "aac" | "mac" | "flac" | "m4a" | "opus" | "wav"
             => Self::Audio,
             "mkv" | "webm" | "mpeg" | "mp4" | "avi" | "flv" | "mpg" | "wmv" | "m4v" | "mov"
             => Self::Video,
@@ -75,12 +88,16 @@ impl ExtensionKind {
             => Self::Iso,
             "ipynb"
             => Self::Notebook,
-            "doc" | "docx" | "odt" | "sxw" | "xlsx" | "xls" 
+            "doc" | "docx" | "odt" | "sxw" | "xlsx" | "xls" | "ods"
             => Self::Office,
             "epub"
             => Self::Epub,
             "torrent"
             => Self::Torrent,
+            "desktop"
+            => Self::Desktop,
+            "csv" | "tsv"
+            => Self::Table,
             _
             => Self::Default,
         }
@@ -141,6 +158,8 @@ impl std::fmt::Display for ExtensionKind {
             Self::Office    => write!(f, "office"),
             Self::Epub      => write!(f, "epub"),
             Self::Torrent   => write!(f, "torrent"),
+            Self::Table     => write!(f, "table"),
+            Self::Desktop   => write!(f, "desktop"),
             Self::Default   => write!(f, "default"),
         }
     }
@@ -156,6 +175,7 @@ pub enum Preview {
     Binary(BinaryContent),
     Ueberzug(Ueber),
     Tree(Tree),
+    Directory(DirectoryListing),
     #[default]
     Empty,
 }
@@ -171,6 +191,7 @@ impl Preview {
             Self::Binary(preview) => preview.len(),
             Self::Ueberzug(preview) => preview.len(),
             Self::Tree(tree) => tree.displayable().lines().len(),
+            Self::Directory(listing) => listing.len(),
         }
     }
 
@@ -179,9 +200,11 @@ impl Preview {
             Self::Empty => "empty",
             Self::Syntaxed(_) => "an highlighted text",
             Self::Text(text) => text.kind.for_first_line(),
+            Self::Binary(binary) if binary.truncated => "a binary file (truncated, file too large)",
             Self::Binary(_) => "a binary file",
             Self::Ueberzug(uber) => uber.kind.for_first_line(),
             Self::Tree(_) => "a tree",
+            Self::Directory(_) => "a directory",
         }
     }
 
@@ -195,6 +218,52 @@ impl Preview {
         ContentWindow::new(self.len(), height)
     }
 
+    /// Text of the lines from `top` to `bottom` (excluded), without colors nor ANSI codes.
+    /// Images and empty previews have no text.
+    pub fn text(&self, top: usize, bottom: usize) -> Option<String> {
+        let nb = bottom.saturating_sub(top);
+        let lines: Vec<String> = match self {
+            Self::Empty | Self::Ueberzug(_) => return None,
+            Self::Syntaxed(preview) => preview
+                .content
+                .iter()
+                .skip(top)
+                .take(nb)
+                .map(|line| line.iter().map(|token| token.content.as_str()).collect())
+                .collect(),
+            Self::Text(preview) => preview
+                .content
+                .iter()
+                .skip(top)
+                .take(nb)
+                .map(|line| AnsiString::parse(line).stripped().to_owned())
+                .collect(),
+            Self::Binary(preview) => preview
+                .content
+                .iter()
+                .skip(top)
+                .take(nb)
+                .map(|line| format!("{} {}", line.format_hex(), line.format_as_ascii()))
+                .collect(),
+            Self::Tree(tree) => tree
+                .displayable()
+                .lines()
+                .iter()
+                .skip(top)
+                .take(nb)
+                .map(|line| format!("{}{}", line.prefix(), line.filename(false)))
+                .collect(),
+            Self::Directory(listing) => listing
+                .content
+                .iter()
+                .skip(top)
+                .take(nb)
+                .map(|file| format!("{}  {}", file.size_column, file.filename))
+                .collect(),
+        };
+        Some(lines.join("\n"))
+    }
+
     pub fn filepath(&self) -> String {
         match self {
             Self::Empty => "".to_owned(),
@@ -203,6 +272,7 @@ impl Preview {
             Self::Binary(preview) => preview.path.to_string_lossy().to_string(),
             Self::Ueberzug(preview) => preview.identifier.to_owned(),
             Self::Tree(tree) => tree.root_path().to_string_lossy().to_string(),
+            Self::Directory(listing) => listing.path.to_string_lossy().to_string(),
         }
     }
 }
@@ -211,17 +281,40 @@ impl Preview {
 /// Using a builder is useful since there's many kind of preview which all use a different method.
 pub struct PreviewBuilder {
     path: PathBuf,
+    /// Directories are previewed with a listing instead of a tree.
+    directory_listing: bool,
+    /// The previewers of the config file are run, it's only done in the background previewer.
+    configured_previewers: bool,
 }
 
 impl PreviewBuilder {
     const CONTENT_INSPECTOR_MIN_SIZE: usize = 1024;
+    /// Configured previewers running longer than this are killed, the built-in preview is used instead.
+    const CONFIGURED_PREVIEWER_TIMEOUT: Duration = Duration::from_secs(3);
 
     pub fn new(path: &Path) -> Self {
         Self {
             path: path.to_owned(),
+            directory_listing: false,
+            configured_previewers: false,
         }
     }
 
+    /// Run the previewer configured for the extension of the file, if any.
+    /// Those are external commands which may be slow, they're only run in the background previewer
+    /// and killed after [`Self::CONFIGURED_PREVIEWER_TIMEOUT`].
+    pub fn with_configured_previewers(mut self) -> Self {
+        self.configured_previewers = true;
+        self
+    }
+
+    /// Preview directories with a lightweight listing, used by the second pane
+    /// which previews every selected file while browsing.
+    pub fn with_directory_listing(mut self) -> Self {
+        self.directory_listing = true;
+        self
+    }
+
     /// Empty preview, holding nothing.
     pub fn empty() -> Preview {
         clear_tmp_files();
@@ -248,13 +341,16 @@ impl PreviewBuilder {
         }
     }
 
-    /// Creates a new `Directory` from the self.file_info
-    /// It explores recursivelly the directory and creates a tree.
-    /// The recursive exploration is limited to depth 2.
+    /// Creates a listing of the directory, like `ls` would, if it's previewed in the second pane.
+    /// The directory isn't explored recursively, it's read only once.
+    /// Otherwise it explores recursivelly the directory and creates a tree.
+    /// The recursive exploration is limited to depth 4.
     fn directory(&self) -> Result<Preview> {
-        let users = Users::default();
+        if self.directory_listing {
+            return Ok(Preview::Directory(DirectoryListing::new(&self.path)));
+        }
         Ok(Preview::Tree(
-            TreeBuilder::new(std::sync::Arc::from(self.path.as_path()), &users)
+            TreeBuilder::new(Arc::from(self.path.as_path()), &Users::default())
                 .with_max_depth(4)
                 .with_hidden(false)
                 .with_filter_kind(&FilterKind::All)
@@ -262,17 +358,44 @@ impl PreviewBuilder {
         ))
     }
 
+    /// Entries of a zip or tar archive, displayed as a tree whose folders can be folded.
+    /// If the entries can't be read, the archive is previewed as text.
+    fn archive(&self, ext: &str) -> Result<Preview> {
+        let entries = match ext {
+            "zip" => list_files_zip(&self.path),
+            _ => list_paths_tar(&self.path),
+        };
+        match entries {
+            Ok(entries) if !entries.is_empty() => Ok(Preview::Tree(
+                TreeBuilder::new(Arc::from(self.path.as_path()), &Users::default())
+                    .build_from_entries(&entries),
+            )),
+            _ => Ok(Preview::Text(Text::archive(&self.path, ext)?)),
+        }
+    }
+
     fn valid_symlink(&self) -> Result<Preview> {
-        Self::new(&std::fs::read_link(&self.path).unwrap_or_default()).build()
+        Self {
+            path: std::fs::read_link(&self.path).unwrap_or_default(),
+            directory_listing: self.directory_listing,
+            configured_previewers: self.configured_previewers,
+        }
+        .build()
     }
 
     fn normal_file(&self) -> Result<Preview> {
         let extension = extract_extension(&self.path).to_lowercase();
+        if let Some(preview) = self.configured_previewer(&extension) {
+            return Ok(preview);
+        }
+        if is_man_page(&self.path) {
+            if let Some(man_page) = Text::man_page(&self.path) {
+                return Ok(Preview::Text(man_page));
+            }
+        }
         let kind = ExtensionKind::matcher(&extension);
         match kind {
-            ExtensionKind::Archive if kind.has_programs() => {
-                Ok(Preview::Text(Text::archive(&self.path, &extension)?))
-            }
+            ExtensionKind::Archive if kind.has_programs() => self.archive(&extension),
             ExtensionKind::Sevenz if kind.has_programs() => {
                 Ok(Preview::Text(Text::sevenz(&self.path)?))
             }
@@ -286,8 +409,24 @@ impl PreviewBuilder {
             ExtensionKind::Notebook if kind.has_programs() => {
                 Ok(Self::notebook(&self.path).context("Preview: Couldn't parse notebook")?)
             }
-            ExtensionKind::Audio if kind.has_programs() => {
-                Ok(Preview::Text(Text::media_content(&self.path)?))
+            ExtensionKind::Audio => match Text::audio_tags(&self.path) {
+                Some(tags) => Ok(Preview::Text(tags)),
+                None if kind.has_programs() => Ok(Preview::Text(Text::media_content(&self.path)?)),
+                None => self.text_or_binary(),
+            },
+            ExtensionKind::Table => match Text::table(&self.path, &extension) {
+                Some(table) => Ok(Preview::Text(table)),
+                None => self.text_or_binary(),
+            },
+            ExtensionKind::Desktop => match Text::desktop_entry(&self.path) {
+                Some(entry) => Ok(Preview::Text(entry)),
+                None => self.text_or_binary(),
+            },
+            ExtensionKind::Office if !kind.has_programs() || !is_in_path(UEBERZUG) => {
+                match Text::office(&self.path, &extension) {
+                    Some(text) => Ok(Preview::Text(text)),
+                    None => self.text_or_binary(),
+                }
             }
             _ if kind.is_ueber_kind() && kind.has_programs() => Self::ueber(&self.path, kind),
             _ => match self.syntaxed(&extension) {
@@ -297,6 +436,31 @@ impl PreviewBuilder {
         }
     }
 
+    /// Output of the previewer configured for this extension, displayed with its colors.
+    /// None if there's no previewer, if it fails, times out or outputs nothing, the built-in preview is used then.
+    fn configured_previewer(&self, extension: &str) -> Option<Preview> {
+        if !self.configured_previewers {
+            return None;
+        }
+        let (executable, args) = previewer_command(extension)?.split_first()?;
+        let path_str = self.path.to_str()?;
+        let mut args: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
+        args.push(path_str);
+        let output = execute_and_capture_output_with_timeout(
+            executable,
+            &args,
+            Self::CONFIGURED_PREVIEWER_TIMEOUT,
+        )
+        .ok()?;
+        if output.is_empty() {
+            return None;
+        }
+        Some(Preview::Text(Text::command_stdout(
+            &output,
+            format!("{executable} {path_str}"),
+        )))
+    }
+
     fn ueber(path: &Path, kind: ExtensionKind) -> Result<Preview> {
         let preview = UeberBuilder::new(path, kind.into()).build()?;
         if preview.is_empty() {
@@ -386,11 +550,235 @@ impl PreviewBuilder {
         crate::log_info!("cli_info. command {command} - output\n{output}");
         Preview::Text(Text::command_stdout(output, command))
     }
+
+    /// Empty preview of a streamed command. Lines are appended while the command runs.
+    pub fn streamed_command(command: String) -> Preview {
+        Preview::Text(Text::command_stream(command))
+    }
+
+    /// Every information about a file, displayed in full screen.
+    pub fn properties(path: &Path, lines: Vec<String>) -> Preview {
+        Preview::Text(Text::properties(path, lines))
+    }
+
+    /// Disk usage breakdown of a directory, waiting for the sizes of its children.
+    pub fn disk_usage(path: &Path, total: usize) -> Preview {
+        Preview::Text(Text::disk_usage(path, total))
+    }
+}
+
+/// True if the file looks like a man page : its extension is a section number like `ls.1` or `printf.3p`,
+/// optionally compressed with gzip like `ls.1.gz`.
+/// It must be in a `manN` directory like `/usr/share/man/man1` or start with a roff header `.TH`.
+fn is_man_page(path: &Path) -> bool {
+    let Some(filename) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let (filename, is_compressed) = match filename.strip_suffix(".gz") {
+        Some(stripped) => (stripped, true),
+        None => (filename, false),
+    };
+    let has_section = filename
+        .rsplit_once('.')
+        .is_some_and(|(_, section)| section.starts_with(|c: char| c.is_ascii_digit()));
+    if !has_section {
+        return false;
+    }
+    let in_man_directory = path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_prefix("man"))
+        .is_some_and(|section| section.starts_with(|c: char| c.is_ascii_digit()));
+    in_man_directory || has_roff_header(path, is_compressed)
+}
+
+/// True if one of the first lines of the file is a roff title header `.TH`.
+/// Comments (`.\"`) and other requests may precede it.
+fn has_roff_header(path: &Path, is_compressed: bool) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    let reader: Box<dyn Read> = if is_compressed {
+        Box::new(GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+    BufReader::new(reader.take(8192))
+        .lines()
+        .take(20)
+        .map_while(Result::ok)
+        .any(|line| line.starts_with(".TH "))
+}
+
+/// Remove the overstrike sequences (`c\x08c` for bold, `_\x08c` for underline) used by groff.
+fn strip_overstrike(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    for c in line.chars() {
+        if c == '\x08' {
+            stripped.pop();
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+/// Parse the rows of a CSV like content, separated by `separator`.
+/// Fields may be quoted, a quote is escaped by another quote and quoted fields may span multiple lines.
+/// At most `max_rows` rows are read, the boolean is true if some rows were left.
+/// Returns `None` if a line can't be read, if a quote is never closed or if there's no row.
+fn parse_table(
+    reader: impl BufRead,
+    separator: char,
+    max_rows: usize,
+) -> Option<(Vec<Vec<String>>, bool)> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    for line in reader.lines() {
+        let line = line.ok()?;
+        let line = line.trim_end_matches('\r');
+        if in_quotes {
+            field.push(' ');
+        } else if rows.len() >= max_rows {
+            return Some((rows, true));
+        } else if line.is_empty() {
+            continue;
+        }
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if in_quotes && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' if in_quotes => in_quotes = false,
+                '"' if field.is_empty() => in_quotes = true,
+                c if c == separator && !in_quotes => row.push(std::mem::take(&mut field)),
+                c => field.push(c),
+            }
+        }
+        if !in_quotes {
+            row.push(std::mem::take(&mut field));
+            rows.push(std::mem::take(&mut row));
+        }
+    }
+    if in_quotes || rows.is_empty() {
+        return None;
+    }
+    Some((rows, false))
+}
+
+/// Align the columns of the rows, separated by `│`. The first row is the header, underlined by `─`.
+/// Fields wider than `max_width` are truncated.
+fn format_table(rows: &[Vec<String>], max_width: usize) -> Vec<String> {
+    let nb_columns = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+    let mut widths = vec![0; nb_columns];
+    for row in rows {
+        for (width, field) in widths.iter_mut().zip(row) {
+            *width = (*width).max(field.as_str().utf_width().min(max_width));
+        }
+    }
+    let mut lines: Vec<String> = rows
+        .iter()
+        .map(|row| format_table_row(row, &widths))
+        .collect();
+    let header_line = widths
+        .iter()
+        .map(|width| "─".repeat(*width))
+        .collect::<Vec<_>>()
+        .join("─┼─");
+    lines.insert(1, header_line);
+    lines
+}
+
+fn format_table_row(row: &[String], widths: &[usize]) -> String {
+    let mut line = String::new();
+    for (index, width) in widths.iter().enumerate() {
+        if index > 0 {
+            line.push_str(" │ ");
+        }
+        let field = row
+            .get(index)
+            .map(|field| field.as_str())
+            .unwrap_or_default();
+        let mut field_width = field.utf_width();
+        if field_width > *width {
+            line.extend(field.graphemes(true).take(width.saturating_sub(1)));
+            line.push('…');
+            field_width = *width;
+        } else {
+            line.push_str(field);
+        }
+        line.push_str(&" ".repeat(width - field_width));
+    }
+    line.trim_end().to_owned()
+}
+
+/// Parse the keys of the `[Desktop Entry]` group of a `.desktop` file, other groups are ignored.
+/// Returns `None` if a line can't be read or isn't a comment, a group header or a `key=value` pair,
+/// or if the group has no `Name`.
+fn parse_desktop_entry(reader: impl BufRead) -> Option<HashMap<String, String>> {
+    let mut entry = HashMap::new();
+    let mut in_desktop_entry = false;
+    let mut has_desktop_entry = false;
+    for line in reader.lines() {
+        let line = line.ok()?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(group) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_desktop_entry = group == "Desktop Entry";
+            has_desktop_entry |= in_desktop_entry;
+            continue;
+        }
+        let (key, value) = line.split_once('=')?;
+        if in_desktop_entry {
+            entry.insert(key.trim().to_owned(), value.trim().to_owned());
+        }
+    }
+    if !has_desktop_entry || !entry.contains_key("Name") {
+        return None;
+    }
+    Some(entry)
+}
+
+/// Suffixes of the localized keys of a desktop entry matching the current locale, most precise first.
+/// `fr_FR.UTF-8@euro` gives `fr_FR@euro`, `fr_FR`, `fr@euro` and `fr`.
+fn desktop_entry_locales() -> Vec<String> {
+    let Some(locale) = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|locale| !locale.is_empty())
+    else {
+        return vec![];
+    };
+    let (locale, modifier) = match locale.split_once('@') {
+        Some((locale, modifier)) => (locale.to_owned(), Some(modifier.to_owned())),
+        None => (locale, None),
+    };
+    let locale = locale.split('.').next().unwrap_or_default();
+    let lang = locale.split('_').next().unwrap_or_default();
+    let mut candidates = vec![];
+    for base in [locale, lang] {
+        if let Some(modifier) = &modifier {
+            candidates.push(format!("{base}@{modifier}"));
+        }
+        candidates.push(base.to_owned());
+    }
+    candidates.dedup();
+    candidates
+        .retain(|candidate| !candidate.is_empty() && candidate != "C" && candidate != "POSIX");
+    candidates
 }
 
 /// Read a number of lines from a text file. Returns a vector of strings.
+/// Read at most `size_limit` lines and [`max_preview_size_bytes`] bytes.
 fn read_nb_lines(path: &Path, size_limit: usize) -> Result<Vec<String>> {
-    let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+    let reader = std::io::BufReader::new(std::fs::File::open(path)?.take(max_preview_size_bytes()));
     Ok(reader
         .lines()
         .take(size_limit)
@@ -408,14 +796,22 @@ pub enum TextKind {
     Archive,
     Blockdevice,
     CommandStdout,
+    AudioTags,
+    CommandStream,
+    DesktopEntry,
+    DiskUsage,
     Epub,
     FifoChardevice,
     Help,
     Iso,
     Log,
+    Manpage,
     Mediacontent,
+    Office,
+    Properties,
     Sevenz,
     Socket,
+    Table,
     Torrent,
 }
 
@@ -427,14 +823,22 @@ impl TextKind {
             Self::Archive => "an archive",
             Self::Blockdevice => "a Blockdevice file",
             Self::CommandStdout => "a command stdout",
+            Self::AudioTags => "audio tags",
+            Self::CommandStream => "a streamed command stdout",
+            Self::DesktopEntry => "a desktop entry",
+            Self::DiskUsage => "a disk usage breakdown",
             Self::Epub => "an epub",
             Self::FifoChardevice => "a Fifo or Chardevice file",
             Self::Help => "Help",
             Self::Iso => "Iso",
             Self::Log => "Log",
+            Self::Manpage => "a man page",
             Self::Mediacontent => "a media content",
+            Self::Office => "an office document",
+            Self::Properties => "the properties of a file",
             Self::Sevenz => "a 7z archive",
             Self::Socket => "a Socket file",
+            Self::Table => "a table",
             Self::Torrent => "a torrent",
         }
     }
@@ -446,6 +850,40 @@ impl Display for TextKind {
     }
 }
 
+/// Holds a sorted listing of the files of a directory.
+/// Only the direct children are read, which is fast even for huge directories.
+#[derive(Default)]
+pub struct DirectoryListing {
+    pub path: PathBuf,
+    content: Vec<FileInfo>,
+}
+
+impl DirectoryListing {
+    fn new(path: &Path) -> Self {
+        let users = Users::default();
+        let mut content =
+            files_collection(path, &users, false, &FilterKind::All, true).unwrap_or_default();
+        SortKind::default().sort(&mut content);
+        Self {
+            path: path.to_owned(),
+            content,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.content.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
+    /// Path of the file at this index, if any.
+    pub fn path_from_index(&self, index: usize) -> Option<&Path> {
+        Some(&self.content.get(index)?.path)
+    }
+}
+
 /// Holds a preview of a text content.
 /// It's a vector of strings (per line)
 #[derive(Clone, Default, Debug)]
@@ -459,6 +897,9 @@ pub struct Text {
 impl Text {
     /// Only files with less than 1MiB will be read
     const SIZE_LIMIT: usize = 1 << 20;
+    const TRUNCATED_NOTICE: &'static str = "(truncated, file too large)";
+    const TABLE_MAX_ROWS: usize = 1000;
+    const TABLE_MAX_COLUMN_WIDTH: usize = 40;
 
     fn help(help: &str) -> Self {
         let content: Vec<String> = help.lines().map(|line| line.to_owned()).collect();
@@ -495,8 +936,12 @@ impl Text {
         })
     }
 
+    /// Lines of a text file. If it's larger than [`max_preview_size_bytes`], a notice is added after its first bytes.
     fn from_file(path: &Path) -> Result<Self> {
-        let content = read_nb_lines(path, Self::SIZE_LIMIT)?;
+        let mut content = read_nb_lines(path, Self::SIZE_LIMIT)?;
+        if path.metadata()?.len() > max_preview_size_bytes() {
+            content.push(Self::TRUNCATED_NOTICE.to_owned());
+        }
         Ok(Self {
             title: filename_from_path(path).context("")?.to_owned(),
             kind: TextKind::TEXTFILE,
@@ -518,6 +963,29 @@ impl Text {
         })
     }
 
+    /// Man page formated with `man -l`.
+    /// If `man` isn't installed, the roff source is displayed, decompressed if needed.
+    fn man_page(path: &Path) -> Option<Self> {
+        let path_str = path.to_str()?;
+        let output = if is_in_path(MAN) {
+            execute_and_capture_output_without_check(MAN, &["-l", path_str]).ok()?
+        } else if path_str.ends_with(".gz") {
+            read_gz_to_string(path).ok()?
+        } else {
+            std::fs::read_to_string(path).ok()?
+        };
+        if output.is_empty() {
+            return None;
+        }
+        let content: Vec<String> = output.lines().map(strip_overstrike).collect();
+        Some(Self {
+            title: filename_from_path(path).unwrap_or_default().to_owned(),
+            kind: TextKind::Manpage,
+            length: content.len(),
+            content,
+        })
+    }
+
     fn media_content(path: &Path) -> Result<Self> {
         Self::from_command_output(
             TextKind::Mediacontent,
@@ -558,6 +1026,184 @@ impl Text {
         )
     }
 
+    /// Text extracted from an office document, used when it can't be displayed as images.
+    /// Spreadsheets are converted to CSV by libreoffice, only the first sheet is kept.
+    /// Documents are converted to plain text by pandoc if it can read them, by libreoffice otherwise.
+    /// Returns `None` if no converter is installed or the conversion failed.
+    fn office(path: &Path, extension: &str) -> Option<Self> {
+        let path_str = path.to_str()?;
+        let output = match extension {
+            "xlsx" | "xls" | "ods" if is_in_path(LIBREOFFICE) => {
+                Self::office_spreadsheet_to_csv(path, path_str)?
+            }
+            "docx" | "odt" if is_in_path(PANDOC) => {
+                execute_and_capture_output_without_check(PANDOC, &["-t", "plain", "--", path_str])
+                    .ok()?
+            }
+            "doc" | "docx" | "odt" | "sxw" if is_in_path(LIBREOFFICE) => {
+                execute_and_capture_output_without_check(
+                    LIBREOFFICE,
+                    &["--headless", "--cat", path_str],
+                )
+                .ok()?
+            }
+            _ => return None,
+        };
+        let content: Vec<String> = output.lines().map(|line| line.to_owned()).collect();
+        Some(Self {
+            title: filename_from_path(path).ok()?.to_owned(),
+            kind: TextKind::Office,
+            length: content.len(),
+            content,
+        })
+    }
+
+    /// Tags and properties of an audio file read with `lofty`: title, artist, album, duration, bitrate...
+    /// Missing tags are skipped.
+    /// Returns `None` if the file can't be parsed, it's then previewed with mediainfo or as binary.
+    fn audio_tags(path: &Path) -> Option<Self> {
+        let tagged_file = lofty::read_from_path(path).ok()?;
+        let properties = tagged_file.properties();
+        let mut content = vec![];
+        if let Some(tag) = tagged_file
+            .primary_tag()
+            .or_else(|| tagged_file.first_tag())
+        {
+            let tags = [
+                ("Title", tag.title().map(|title| title.into_owned())),
+                ("Artist", tag.artist().map(|artist| artist.into_owned())),
+                ("Album", tag.album().map(|album| album.into_owned())),
+                ("Genre", tag.genre().map(|genre| genre.into_owned())),
+                ("Track", tag.track().map(|track| track.to_string())),
+                ("Date", tag.date().map(|date| date.to_string())),
+            ];
+            for (name, value) in tags {
+                if let Some(value) = value {
+                    content.push(format!("{name:<12} {value}"));
+                }
+            }
+            content.push(String::new());
+        }
+        let duration = properties.duration().as_secs();
+        content.push(format!(
+            "{name:<12} {minutes}:{seconds:02}",
+            name = "Duration",
+            minutes = duration / 60,
+            seconds = duration % 60
+        ));
+        let properties = [
+            (
+                "Bitrate",
+                properties
+                    .audio_bitrate()
+                    .map(|bitrate| format!("{bitrate} kbps")),
+            ),
+            (
+                "Sample rate",
+                properties.sample_rate().map(|rate| format!("{rate} Hz")),
+            ),
+            (
+                "Bit depth",
+                properties.bit_depth().map(|depth| format!("{depth} bits")),
+            ),
+            (
+                "Channels",
+                properties.channels().map(|channels| channels.to_string()),
+            ),
+        ];
+        for (name, value) in properties {
+            if let Some(value) = value {
+                content.push(format!("{name:<12} {value}"));
+            }
+        }
+        Some(Self {
+            title: filename_from_path(path).ok()?.to_owned(),
+            kind: TextKind::AudioTags,
+            length: content.len(),
+            content,
+        })
+    }
+
+    /// CSV or TSV file displayed as a table whose columns are aligned, the first row being the header.
+    /// Only the first rows are read and very wide columns are truncated.
+    /// Returns `None` if the file can't be parsed, it's then previewed as text.
+    fn table(path: &Path, extension: &str) -> Option<Self> {
+        let separator = if extension == "tsv" { '\t' } else { ',' };
+        let file = std::fs::File::open(path).ok()?;
+        let reader = BufReader::new(file.take(max_preview_size_bytes()));
+        let (rows, is_truncated) = parse_table(reader, separator, Self::TABLE_MAX_ROWS)?;
+        let mut content = format_table(&rows, Self::TABLE_MAX_COLUMN_WIDTH);
+        if is_truncated {
+            content.push(format!("(first {nb} rows)", nb = Self::TABLE_MAX_ROWS));
+        }
+        Some(Self {
+            title: filename_from_path(path).ok()?.to_owned(),
+            kind: TextKind::Table,
+            length: content.len(),
+            content,
+        })
+    }
+
+    /// Convert the first sheet of a spreadsheet to a CSV file in a new temporary directory and read it.
+    /// The directory is removed once the file is read, existing files are never touched.
+    fn office_spreadsheet_to_csv(path: &Path, path_str: &str) -> Option<String> {
+        let tmp_dir = tempfile::tempdir().ok()?;
+        let outdir = tmp_dir.path().to_str()?;
+        execute_and_capture_output_without_check(
+            LIBREOFFICE,
+            &[
+                "--headless",
+                "--convert-to",
+                "csv",
+                "--outdir",
+                outdir,
+                path_str,
+            ],
+        )
+        .ok()?;
+        let csv = tmp_dir.path().join(path.file_name()?).with_extension("csv");
+        std::fs::read_to_string(csv).ok()
+    }
+
+    /// Name, comment, command, icon and categories of a `.desktop` file.
+    /// The name and the comment are translated for the current locale if possible.
+    /// Returns `None` if the file isn't a valid desktop entry.
+    fn desktop_entry(path: &Path) -> Option<Self> {
+        let file = std::fs::File::open(path).ok()?;
+        let entry = parse_desktop_entry(BufReader::new(file.take(max_preview_size_bytes())))?;
+        let locales = desktop_entry_locales();
+        let localized = |key: &str| {
+            locales
+                .iter()
+                .find_map(|locale| entry.get(&format!("{key}[{locale}]")))
+                .or_else(|| entry.get(key))
+        };
+        let categories = entry.get("Categories").map(|categories| {
+            categories
+                .split(';')
+                .filter(|category| !category.is_empty())
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+        let fields = [
+            ("Name", localized("Name").cloned()),
+            ("Comment", localized("Comment").cloned()),
+            ("Exec", entry.get("Exec").cloned()),
+            ("Icon", entry.get("Icon").cloned()),
+            ("Categories", categories),
+        ];
+        let content: Vec<String> = fields
+            .into_iter()
+            .filter_map(|(label, value)| Some(format!("{label:<12}{value}", value = value?)))
+            .collect();
+        Some(Self {
+            title: filename_from_path(path).ok()?.to_owned(),
+            kind: TextKind::DesktopEntry,
+            length: content.len(),
+            content,
+        })
+    }
+
     fn torrent(path: &Path) -> Result<Self> {
         Self::from_command_output(
             TextKind::Torrent,
@@ -620,11 +1266,107 @@ impl Text {
         }
     }
 
+    fn command_stream(title: String) -> Self {
+        Self {
+            title,
+            kind: TextKind::CommandStream,
+            content: vec![],
+            length: 0,
+        }
+    }
+
+    /// Append a line at the end of the content.
+    pub fn push_line(&mut self, line: String) {
+        self.content.push(line);
+        self.length += 1;
+    }
+
+    fn properties(path: &Path, content: Vec<String>) -> Self {
+        Self {
+            title: format!("Properties of {path}", path = path.display()),
+            kind: TextKind::Properties,
+            length: content.len(),
+            content,
+        }
+    }
+
+    fn disk_usage(path: &Path, total: usize) -> Self {
+        let content = vec![format!("Computing the size of {total} entries...")];
+        Self {
+            title: disk_usage_title(path),
+            kind: TextKind::DiskUsage,
+            content,
+            length: 1,
+        }
+    }
+
+    /// True if it's the disk usage breakdown of this directory.
+    pub fn is_disk_usage_of(&self, path: &Path) -> bool {
+        matches!(self.kind, TextKind::DiskUsage) && self.title == disk_usage_title(path)
+    }
+
+    /// Display the progress of the disk usage computation: `done` children out of `total`
+    /// and the one whose size is currently computed.
+    pub fn set_disk_usage_progress(&mut self, done: usize, total: usize, current: &str) {
+        self.content = vec![
+            format!("Computing the size of {total} entries... {done} / {total}"),
+            format!("Walking {current}"),
+        ];
+        self.length = self.content.len();
+    }
+
+    /// Replace the content by the sizes of the children, sorted by descending size.
+    /// Each line holds the size, a bar proportional to the largest child, the percentage of the total and the name.
+    pub fn set_disk_usage(&mut self, mut sizes: Vec<(PathBuf, u64)>) {
+        sizes.sort_by(|(_, a), (_, b)| b.cmp(a));
+        let total: u64 = sizes.iter().map(|(_, size)| size).sum();
+        let largest = sizes.first().map(|(_, size)| *size).unwrap_or_default();
+        let mut content = vec![
+            format!(
+                "Total: {total}  in {len} entries",
+                total = human_size(total).trim(),
+                len = sizes.len()
+            ),
+            String::new(),
+        ];
+        content.extend(sizes.iter().map(|(path, size)| {
+            let bar_len = if largest == 0 {
+                0
+            } else {
+                (*size as f64 / largest as f64 * DISK_USAGE_BAR_WIDTH as f64).round() as usize
+            };
+            let percent = if total == 0 {
+                0.0
+            } else {
+                *size as f64 / total as f64 * 100.0
+            };
+            let mut name = filename_from_path(path).unwrap_or_default().to_owned();
+            if path.is_dir() {
+                name.push('/');
+            }
+            format!(
+                "{size:>6}  {bar:<width$}  {percent:>5.1}%  {name}",
+                size = human_size(*size).trim(),
+                bar = "█".repeat(bar_len),
+                width = DISK_USAGE_BAR_WIDTH,
+            )
+        }));
+        self.length = content.len();
+        self.content = content;
+    }
+
     fn len(&self) -> usize {
         self.length
     }
 }
 
+/// Width of the bar of the largest child in a disk usage breakdown.
+const DISK_USAGE_BAR_WIDTH: usize = 30;
+
+fn disk_usage_title(path: &Path) -> String {
+    format!("Disk usage of {path}", path = path.display())
+}
+
 /// Holds a preview of a code text file whose language is supported by `Syntect`.
 /// The file is colored propery and line numbers are shown.
 #[derive(Clone, Default)]
@@ -641,7 +1383,7 @@ impl HLContent {
     /// Creates a new displayable content of a syntect supported file.
     /// It may fail if the file isn't properly formatted or the extension
     /// is wrong (ie. python content with .c extension).
-    /// ATM only Monokaï (dark) theme is supported.
+    /// The theme is configurable, see `syntax_theme`.
     fn new(path: &Path, syntax_set: SyntaxSet, syntax_ref: &SyntaxReference) -> Result<Self> {
         let raw_content = read_nb_lines(path, Self::SIZE_LIMIT)?;
         Self::build(
@@ -688,23 +1430,13 @@ impl HLContent {
         &self.path
     }
 
-    fn get_or_init_monokai() -> &'static Theme {
-        MONOKAI_THEME.get_or_init(|| {
-            let mut monokai = BufReader::new(Cursor::new(include_bytes!(
-                "../../../assets/themes/Monokai_Extended.tmTheme"
-            )));
-            ThemeSet::load_from_reader(&mut monokai).expect("Couldn't find monokai theme")
-        })
-    }
-
     fn parse_raw_content(
         raw_content: Vec<String>,
         syntax_set: SyntaxSet,
         syntax_ref: &SyntaxReference,
     ) -> Result<Vec<Vec<SyntaxedString>>> {
         let mut highlighted_content = vec![];
-        let monokai = Self::get_or_init_monokai();
-        let mut highlighter = HighlightLines::new(syntax_ref, monokai);
+        let mut highlighter = HighlightLines::new(syntax_ref, syntax_theme());
 
         for line in raw_content.iter() {
             let mut v_line = vec![];
@@ -765,54 +1497,78 @@ impl SyntaxedString {
 
 /// Holds a preview of a binary content.
 /// It doesn't try to respect endianness.
-/// The lines are formatted to display 16 bytes.
-/// The number of lines is truncated to $2^20 = 1048576$.
+/// The lines are formatted to display `bytes_per_row` bytes, 16 by default.
+/// The content is truncated to [`max_preview_size_bytes`], 16 MiB by default.
 #[derive(Clone, Default)]
 pub struct BinaryContent {
     pub path: PathBuf,
+    size: u64,
+    truncated: bool,
     length: u64,
+    bytes_per_row: usize,
     content: Vec<Line>,
 }
 
 impl BinaryContent {
-    const LINE_WIDTH: usize = 16;
-    const SIZE_LIMIT: usize = 1048576;
+    /// Allowed number of bytes displayed per line.
+    pub const BYTES_PER_ROW: [usize; 3] = [8, 16, 32];
 
     fn new(path: &Path) -> Result<Self> {
         let Ok(metadata) = path.metadata() else {
             return Ok(Self::default());
         };
-        let length = metadata.len() / Self::LINE_WIDTH as u64;
-        let content = Self::read_content(path)?;
-
-        Ok(Self {
+        let bytes = Self::read_bytes(path)?;
+        let mut binary = Self {
             path: path.to_path_buf(),
-            length,
-            content,
-        })
+            size: min(metadata.len(), bytes.len() as u64),
+            truncated: metadata.len() > bytes.len() as u64,
+            ..Default::default()
+        };
+        binary.layout(&bytes, binary_bytes_per_row());
+        Ok(binary)
     }
 
-    fn read_content(path: &Path) -> Result<Vec<Line>> {
-        let mut reader = BufReader::new(std::fs::File::open(path)?);
-        let mut buffer = [0; Self::LINE_WIDTH];
-        let mut content = vec![];
-        while let Ok(nb_bytes_read) = reader.read(&mut buffer[..]) {
-            if nb_bytes_read != Self::LINE_WIDTH {
-                content.push(Line::new((&buffer[0..nb_bytes_read]).into()));
-                break;
-            }
-            content.push(Line::new(buffer.into()));
-            if content.len() >= Self::SIZE_LIMIT {
-                break;
-            }
-        }
-        Ok(content)
+    fn read_bytes(path: &Path) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+        BufReader::new(std::fs::File::open(path)?)
+            .take(max_preview_size_bytes())
+            .read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Split the bytes in lines of `bytes_per_row` bytes.
+    fn layout(&mut self, bytes: &[u8], bytes_per_row: usize) {
+        self.bytes_per_row = bytes_per_row;
+        self.length = self.size / bytes_per_row as u64;
+        self.content = bytes
+            .chunks(bytes_per_row)
+            .map(|chunk| Line::new(chunk.to_vec()))
+            .collect();
+    }
+
+    /// Display the next allowed number of bytes per line, cycling through [`Self::BYTES_PER_ROW`].
+    pub fn cycle_bytes_per_row(&mut self) {
+        let index = Self::BYTES_PER_ROW
+            .iter()
+            .position(|&width| width == self.bytes_per_row)
+            .unwrap_or_default();
+        let next = Self::BYTES_PER_ROW[(index + 1) % Self::BYTES_PER_ROW.len()];
+        let bytes: Vec<u8> = self
+            .content
+            .iter()
+            .flat_map(|line| line.line.iter().copied())
+            .collect();
+        self.layout(&bytes, next);
+    }
+
+    pub fn bytes_per_row(&self) -> usize {
+        self.bytes_per_row
     }
 
     /// WATCHOUT !
     /// Doesn't return the size of the file, like similar methods in other variants.
     /// It returns the number of **lines**.
-    /// It's the size of the file divided by `BinaryContent::LINE_WIDTH` which is 16.
+    /// It's the size of the file divided by the number of bytes per line.
     pub fn len(&self) -> usize {
         self.length as usize
     }
@@ -822,7 +1578,7 @@ impl BinaryContent {
     }
 
     pub fn number_width_hex(&self) -> usize {
-        format!("{:x}", self.len() * 16).len()
+        format!("{:x}", self.len() * self.bytes_per_row).len()
     }
 }
 
@@ -838,7 +1594,7 @@ impl Line {
         Self { line }
     }
 
-    /// Format a line of 16 bytes as BigEndian, separated by spaces.
+    /// Format a line of bytes as BigEndian, separated by spaces.
     /// Every byte is zero filled if necessary.
     pub fn format_hex(&self) -> String {
         let mut hex_repr = String::new();
@@ -862,7 +1618,7 @@ impl Line {
         }
     }
 
-    /// Format a line of 16 bytes as an ASCII string.
+    /// Format a line of bytes as an ASCII string.
     /// Non ASCII printable bytes are replaced by dots.
     pub fn format_as_ascii(&self) -> String {
         self.line.iter().map(Self::byte_to_char).collect()
@@ -937,3 +1693,4 @@ impl_take_skip!(HLContent, VecSyntaxedString);
 impl_take_skip!(Text, String);
 impl_take_skip!(BinaryContent, Line);
 impl_take_skip!(TreeLines, TLine);
+impl_take_skip!(DirectoryListing, FileInfo);
@@ -1,16 +1,19 @@
 use std::borrow::Borrow;
 use std::cmp::min;
-use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use ratatui::style::{Modifier, Style};
 
 use crate::common::{filename_from_path, has_last_modification_happened_less_than};
+use crate::config::{extension_color, tree_connectors, tree_indent, FILE_STYLES};
 use crate::impl_index_to_index;
+use crate::io::color_to_style;
 use crate::modes::{
-    files_collection, ContentWindow, FileInfo, FilterKind, Flagged, Icon, SortKind, ToPath, Users,
+    extract_extension, files_collection, ContentWindow, FileInfo, FilterKind, Flagged, Icon,
+    SortKind, ToPath, Users,
 };
 
 /// An element of a tree.
@@ -191,13 +194,32 @@ impl<'a> TreeBuilder<'a> {
             self.filter_kind,
         )
         .build();
-        let displayable_lines = TreeLinesBuilder::new(&nodes, &self.root_path, self.users).build();
+        let displayable_lines =
+            TreeLinesBuilder::new(&nodes, &self.root_path, self.users, false).build();
 
         Tree {
             selected: self.root_path.clone(),
             root_path: self.root_path,
             nodes,
             displayable_lines,
+            in_memory: false,
+        }
+    }
+
+    /// Build a tree from paths held in memory instead of the filesystem, like the entries of an archive.
+    /// Entries are relative to the root path and folders end with a `/`.
+    /// Folders are listed first, then files, both sorted by name. Depth, filter and hidden settings are ignored.
+    pub fn build_from_entries(self, entries: &[String]) -> Tree {
+        let nodes = EntriesNodesBuilder::new(&self.root_path, entries).build();
+        let displayable_lines =
+            TreeLinesBuilder::new(&nodes, &self.root_path, self.users, true).build();
+
+        Tree {
+            selected: self.root_path.clone(),
+            root_path: self.root_path,
+            nodes,
+            displayable_lines,
+            in_memory: true,
         }
     }
 }
@@ -331,31 +353,137 @@ impl<'a> NodesBuilder<'a> {
     }
 }
 
-#[inline]
-fn first_prefix(prefix: &str) -> String {
-    let mut prefix = prefix.to_string();
-    prefix.push(' ');
-    prefix = prefix.replace("└──", "  ");
-    prefix = prefix.replace("├──", "│ ");
-    prefix.push_str("└──");
-    prefix
+/// A builder of [`Tree`] nodes from relative paths which don't exist in the filesystem.
+/// Missing parent folders are created. Folders always have children, even if it's empty.
+struct EntriesNodesBuilder<'a> {
+    root_path: &'a Arc<Path>,
+    entries: &'a [String],
 }
 
-#[inline]
-fn other_prefix(prefix: &str) -> String {
-    let mut prefix = prefix.to_string();
-    prefix.push(' ');
-    prefix = prefix.replace("└──", "  ");
-    prefix = prefix.replace("├──", "│ ");
-    prefix.push_str("├──");
-    prefix
+impl<'a> EntriesNodesBuilder<'a> {
+    fn new(root_path: &'a Arc<Path>, entries: &'a [String]) -> Self {
+        Self { root_path, entries }
+    }
+
+    fn build(self) -> HashMap<Arc<Path>, Node> {
+        let mut children = self.children_of_folders();
+        let mut stack = vec![self.root_path.to_owned()];
+        let mut nodes: HashMap<Arc<Path>, Node> = HashMap::new();
+        let mut last_path = self.root_path.to_owned();
+        let mut index = 0;
+
+        while let Some(current_path) = stack.pop() {
+            let current_children = children.remove(&current_path);
+            if let Some(current_children) = &current_children {
+                stack.extend(current_children.iter().cloned());
+            }
+            let current_node = Node::new(&current_path, current_children, &last_path, index);
+            if let Some(last_node) = nodes.get_mut(&last_path) {
+                last_node.next = current_path.clone();
+            }
+            last_path = current_path.clone();
+            nodes.insert(current_path, current_node);
+            index += 1;
+        }
+        if let Some(root_node) = nodes.get_mut(self.root_path) {
+            root_node.prev = last_path;
+            root_node.select();
+        }
+        nodes
+    }
+
+    /// Children of every folder, in reversed display order since they're poped from a stack.
+    fn children_of_folders(&self) -> HashMap<Arc<Path>, Vec<Arc<Path>>> {
+        let mut children: HashMap<Arc<Path>, Vec<Arc<Path>>> = HashMap::new();
+        let mut known: HashSet<Arc<Path>> = HashSet::new();
+        children.insert(self.root_path.to_owned(), vec![]);
+        for entry in self.entries {
+            let mut parent = self.root_path.to_owned();
+            let mut components = Path::new(entry)
+                .components()
+                .filter(|component| matches!(component, Component::Normal(_)))
+                .peekable();
+            while let Some(component) = components.next() {
+                let path: Arc<Path> = Arc::from(parent.join(component));
+                let is_folder = components.peek().is_some() || entry.ends_with('/');
+                if is_folder {
+                    children.entry(path.clone()).or_default();
+                }
+                if known.insert(path.clone()) {
+                    children.entry(parent).or_default().push(path.clone());
+                }
+                parent = path;
+            }
+        }
+        let folders: HashSet<Arc<Path>> = children.keys().cloned().collect();
+        for folder_children in children.values_mut() {
+            folder_children.sort_by(|a, b| {
+                (folders.contains(a), b.file_name()).cmp(&(folders.contains(b), a.file_name()))
+            });
+        }
+        children
+    }
+}
+
+/// Glyphs drawn before the filenames, linking the nodes to their parent.
+/// Their width and style are read from the config file, see `tree_indent` and `tree_connectors`.
+struct TreeGlyphs {
+    /// Before the last child of a node: `└──`
+    last: String,
+    /// Before the other children: `├──`
+    other: String,
+    /// Below a child which isn't the last one: `│ `
+    continued: String,
+    /// Below the last child of a node
+    finished: String,
+}
+
+impl TreeGlyphs {
+    fn new() -> Self {
+        let indent = tree_indent();
+        let blank = " ".repeat(indent);
+        if !tree_connectors() {
+            return Self {
+                last: format!(" {blank}"),
+                other: format!(" {blank}"),
+                continued: blank.clone(),
+                finished: blank,
+            };
+        }
+        let line = "─".repeat(indent);
+        Self {
+            last: format!("└{line}"),
+            other: format!("├{line}"),
+            continued: format!("│{blank}", blank = " ".repeat(indent - 1)),
+            finished: blank,
+        }
+    }
+
+    /// Prefix displayed before a node, from the prefix of its ancestors.
+    /// The root, which isn't a child, has no prefix.
+    fn prefix(&self, ancestors: &str, is_last: Option<bool>) -> String {
+        match is_last {
+            None => String::new(),
+            Some(true) => format!("{ancestors} {last}", last = self.last),
+            Some(false) => format!("{ancestors} {other}", other = self.other),
+        }
+    }
+
+    /// Prefix of the ancestors of the children of a node.
+    fn children_ancestors(&self, ancestors: &str, is_last: Option<bool>) -> String {
+        match is_last {
+            None => String::new(),
+            Some(true) => format!("{ancestors} {finished}", finished = self.finished),
+            Some(false) => format!("{ancestors} {continued}", continued = self.continued),
+        }
+    }
 }
 
 #[inline]
-fn filename_format(current_path: &Path, folded: bool, with_icon: bool) -> String {
+fn filename_format(current_path: &Path, is_dir: bool, folded: bool, with_icon: bool) -> String {
     let icon = if with_icon { current_path.icon() } else { "" };
     let filename = filename_from_path(current_path).unwrap_or_default();
-    if current_path.is_dir() && !current_path.is_symlink() {
+    if is_dir {
         let fold_symbol = if folded { "▸" } else { "▾" };
         format!("{fold_symbol} {icon}{filename}")
     } else {
@@ -367,6 +495,7 @@ struct TreeLinesBuilder<'a> {
     nodes: &'a HashMap<Arc<Path>, Node>,
     root_path: &'a Arc<Path>,
     users: &'a Users,
+    in_memory: bool,
 }
 
 impl<'a> TreeLinesBuilder<'a> {
@@ -374,11 +503,13 @@ impl<'a> TreeLinesBuilder<'a> {
         nodes: &'a HashMap<Arc<Path>, Node>,
         root_path: &'a Arc<Path>,
         users: &'a Users,
+        in_memory: bool,
     ) -> Self {
         Self {
             nodes,
             root_path,
             users,
+            in_memory,
         }
     }
 
@@ -397,37 +528,45 @@ impl<'a> TreeLinesBuilder<'a> {
     ///     The prefix (straight lines displaying targets) must also be calcuated immediatly.
     ///     Name format is calculated on the fly.
     fn build(self) -> TreeLines {
-        let mut stack = vec![("".to_owned(), self.root_path.clone())];
+        let glyphs = TreeGlyphs::new();
+        let mut stack = vec![("".to_owned(), None, self.root_path.clone())];
         let mut lines = vec![];
         let mut index = 0;
 
-        while let Some((prefix, path)) = stack.pop() {
+        while let Some((ancestors, is_last, path)) = stack.pop() {
             let Some(node) = self.nodes.get(&path) else {
                 continue;
             };
+            let prefix = glyphs.prefix(&ancestors, is_last);
 
             if node.selected {
                 index = lines.len();
             }
 
-            let Ok(fileinfo) = FileInfo::new(&path, self.users) else {
-                continue;
-            };
-
-            lines.push(TLine::new(&fileinfo, &prefix, node, &path));
+            if self.in_memory {
+                lines.push(TLine::from_entry(&prefix, node, &path));
+            } else {
+                let Ok(fileinfo) = FileInfo::new(&path, self.users) else {
+                    continue;
+                };
+                lines.push(TLine::new(&fileinfo, &prefix, node, &path));
+            }
 
             if node.have_children() {
-                Self::stack_children(&mut stack, prefix, node);
+                let children_ancestors = glyphs.children_ancestors(&ancestors, is_last);
+                Self::stack_children(&mut stack, children_ancestors, node);
             }
         }
         TreeLines::new(lines, index)
     }
 
+    /// Children are popped in reverse order, the first one pushed is displayed last.
     #[inline]
-    fn stack_children(stack: &mut Vec<(String, Arc<Path>)>, prefix: String, current_node: &Node) {
-        let first_prefix = first_prefix(&prefix);
-        let other_prefix = other_prefix(&prefix);
-
+    fn stack_children(
+        stack: &mut Vec<(String, Option<bool>, Arc<Path>)>,
+        ancestors: String,
+        current_node: &Node,
+    ) {
         let Some(children) = &current_node.children else {
             return;
         };
@@ -435,10 +574,10 @@ impl<'a> TreeLinesBuilder<'a> {
         let Some(first_leaf) = children.next() else {
             return;
         };
-        stack.push((first_prefix, first_leaf.clone()));
+        stack.push((ancestors.clone(), Some(true), first_leaf.clone()));
 
         for leaf in children {
-            stack.push((other_prefix.clone(), leaf.clone()));
+            stack.push((ancestors.clone(), Some(false), leaf.clone()));
         }
     }
 }
@@ -499,6 +638,7 @@ impl_index_to_index!(TLine, TreeLines);
 #[derive(Clone, Debug)]
 pub struct TLine {
     folded: bool,
+    is_dir: bool,
     prefix: Arc<str>,
     pub path: Arc<Path>,
     pub style: Style,
@@ -519,9 +659,11 @@ impl TLine {
             .format_no_filename()
             .unwrap_or_else(|_| "?".repeat(19));
         let folded = node.folded;
+        let is_dir = fileinfo.is_dir();
 
         Self {
             folded,
+            is_dir,
             prefix,
             path,
             style,
@@ -529,9 +671,32 @@ impl TLine {
         }
     }
 
+    /// Line of a node which doesn't exist in the filesystem, like an archive entry.
+    /// Nodes with children are folders. There's no metadata.
+    fn from_entry(prefix: &str, node: &Node, path: &Path) -> Self {
+        let is_dir = node.children.is_some();
+        let mut style = if is_dir {
+            FILE_STYLES.get().expect("Colors should be set").directory
+        } else {
+            color_to_style(extension_color(extract_extension(path)))
+        };
+        if node.selected() {
+            style.add_modifier |= Modifier::REVERSED;
+        }
+
+        Self {
+            folded: node.folded,
+            is_dir,
+            prefix: Arc::from(prefix),
+            path: Arc::from(path),
+            style,
+            metadata: String::new(),
+        }
+    }
+
     /// Formated filename
     pub fn filename(&self, with_icon: bool) -> String {
-        filename_format(&self.path, self.folded, with_icon)
+        filename_format(&self.path, self.is_dir, self.folded, with_icon)
     }
 
     /// Vertical bar displayed before the filename to show
@@ -579,6 +744,7 @@ pub struct Tree {
     selected: Arc<Path>,
     nodes: HashMap<Arc<Path>, Node>,
     displayable_lines: TreeLines,
+    in_memory: bool,
 }
 
 impl Default for Tree {
@@ -588,6 +754,7 @@ impl Default for Tree {
             selected: Arc::from(Path::new("")),
             nodes: HashMap::new(),
             displayable_lines: TreeLines::default(),
+            in_memory: false,
         }
     }
 }
@@ -879,7 +1046,8 @@ impl Tree {
     }
 
     fn remake_displayable(&mut self, users: &Users) {
-        self.displayable_lines = TreeLinesBuilder::new(&self.nodes, &self.root_path, users).build();
+        self.displayable_lines =
+            TreeLinesBuilder::new(&self.nodes, &self.root_path, users, self.in_memory).build();
     }
 
     pub fn displayable(&self) -> &TreeLines {
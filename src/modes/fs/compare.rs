@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::fs::{symlink_metadata, Metadata};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use walkdir::WalkDir;
+
+/// What is compared between two files with the same relative path.
+/// Directories are only compared by their presence.
+/// Files are compared by size and modification time, rounded to the second since copies
+/// don't always keep the sub second part.
+#[derive(PartialEq, Eq)]
+enum Signature {
+    Directory,
+    File { size: u64, modified: u64 },
+}
+
+impl Signature {
+    fn new(metadata: &Metadata) -> Self {
+        if metadata.is_dir() {
+            return Self::Directory;
+        }
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        Self::File {
+            size: metadata.len(),
+            modified,
+        }
+    }
+}
+
+/// Differences between a directory and another one.
+#[derive(Default)]
+pub struct Comparison {
+    /// Files of the first directory which are missing or different in the other one.
+    pub differing: Vec<PathBuf>,
+    /// Number of files of the first directory which are missing in the other one.
+    pub only_here: usize,
+    /// Number of files of the other directory which are missing in the first one.
+    pub only_there: usize,
+    /// Number of files present in both directories with a different size or modification time.
+    pub modified: usize,
+}
+
+impl Comparison {
+    /// Compare the children of `here` with those of `there`, matched by their path relative to their directory.
+    /// If `recursive` is false, only `children` of `here` are compared, allowing the caller to respect
+    /// its display settings. Otherwise both directories are walked recursively and `children` is ignored.
+    pub fn new(here: &Path, children: &[PathBuf], there: &Path, recursive: bool) -> Self {
+        let (here_signatures, there_signatures) = if recursive {
+            (walk_signatures(here), walk_signatures(there))
+        } else {
+            let there_children: Vec<PathBuf> = std::fs::read_dir(there)
+                .map(|entries| {
+                    entries
+                        .filter_map(|entry| Some(entry.ok()?.path()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            (
+                signatures(here, children.iter()),
+                signatures(there, there_children.iter()),
+            )
+        };
+        let mut comparison = Self::default();
+        for (relative, signature) in here_signatures.iter() {
+            match there_signatures.get(relative) {
+                None => comparison.only_here += 1,
+                Some(other) if other != signature => comparison.modified += 1,
+                Some(_) => continue,
+            }
+            comparison.differing.push(here.join(relative));
+        }
+        comparison.only_there = there_signatures
+            .keys()
+            .filter(|relative| !here_signatures.contains_key(*relative))
+            .count();
+        comparison.differing.sort_unstable();
+        comparison
+    }
+}
+
+fn signatures<'a>(
+    root: &Path,
+    paths: impl Iterator<Item = &'a PathBuf>,
+) -> HashMap<PathBuf, Signature> {
+    paths
+        .filter_map(|path| {
+            let relative = path.strip_prefix(root).ok()?.to_path_buf();
+            let metadata = symlink_metadata(path).ok()?;
+            Some((relative, Signature::new(&metadata)))
+        })
+        .collect()
+}
+
+fn walk_signatures(root: &Path) -> HashMap<PathBuf, Signature> {
+    WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let relative = entry.path().strip_prefix(root).ok()?.to_path_buf();
+            let metadata = entry.metadata().ok()?;
+            Some((relative, Signature::new(&metadata)))
+        })
+        .collect()
+}
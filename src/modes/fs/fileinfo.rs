@@ -2,6 +2,7 @@ use std::fs::{symlink_metadata, DirEntry, Metadata};
 use std::os::unix::fs::{FileTypeExt, MetadataExt};
 use std::path;
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use anyhow::{Context, Result};
 use chrono::offset::Local;
@@ -167,6 +168,8 @@ pub struct FileInfo {
     pub group: Arc<str>,
     /// System time of last modification
     pub system_time: Arc<str>,
+    /// Time of last access, time of last modification if the filesystem doesn't provide it.
+    pub access_time: SystemTime,
     /// Is this file currently selected ?
     // is_selected: bool,
     /// What kind of file is this ?
@@ -188,6 +191,7 @@ impl FileInfo {
         let owner = extract_owner(&metadata, users);
         let group = extract_group(&metadata, users);
         let system_time = extract_datetime(metadata.modified()?)?;
+        let access_time = metadata.accessed().or_else(|_| metadata.modified())?;
         let file_kind = FileKind::new(&metadata, &path);
         let size_column = SizeColumn::new(true_size, &metadata, &file_kind);
         let extension = extract_extension(&path).into();
@@ -201,6 +205,7 @@ impl FileInfo {
             owner,
             group,
             system_time,
+            access_time,
             file_kind,
             extension,
             kind_format,
@@ -222,7 +227,8 @@ impl FileInfo {
         Ok(file_info)
     }
 
-    fn metadata(&self) -> Result<std::fs::Metadata> {
+    /// Metadata of the file itself, symbolic links aren't followed.
+    pub fn metadata(&self) -> Result<std::fs::Metadata> {
         Ok(symlink_metadata(&self.path)?)
     }
 
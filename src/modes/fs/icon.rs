@@ -1,5 +1,6 @@
 use crate::{
     common::DIR_ICON,
+    config::configured_icon,
     modes::{extract_extension, FileInfo, FileKind},
 };
 
@@ -12,9 +13,13 @@ pub trait Icon {
 
 impl Icon for str {
     /// Associate an extension to a nerdfont icon.
+    /// Icons configured in the `icons` section of the config file replace the default ones.
     /// Adapted from [ranger_devicons](https://github.com/alexanderjeurissen/ranger_devicons)
     #[inline]
     fn icon(&self) -> &'static str {
+        if let Some(icon) = configured_icon(self) {
+            return icon;
+        }
         match self {
             "7z" => " ",
             "a" => " ",
@@ -1,7 +1,9 @@
+mod compare;
 mod fileinfo;
 mod icon;
 mod users;
 
+pub use compare::Comparison;
 pub use fileinfo::{
     convert_octal_mode, extract_datetime, extract_extension, is_not_hidden, FileInfo, FileKind,
 };
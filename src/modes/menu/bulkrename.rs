@@ -1,4 +1,5 @@
-use std::io::{BufRead, Write};
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::sync::{mpsc::Sender, Arc};
 use std::thread;
@@ -8,6 +9,7 @@ use anyhow::{anyhow, Result};
 
 use crate::common::{random_name, rename, TMP_FOLDER_PATH};
 use crate::event::FmEvents;
+use crate::modes::{extract_extension, ExtensionKind};
 use crate::{log_info, log_line};
 
 type OptionVecPathBuf = Option<Vec<PathBuf>>;
@@ -30,6 +32,70 @@ impl BulkExecutor {
         }
     }
 
+    /// Executor renaming images to their capture date, read from EXIF.
+    /// New names are built immediately, nothing is asked to the user.
+    /// Files which can't be renamed are skipped and logged.
+    fn from_exif(flagged: Vec<PathBuf>, parent_dir: &str) -> Self {
+        Self::from_renamer(flagged, parent_dir, exif_filename)
+    }
+
+    /// Executor changing the case of filenames, keeping their extensions.
+    /// Files whose name doesn't change or whose new name is already used are skipped and logged.
+    fn from_case_transform(
+        flagged: Vec<PathBuf>,
+        parent_dir: &str,
+        case_transform: CaseTransform,
+    ) -> Self {
+        Self::from_renamer(flagged, parent_dir, |path, used_names| {
+            case_transform.filename(path, used_names)
+        })
+    }
+
+    /// Executor prefixing the flagged filenames with a numbered sequence, in the order of `flagged`.
+    /// Files whose new name is already used are skipped and logged, their number isn't reused.
+    fn from_sequence(flagged: Vec<PathBuf>, parent_dir: &str, sequence: NumberedSequence) -> Self {
+        let mut number = sequence.start;
+        Self::from_renamer(flagged, parent_dir, |path, used_names| {
+            let filename = sequence.filename(number, path, used_names);
+            number += 1;
+            filename
+        })
+    }
+
+    /// Executor replacing the extension of the flagged filenames, keeping their base names.
+    /// An empty `extension` removes it.
+    fn from_extension(flagged: Vec<PathBuf>, parent_dir: &str, extension: &str) -> Self {
+        Self::from_renamer(flagged, parent_dir, |path, used_names| {
+            extension_filename(path, extension, used_names)
+        })
+    }
+
+    /// Executor URL decoding the flagged filenames, like `My%20File.pdf` -> `My File.pdf`.
+    fn from_url_decode(flagged: Vec<PathBuf>, parent_dir: &str) -> Self {
+        Self::from_renamer(flagged, parent_dir, url_decoded_filename)
+    }
+
+    /// Executor whose new names are built by `renamer` from the flagged paths.
+    /// `renamer` records the new paths it used and returns `None` for skipped files.
+    fn from_renamer<F>(flagged: Vec<PathBuf>, parent_dir: &str, mut renamer: F) -> Self
+    where
+        F: FnMut(&Path, &mut HashSet<PathBuf>) -> Option<String>,
+    {
+        let mut original_filepath = vec![];
+        let mut new_filenames = vec![];
+        let mut used_names = HashSet::new();
+        for path in flagged {
+            let Some(new_filename) = renamer(&path, &mut used_names) else {
+                continue;
+            };
+            original_filepath.push(path);
+            new_filenames.push(new_filename);
+        }
+        let mut executor = Self::new(original_filepath, parent_dir);
+        executor.new_filenames = new_filenames;
+        executor
+    }
+
     fn ask_filenames(self) -> Result<Self> {
         create_random_file(&self.temp_file)?;
         log_info!("created {temp_file}", temp_file = self.temp_file.display());
@@ -138,8 +204,11 @@ impl BulkExecutor {
         Ok(Some(new_path))
     }
 
+    /// Remove the temporary file. Nothing is done if it wasn't created.
     fn del_temporary_file(&self) -> Result<()> {
-        std::fs::remove_file(&self.temp_file)?;
+        if self.temp_file.exists() {
+            std::fs::remove_file(&self.temp_file)?;
+        }
         Ok(())
     }
 }
@@ -176,6 +245,303 @@ fn get_new_filenames(temp_file: &Path) -> Result<Vec<String>> {
     Ok(new_names)
 }
 
+/// New filename of an image from its capture date, like `2023-05-01_14-30-22.jpg`.
+/// If this name is already used, in the directory or by another renamed image, a numeric suffix is added.
+/// Returns `None` and logs the reason for non images, images without EXIF date and already well named images.
+fn exif_filename(path: &Path, used_names: &mut HashSet<PathBuf>) -> Option<String> {
+    let extension = extract_extension(path).to_lowercase();
+    if !matches!(ExtensionKind::matcher(&extension), ExtensionKind::Image) {
+        log_line!("Exif rename: {path} isn't an image", path = path.display());
+        return None;
+    }
+    let Some(stem) = exif_capture_date(path) else {
+        log_line!(
+            "Exif rename: {path} has no capture date",
+            path = path.display()
+        );
+        return None;
+    };
+    let parent = path.parent()?;
+    let mut suffix = 0;
+    loop {
+        let filename = if suffix == 0 {
+            format!("{stem}.{extension}")
+        } else {
+            format!("{stem}_{suffix}.{extension}")
+        };
+        let new_path = parent.join(&filename);
+        if new_path == path {
+            log_info!(
+                "Exif rename: {path} is already named after its capture date",
+                path = path.display()
+            );
+            return None;
+        }
+        if !new_path.exists() && used_names.insert(new_path) {
+            return Some(filename);
+        }
+        suffix += 1;
+    }
+}
+
+/// Capture date of an image, formated as `2023-05-01_14-30-22`.
+/// It's read from the `DateTimeOriginal` EXIF tag.
+fn exif_capture_date(path: &Path) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut BufReader::new(file))
+        .ok()?;
+    let field = exif.get_field(exif::Tag::DateTimeOriginal, exif::In::PRIMARY)?;
+    let exif::Value::Ascii(ref values) = field.value else {
+        return None;
+    };
+    let date = exif::DateTime::from_ascii(values.first()?).ok()?;
+    Some(format!(
+        "{year:04}-{month:02}-{day:02}_{hour:02}-{minute:02}-{second:02}",
+        year = date.year,
+        month = date.month,
+        day = date.day,
+        hour = date.hour,
+        minute = date.minute,
+        second = date.second,
+    ))
+}
+
+/// New filename of this file with another extension, its base name is kept.
+/// Files without extension get one, an empty `extension` removes it.
+/// Returns `None` and logs the reason if the name doesn't change or if the new name is already used,
+/// in the directory or by another renamed file.
+fn extension_filename(
+    path: &Path,
+    extension: &str,
+    used_names: &mut HashSet<PathBuf>,
+) -> Option<String> {
+    let filename = path.file_name()?.to_string_lossy();
+    let old_extension = extract_extension(path);
+    let base = if old_extension.is_empty() {
+        &filename
+    } else {
+        &filename[..filename.len() - old_extension.len() - 1]
+    };
+    let filename = if extension.is_empty() {
+        base.to_owned()
+    } else {
+        format!("{base}.{extension}")
+    };
+    let new_path = path.parent()?.join(&filename);
+    if new_path == path {
+        log_info!(
+            "Extension rename: {path} is unchanged",
+            path = path.display()
+        );
+        return None;
+    }
+    if new_path.exists() || !used_names.insert(new_path) {
+        log_line!(
+            "Extension rename: {filename} already exists, {path} is skipped",
+            path = path.display()
+        );
+        return None;
+    }
+    Some(filename)
+}
+
+/// New filename of this file, URL decoded: every `%XX` sequence is replaced by the byte it encodes.
+/// Returns `None` and logs the reason if the name doesn't change, if the decoded name isn't valid
+/// (invalid UTF-8, a `/` or a nul byte) or if the new name is already used,
+/// in the directory or by another renamed file.
+fn url_decoded_filename(path: &Path, used_names: &mut HashSet<PathBuf>) -> Option<String> {
+    let filename = path.file_name()?.to_string_lossy();
+    let Some(decoded) = url_decode(&filename) else {
+        log_line!(
+            "URL decode: decoded name of {path} isn't valid, it's skipped",
+            path = path.display()
+        );
+        return None;
+    };
+    let new_path = path.parent()?.join(&decoded);
+    if new_path == path {
+        log_info!("URL decode: {path} is unchanged", path = path.display());
+        return None;
+    }
+    if new_path.exists() || !used_names.insert(new_path) {
+        log_line!(
+            "URL decode: {decoded} already exists, {path} is skipped",
+            path = path.display()
+        );
+        return None;
+    }
+    Some(decoded)
+}
+
+/// Replace every `%XX` sequence, `XX` being 2 hexadecimal digits, by the byte it encodes.
+/// Other `%` are kept as is.
+/// Returns `None` if the result isn't valid UTF-8 or can't be used as a filename.
+fn url_decode(encoded: &str) -> Option<String> {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            if let Some(byte) = bytes
+                .get(index + 1..index + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+    let decoded = String::from_utf8(decoded).ok()?;
+    if decoded.is_empty() || decoded.contains(['/', '\0']) {
+        return None;
+    }
+    Some(decoded)
+}
+
+/// Case transformations applied to the flagged filenames.
+/// The extension is kept as is.
+#[derive(Clone, Copy)]
+pub enum CaseTransform {
+    /// `My File.TXT` -> `my file.TXT`
+    Lowercase,
+    /// `My File.TXT` -> `MY FILE.TXT`
+    Uppercase,
+    /// `My File-name.TXT` -> `my_file_name.TXT`, also splitting camelCase words.
+    SnakeCase,
+}
+
+impl CaseTransform {
+    /// Transform the name of the file, without its extension.
+    fn apply(&self, stem: &str) -> String {
+        match self {
+            Self::Lowercase => stem.to_lowercase(),
+            Self::Uppercase => stem.to_uppercase(),
+            Self::SnakeCase => Self::snake_case(stem),
+        }
+    }
+
+    /// Words are separated by whitespaces, dashes, underscores or a lowercase letter followed by an uppercase one.
+    /// They are lowercased and joined with underscores.
+    fn snake_case(stem: &str) -> String {
+        let mut words: Vec<String> = vec![];
+        let mut word = String::new();
+        let mut previous_is_lowercase = false;
+        for c in stem.chars() {
+            if c.is_whitespace() || c == '-' || c == '_' {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                previous_is_lowercase = false;
+                continue;
+            }
+            if c.is_uppercase() && previous_is_lowercase {
+                words.push(std::mem::take(&mut word));
+            }
+            previous_is_lowercase = c.is_lowercase() || c.is_ascii_digit();
+            word.extend(c.to_lowercase());
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+        words.join("_")
+    }
+
+    /// New filename of this file, keeping its extension.
+    /// Returns `None` and logs the reason if the name doesn't change or if the new name is already used,
+    /// in the directory or by another renamed file.
+    fn filename(&self, path: &Path, used_names: &mut HashSet<PathBuf>) -> Option<String> {
+        let stem = path.file_stem()?.to_string_lossy();
+        let new_stem = self.apply(&stem);
+        let filename = match path.extension() {
+            Some(extension) => format!("{new_stem}.{ext}", ext = extension.to_string_lossy()),
+            None => new_stem,
+        };
+        let new_path = path.parent()?.join(&filename);
+        if new_path == path {
+            log_info!("Case rename: {path} is unchanged", path = path.display());
+            return None;
+        }
+        if new_path.exists() || !used_names.insert(new_path) {
+            log_line!(
+                "Case rename: {filename} already exists, {path} is skipped",
+                path = path.display()
+            );
+            return None;
+        }
+        Some(filename)
+    }
+}
+
+/// Zero padded numbers prepended to filenames, like `01_first.jpg`, `02_second.jpg`...
+#[derive(Clone, Copy)]
+pub struct NumberedSequence {
+    start: usize,
+    width: usize,
+}
+
+impl NumberedSequence {
+    const SEPARATOR: char = '_';
+    const MIN_WIDTH: usize = 2;
+
+    /// Parse the first number and the width of the sequence, separated by a space, like `1 2`.
+    /// Both are optional: the sequence starts at 1 and the width is the number of digits
+    /// of the last number of the `nb_files` files, at least 2.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a value isn't a positive integer or if there's more than 2 values.
+    pub fn parse(input: &str, nb_files: usize) -> Result<Self> {
+        let values = input
+            .split_whitespace()
+            .map(|value| value.parse::<usize>())
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let (start, width) = match values[..] {
+            [] => (1, None),
+            [start] => (start, None),
+            [start, width] => (start, Some(width)),
+            _ => {
+                return Err(anyhow!(
+                    "expected the first number and the width, like `1 2`"
+                ))
+            }
+        };
+        let last = start + nb_files.saturating_sub(1);
+        let width = width.unwrap_or_else(|| last.to_string().len().max(Self::MIN_WIDTH));
+        Ok(Self { start, width })
+    }
+
+    /// New filename of this file, prefixed by `number`.
+    /// Returns `None` and logs the reason if the new name is already used,
+    /// in the directory or by another renamed file.
+    fn filename(
+        &self,
+        number: usize,
+        path: &Path,
+        used_names: &mut HashSet<PathBuf>,
+    ) -> Option<String> {
+        let filename = format!(
+            "{number:0width$}{separator}{name}",
+            width = self.width,
+            separator = Self::SEPARATOR,
+            name = path.file_name()?.to_string_lossy(),
+        );
+        let new_path = path.parent()?.join(&filename);
+        if new_path.exists() || !used_names.insert(new_path) {
+            log_line!(
+                "Numbered rename: {filename} already exists, {path} is skipped",
+                path = path.display()
+            );
+            return None;
+        }
+        Some(filename)
+    }
+}
+
 /// A `BulkExecutor` and a sender of [`FmEvents`].
 /// It's used to execute creation / renaming of multiple files at once.
 /// Obviously it's inspired by ranger.
@@ -218,6 +584,94 @@ impl Bulk {
         Ok(())
     }
 
+    /// Prepare the renaming of flagged images to their capture date.
+    /// The new names are displayed for confirmation before anything is renamed.
+    /// Returns false if no file can be renamed.
+    pub fn exif_rename(
+        &mut self,
+        flagged_in_current_dir: Vec<PathBuf>,
+        current_tab_path_str: &str,
+    ) -> bool {
+        self.set_prepared(BulkExecutor::from_exif(
+            flagged_in_current_dir,
+            current_tab_path_str,
+        ))
+    }
+
+    /// Prepare the case transformation of the flagged filenames.
+    /// The new names are displayed for confirmation before anything is renamed.
+    /// Returns false if no file can be renamed.
+    pub fn case_rename(
+        &mut self,
+        flagged_in_current_dir: Vec<PathBuf>,
+        current_tab_path_str: &str,
+        case_transform: CaseTransform,
+    ) -> bool {
+        self.set_prepared(BulkExecutor::from_case_transform(
+            flagged_in_current_dir,
+            current_tab_path_str,
+            case_transform,
+        ))
+    }
+
+    /// Prepare the prefixing of the flagged filenames with a numbered sequence.
+    /// Files are numbered in the order of `flagged_in_display_order`.
+    /// The new names are displayed for confirmation before anything is renamed.
+    /// Returns false if no file can be renamed.
+    pub fn numbered_rename(
+        &mut self,
+        flagged_in_display_order: Vec<PathBuf>,
+        current_tab_path_str: &str,
+        sequence: NumberedSequence,
+    ) -> bool {
+        self.set_prepared(BulkExecutor::from_sequence(
+            flagged_in_display_order,
+            current_tab_path_str,
+            sequence,
+        ))
+    }
+
+    /// Prepare the replacement of the extension of the flagged filenames.
+    /// The new names are displayed for confirmation before anything is renamed.
+    /// Returns false if no file can be renamed.
+    pub fn extension_rename(
+        &mut self,
+        flagged_in_current_dir: Vec<PathBuf>,
+        current_tab_path_str: &str,
+        extension: &str,
+    ) -> bool {
+        self.set_prepared(BulkExecutor::from_extension(
+            flagged_in_current_dir,
+            current_tab_path_str,
+            extension,
+        ))
+    }
+
+    /// Prepare the URL decoding of the flagged filenames.
+    /// The new names are displayed for confirmation before anything is renamed.
+    /// Returns false if no file can be renamed.
+    pub fn url_decode_rename(
+        &mut self,
+        flagged_in_current_dir: Vec<PathBuf>,
+        current_tab_path_str: &str,
+    ) -> bool {
+        self.set_prepared(BulkExecutor::from_url_decode(
+            flagged_in_current_dir,
+            current_tab_path_str,
+        ))
+    }
+
+    /// Holds an executor whose new names are already known.
+    /// Returns false and resets if it has nothing to rename.
+    fn set_prepared(&mut self, executor: BulkExecutor) -> bool {
+        if executor.original_filepath.is_empty() {
+            self.reset();
+            return false;
+        }
+        self.bulk = Some(executor);
+        true
+    }
+
     pub fn watch_in_thread(&mut self, fm_sender: Arc<Sender<FmEvents>>) -> Result<()> {
         match &self.bulk {
             Some(bulk) => bulk.watch_modification_in_thread(fm_sender)?,
@@ -233,6 +687,18 @@ impl Bulk {
         Ok(())
     }
 
+    /// Paths of the files which will be renamed.
+    pub fn renamed_paths(&self) -> Vec<PathBuf> {
+        let Some(bulk) = &self.bulk else {
+            return vec![];
+        };
+        bulk.original_filepath
+            .iter()
+            .take(bulk.new_filenames.len())
+            .cloned()
+            .collect()
+    }
+
     /// String representation of the filetree modifications.
     pub fn format_confirmation(&self) -> Vec<String> {
         if let Some(bulk) = &self.bulk {
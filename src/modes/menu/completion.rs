@@ -1,14 +1,23 @@
+use std::cmp::min;
 use std::fmt;
 use std::fs::{self, ReadDir};
+use std::ops::Range;
 use std::path::Path;
 
-use ratatui::style::{Modifier, Style};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+    Frame,
+};
 
 use crate::common::{is_in_path, tilde, UtfWidth, ZOXIDE};
+use crate::config::{ColorG, Gradient, MENU_STYLES};
 use crate::event::ActionMap;
-use crate::io::{execute_and_capture_output_with_path, DrawMenu};
-use crate::modes::Leave;
-use crate::{impl_content, impl_selectable};
+use crate::io::{color_to_style, execute_and_capture_output_with_path, DrawMenu, Offseted};
+use crate::modes::{ContentWindow, Leave};
+use crate::{colored_skip_take, impl_content, impl_selectable};
 
 /// Different kind of completions
 #[derive(Clone, Default, Copy, Eq, PartialEq)]
@@ -255,6 +264,37 @@ impl Completion {
         self.current_proposition().strip_prefix(input_string)
     }
 
+    /// Display the proposals like [`DrawMenu::draw_menu`] does,
+    /// the part of each proposal matching the typed text is bold and underlined.
+    pub fn draw_highlighted(
+        &self,
+        f: &mut Frame,
+        rect: &Rect,
+        window: &ContentWindow,
+        typed: &str,
+    ) {
+        let mut p_rect = rect.offseted(4, 3);
+        p_rect.height = p_rect.height.saturating_sub(2);
+        let content = self.content();
+        let lines: Vec<_> = colored_skip_take!(content, window)
+            .map(|(index, item, style)| {
+                let style = self.style(index, &style);
+                let Some(range) = matched_range(item, typed) else {
+                    return Line::styled(item.as_str(), style);
+                };
+                Line::from(vec![
+                    Span::styled(&item[..range.start], style),
+                    Span::styled(
+                        &item[range.clone()],
+                        style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                    ),
+                    Span::styled(&item[range.end..], style),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines).render(p_rect, f.buffer_mut());
+    }
+
     /// Reverse the received effect if the index match the selected index.
     pub fn style(&self, index: usize, style: &Style) -> Style {
         let mut style = *style;
@@ -281,6 +321,37 @@ fn filename_startswith(entry: &std::fs::DirEntry, pattern: &str) -> bool {
         .starts_with(pattern)
 }
 
+/// Byte range of the last component of the proposal matching the last component of the typed text.
+/// The case is ignored. Returns `None` if nothing was typed or if it doesn't match.
+fn matched_range(proposal: &str, typed: &str) -> Option<Range<usize>> {
+    let needle = typed.rsplit('/').next()?;
+    if needle.is_empty() {
+        return None;
+    }
+    let name_start = proposal
+        .trim_end_matches('/')
+        .rfind('/')
+        .map_or(0, |index| index + 1);
+    let range = find_ignoring_case(&proposal[name_start..], needle)?;
+    Some(name_start + range.start..name_start + range.end)
+}
+
+/// Byte range of the first occurence of `needle` in `haystack`, ignoring the case.
+fn find_ignoring_case(haystack: &str, needle: &str) -> Option<Range<usize>> {
+    haystack.char_indices().find_map(|(start, _)| {
+        let mut end = start;
+        let mut chars = haystack[start..].chars();
+        for expected in needle.chars() {
+            let found = chars.next()?;
+            if !found.to_lowercase().eq(expected.to_lowercase()) {
+                return None;
+            }
+            end += found.len_utf8();
+        }
+        Some(start..end)
+    })
+}
+
 fn split_input_string(input_string: &str) -> (String, String) {
     let steps = input_string.split('/');
     let mut vec_steps: Vec<&str> = steps.collect();
@@ -26,12 +26,22 @@ pub enum CompressionMethod {
 impl CompressionMethod {
     fn to_str(&self) -> &'static str {
         match self {
-            Self::Zip => "ZIP:     archive.zip",
-            Self::Defl => "DEFLATE: archive.tar.gz",
-            Self::Lzma => "LZMA:    archive.tar.xz",
-            Self::Gz => "GZ:      archive.tar.gz",
-            Self::Zlib => "ZLIB:    archive.tar.xz",
-            Self::Sevenz => "7Z:      archive.7z",
+            Self::Zip => "ZIP:     .zip",
+            Self::Defl => "DEFLATE: .tar.gz",
+            Self::Lzma => "LZMA:    .tar.xz",
+            Self::Gz => "GZ:      .tar.gz",
+            Self::Zlib => "ZLIB:    .tar.xz",
+            Self::Sevenz => "7Z:      .7z",
+        }
+    }
+
+    /// Extension of the archive created with this method, including the first dot.
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Zip => ".zip",
+            Self::Defl | Self::Gz => ".tar.gz",
+            Self::Lzma | Self::Zlib => ".tar.xz",
+            Self::Sevenz => ".7z",
         }
     }
 }
@@ -57,23 +67,43 @@ impl Compresser {
     /// Archive the files with tar and compress them with the selected method.
     /// The compression method is chosen by the user.
     /// Archive is created `here` which should be the path of the selected tab.
-    pub fn compress(&self, files: Vec<PathBuf>, here: &Path) -> Result<()> {
+    /// It's named `name` followed by the extension of the method. An empty name is replaced by "archive".
+    /// An existing file is never overwritten.
+    pub fn compress(&self, files: Vec<PathBuf>, here: &Path, name: &str) -> Result<()> {
         let Some(selected) = self.selected() else {
             return Ok(());
         };
+        let archive_name = Self::archive_name(name, selected);
+        if here.join(&archive_name).exists() {
+            log_line!("{archive_name} already exists, it won't be overwritten");
+            return Ok(());
+        }
         match selected {
             #[rustfmt::skip]
-            CompressionMethod::Zip  => Self::zip (Self::archive(here, "archive.zip")?, files)?,
-            CompressionMethod::Lzma => Self::lzma(Self::archive(here, "archive.tar.xz")?, files)?,
-            CompressionMethod::Zlib => Self::zlib(Self::archive(here, "archive.tar.xz")?, files)?,
-            CompressionMethod::Gz => Self::gzip(Self::archive(here, "archive.tar.gz")?, files)?,
-            CompressionMethod::Defl => Self::defl(Self::archive(here, "archive.tar.gz")?, files)?,
-            CompressionMethod::Sevenz => Self::sevenz(here, "archive.7z", files)?,
+            CompressionMethod::Zip  => Self::zip (Self::archive(here, &archive_name)?, files)?,
+            CompressionMethod::Lzma => Self::lzma(Self::archive(here, &archive_name)?, files)?,
+            CompressionMethod::Zlib => Self::zlib(Self::archive(here, &archive_name)?, files)?,
+            CompressionMethod::Gz => Self::gzip(Self::archive(here, &archive_name)?, files)?,
+            CompressionMethod::Defl => Self::defl(Self::archive(here, &archive_name)?, files)?,
+            CompressionMethod::Sevenz => Self::sevenz(here, &archive_name, files)?,
         }
-        log_line!("Compressed with {selected}", selected = selected.to_str());
+        log_line!("Compressed into {archive_name}");
         Ok(())
     }
 
+    /// Name of the archive with the extension of the compression method.
+    /// The extension isn't added twice if the user already typed it.
+    fn archive_name(name: &str, method: &CompressionMethod) -> String {
+        let name = name.trim();
+        let name = if name.is_empty() { "archive" } else { name };
+        let extension = method.extension();
+        if name.ends_with(extension) {
+            name.to_owned()
+        } else {
+            format!("{name}{extension}")
+        }
+    }
+
     fn make_tar<W>(files: Vec<PathBuf>, mut archive: tar::Builder<W>) -> Result<()>
     where
         W: Write,
@@ -0,0 +1,407 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::log_line;
+use crate::modes::{extract_datetime, human_size, CopyMove};
+
+/// What to do with a file whose name is already used in the destination of a copy or a move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictChoice {
+    /// Replace the existing file.
+    Overwrite,
+    /// Don't transfer the file.
+    Skip,
+    /// Transfer the file under another name.
+    Rename,
+    /// Replace the existing file if the transferred one is more recent, skip it otherwise.
+    OverwriteIfNewer,
+}
+
+impl ConflictChoice {
+    /// Choice typed by the user and true if it's applied to every remaining conflict.
+    /// Uppercase keys apply to every remaining conflict.
+    pub fn from_char(c: char) -> Option<(Self, bool)> {
+        let choice = match c.to_ascii_lowercase() {
+            'o' => Self::Overwrite,
+            's' => Self::Skip,
+            'r' => Self::Rename,
+            'n' => Self::OverwriteIfNewer,
+            _ => return None,
+        };
+        Some((choice, c.is_ascii_uppercase()))
+    }
+}
+
+/// Files of a copy or a move whose name is already used in the destination.
+/// They're resolved one at a time by the user before the transfer starts.
+///
+/// A file copied into its own directory isn't a conflict: as before, the copy
+/// has enough `_` appended to its name to make it unique.
+///
+/// Existing directories are never overwritten, overwriting one skips the source instead.
+pub struct Conflicts {
+    copy_move: CopyMove,
+    sources: Vec<PathBuf>,
+    dest: PathBuf,
+    /// Conflicting sources not resolved yet. The first one is asked to the user.
+    pending: Vec<PathBuf>,
+    /// Sources which won't be transferred.
+    skipped: Vec<PathBuf>,
+    /// Final path of the conflicting files which are transferred, by source path.
+    targets: HashMap<PathBuf, PathBuf>,
+}
+
+impl Conflicts {
+    /// Find the sources whose name is used by another file in `dest`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if two sources from different directories have the same name: they'd be transferred to the same path.
+    pub fn new(copy_move: CopyMove, sources: Vec<PathBuf>, dest: &Path) -> Result<Self> {
+        let mut names = HashSet::new();
+        for source in &sources {
+            let Some(filename) = source.file_name() else {
+                continue;
+            };
+            if !names.insert(filename) {
+                bail!(
+                    "Several files named {filename} can't be transferred to the same directory",
+                    filename = filename.to_string_lossy()
+                );
+            }
+        }
+        let pending = sources
+            .iter()
+            .filter(|source| {
+                source.file_name().is_some_and(|filename| {
+                    let target = dest.join(filename);
+                    target.symlink_metadata().is_ok() && &target != *source
+                })
+            })
+            .cloned()
+            .collect();
+        Ok(Self {
+            copy_move,
+            sources,
+            dest: dest.to_path_buf(),
+            pending,
+            skipped: vec![],
+            targets: HashMap::new(),
+        })
+    }
+
+    /// True if every conflict is resolved.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Source of the conflict asked to the user.
+    fn current(&self) -> Option<&Path> {
+        self.pending.first().map(|source| source.as_path())
+    }
+
+    /// Existing file in the destination with the same name as `source`.
+    fn existing(&self, source: &Path) -> Option<PathBuf> {
+        Some(self.dest.join(source.file_name()?))
+    }
+
+    /// Apply a choice to the current conflict, or to every remaining one if `to_all` is true.
+    /// Renamed files have enough `_` appended to their name to make it unique.
+    /// Another name can be typed with [`Conflicts::rename_current`].
+    pub fn resolve(&mut self, choice: ConflictChoice, to_all: bool) {
+        loop {
+            if self.pending.is_empty() {
+                return;
+            }
+            let source = self.pending.remove(0);
+            self.resolve_source(source, choice);
+            if !to_all {
+                return;
+            }
+        }
+    }
+
+    fn resolve_source(&mut self, source: PathBuf, choice: ConflictChoice) {
+        let Some(existing) = self.existing(&source) else {
+            self.skipped.push(source);
+            return;
+        };
+        match choice {
+            ConflictChoice::Overwrite | ConflictChoice::OverwriteIfNewer
+                if is_directory(&existing) =>
+            {
+                log_line!(
+                    "{existing} is a directory, it won't be overwritten",
+                    existing = existing.display()
+                );
+                self.skipped.push(source);
+            }
+            ConflictChoice::Overwrite => {
+                self.targets.insert(source, existing);
+            }
+            ConflictChoice::OverwriteIfNewer if is_newer(&source, &existing) => {
+                self.targets.insert(source, existing);
+            }
+            ConflictChoice::Skip | ConflictChoice::OverwriteIfNewer => self.skipped.push(source),
+            ConflictChoice::Rename => match self.free_name_for(&source) {
+                Some(name) => {
+                    let target = self.dest.join(name);
+                    self.targets.insert(source, target);
+                }
+                None => self.skipped.push(source),
+            },
+        }
+    }
+
+    /// Transfer the current source under `name` in the destination.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the name is invalid or already used.
+    pub fn rename_current(&mut self, name: &str) -> Result<()> {
+        if name.is_empty() || name.contains('/') || matches!(name, "." | "..") {
+            bail!("Invalid name {name}");
+        }
+        let target = self.dest.join(name);
+        if self.is_used(&target) {
+            bail!("{name} is already used");
+        }
+        self.current().context("No conflict to resolve")?;
+        let source = self.pending.remove(0);
+        self.targets.insert(source, target);
+        Ok(())
+    }
+
+    /// A free name for the current source in the destination.
+    pub fn free_name(&self) -> Option<String> {
+        self.free_name_for(self.current()?)
+    }
+
+    /// Name of `source` with enough `_` appended to be unused in the destination.
+    fn free_name_for(&self, source: &Path) -> Option<String> {
+        let mut name = source.file_name()?.to_str()?.to_owned();
+        while self.is_used(&self.dest.join(&name)) {
+            name.push('_');
+        }
+        Some(name)
+    }
+
+    /// True if `target` exists or if another transferred file will be renamed to it.
+    fn is_used(&self, target: &Path) -> bool {
+        target.symlink_metadata().is_ok() || self.targets.values().any(|used| used == target)
+    }
+
+    /// Lines describing the current conflict: both files, their sizes and modification times.
+    pub fn describe(&self) -> Vec<String> {
+        let Some(source) = self.current() else {
+            return vec![];
+        };
+        let Some(existing) = self.existing(source) else {
+            return vec![];
+        };
+        let mut lines = vec![
+            format!(
+                "{existing} already exists, {nb} conflict(s) left.",
+                existing = existing.display(),
+                nb = self.pending.len()
+            ),
+            String::new(),
+            format!("source:   {details}", details = details(source)),
+            format!("existing: {details}", details = details(&existing)),
+        ];
+        if is_directory(&existing) {
+            lines.push("Directories can't be overwritten, o and n skip the source.".to_owned());
+        } else if is_newer(source, &existing) {
+            lines.push("The source is newer.".to_owned());
+        }
+        lines
+    }
+
+    /// Kind of transfer, sources which aren't skipped, destination and final paths of the conflicting files
+    /// by source path.
+    pub fn into_transfer(self) -> (CopyMove, Vec<PathBuf>, PathBuf, HashMap<PathBuf, PathBuf>) {
+        let sources = self
+            .sources
+            .into_iter()
+            .filter(|source| !self.skipped.contains(source))
+            .collect();
+        (self.copy_move, sources, self.dest, self.targets)
+    }
+}
+
+/// Size and modification time of a file.
+fn details(path: &Path) -> String {
+    let Ok(metadata) = path.symlink_metadata() else {
+        return path.display().to_string();
+    };
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| extract_datetime(modified).ok())
+        .unwrap_or_default();
+    format!(
+        "{size}  {modified}  {path}",
+        size = human_size(metadata.len()),
+        path = path.display()
+    )
+}
+
+/// True if `path` is a directory, not a symlink to a directory.
+fn is_directory(path: &Path) -> bool {
+    path.symlink_metadata()
+        .is_ok_and(|metadata| metadata.is_dir())
+}
+
+/// True if `source` was modified more recently than `existing`.
+fn is_newer(source: &Path, existing: &Path) -> bool {
+    let modified = |path: &Path| path.symlink_metadata().and_then(|m| m.modified()).ok();
+    match (modified(source), modified(existing)) {
+        (Some(source), Some(existing)) => source > existing,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir, write, File};
+    use std::time::{Duration, SystemTime};
+
+    use super::*;
+
+    /// A source directory with `names` files and a destination already containing `existing` files.
+    fn setup(names: &[&str], existing: &[&str]) -> (tempfile::TempDir, Vec<PathBuf>, PathBuf) {
+        let root = tempfile::tempdir().unwrap();
+        let source_dir = root.path().join("source");
+        let dest = root.path().join("dest");
+        create_dir(&source_dir).unwrap();
+        create_dir(&dest).unwrap();
+        let sources = names
+            .iter()
+            .map(|name| {
+                let source = source_dir.join(name);
+                write(&source, "source").unwrap();
+                source
+            })
+            .collect();
+        for name in existing {
+            write(dest.join(name), "existing").unwrap();
+        }
+        (root, sources, dest)
+    }
+
+    fn set_modified(path: &Path, modified: SystemTime) {
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_only_existing_names_are_conflicts() {
+        let (_root, sources, dest) = setup(&["a", "b"], &["a"]);
+        let conflicts = Conflicts::new(CopyMove::Copy, sources, &dest).unwrap();
+        assert!(!conflicts.is_empty());
+        assert_eq!(conflicts.pending.len(), 1);
+    }
+
+    #[test]
+    fn test_copy_in_own_directory_isnt_a_conflict() {
+        let (_root, sources, _dest) = setup(&["a"], &[]);
+        let source_dir = sources[0].parent().unwrap().to_path_buf();
+        let conflicts = Conflicts::new(CopyMove::Copy, sources, &source_dir).unwrap();
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_one_at_a_time() {
+        let (_root, sources, dest) = setup(&["a", "b"], &["a", "b"]);
+        let mut conflicts = Conflicts::new(CopyMove::Copy, sources.clone(), &dest).unwrap();
+        conflicts.resolve(ConflictChoice::Overwrite, false);
+        assert!(!conflicts.is_empty());
+        conflicts.resolve(ConflictChoice::Skip, false);
+        assert!(conflicts.is_empty());
+        let (_, transfered, transfer_dest, targets) = conflicts.into_transfer();
+        assert_eq!(transfered, vec![sources[0].clone()]);
+        assert_eq!(transfer_dest, dest);
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[&sources[0]], dest.join("a"));
+    }
+
+    #[test]
+    fn test_resolve_to_all() {
+        let (_root, sources, dest) = setup(&["a", "b", "c"], &["a", "b"]);
+        let mut conflicts = Conflicts::new(CopyMove::Move, sources.clone(), &dest).unwrap();
+        conflicts.resolve(ConflictChoice::Rename, true);
+        assert!(conflicts.is_empty());
+        let (_, transfered, _, targets) = conflicts.into_transfer();
+        assert_eq!(transfered, sources);
+        assert_eq!(targets[&sources[0]], dest.join("a_"));
+        assert_eq!(targets[&sources[1]], dest.join("b_"));
+        assert!(!targets.contains_key(&sources[2]));
+    }
+
+    #[test]
+    fn test_resolve_overwrite_if_newer() {
+        let (_root, sources, dest) = setup(&["old", "new"], &["old", "new"]);
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(3600);
+        set_modified(&sources[0], now - hour);
+        set_modified(&dest.join("old"), now);
+        set_modified(&sources[1], now);
+        set_modified(&dest.join("new"), now - hour);
+        let mut conflicts = Conflicts::new(CopyMove::Copy, sources.clone(), &dest).unwrap();
+        conflicts.resolve(ConflictChoice::OverwriteIfNewer, true);
+        let (_, transfered, _, targets) = conflicts.into_transfer();
+        assert_eq!(transfered, vec![sources[1].clone()]);
+        assert_eq!(targets[&sources[1]], dest.join("new"));
+    }
+
+    #[test]
+    fn test_existing_directory_isnt_overwritten() {
+        let (_root, sources, dest) = setup(&["a"], &[]);
+        create_dir(dest.join("a")).unwrap();
+        write(dest.join("a").join("inner"), "kept").unwrap();
+        let mut conflicts = Conflicts::new(CopyMove::Copy, sources, &dest).unwrap();
+        conflicts.resolve(ConflictChoice::Overwrite, true);
+        let (_, transfered, _, targets) = conflicts.into_transfer();
+        assert!(transfered.is_empty());
+        assert!(targets.is_empty());
+    }
+
+    #[test]
+    fn test_same_names_from_different_directories() {
+        let (root, mut sources, dest) = setup(&["a"], &["a"]);
+        let other_dir = root.path().join("other");
+        create_dir(&other_dir).unwrap();
+        let other = other_dir.join("a");
+        write(&other, "other").unwrap();
+        sources.push(other);
+        assert!(Conflicts::new(CopyMove::Copy, sources.clone(), &dest).is_err());
+        let empty_dest = root.path().join("empty");
+        create_dir(&empty_dest).unwrap();
+        assert!(Conflicts::new(CopyMove::Copy, sources, &empty_dest).is_err());
+    }
+
+    #[test]
+    fn test_rename_current() {
+        let (_root, sources, dest) = setup(&["a", "b"], &["a", "b", "used"]);
+        let mut conflicts = Conflicts::new(CopyMove::Copy, sources.clone(), &dest).unwrap();
+        assert_eq!(conflicts.free_name(), Some("a_".to_owned()));
+        for invalid in ["", ".", "..", "x/y", "used"] {
+            assert!(conflicts.rename_current(invalid).is_err());
+        }
+        conflicts.rename_current("renamed").unwrap();
+        assert!(conflicts.rename_current("renamed").is_err());
+        conflicts.rename_current("other").unwrap();
+        assert!(conflicts.is_empty());
+        assert!(conflicts.rename_current("again").is_err());
+        let (_, transfered, _, targets) = conflicts.into_transfer();
+        assert_eq!(transfered, sources);
+        assert_eq!(targets[&sources[0]], dest.join("renamed"));
+        assert_eq!(targets[&sources[1]], dest.join("other"));
+    }
+}
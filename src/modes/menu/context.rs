@@ -1,9 +1,13 @@
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::Path;
+
+use crate::common::{collapse_home, recursive_size};
 use crate::event::ActionMap;
-use crate::io::Opener;
-use crate::modes::{extract_datetime, ExtensionKind, FileInfo, FileKind};
+use crate::io::{mime_type, Opener};
+use crate::modes::{extract_datetime, human_size, read_xattrs, ExtensionKind, FileInfo, FileKind};
 use crate::{impl_content, impl_selectable};
 
-const CONTEXT: [(&str, ActionMap); 10] = [
+const CONTEXT: [(&str, ActionMap); 12] = [
     ("Open", ActionMap::OpenFile),
     ("Open with", ActionMap::Exec),
     ("Open in Neovim", ActionMap::NvimFilepicker),
@@ -12,6 +16,8 @@ const CONTEXT: [(&str, ActionMap); 10] = [
     ("Delete", ActionMap::Delete),
     ("Trash", ActionMap::TrashMoveFile),
     ("Chmod", ActionMap::Chmod),
+    ("Set xattr", ActionMap::SetXattr),
+    ("Remove xattr", ActionMap::RemoveXattr),
     ("New File", ActionMap::NewFile),
     ("New Directory", ActionMap::NewDir),
 ];
@@ -49,27 +55,146 @@ impl_content!(StaticStr, ContextMenu);
 pub struct MoreInfos<'a> {
     file_info: &'a FileInfo,
     opener: &'a Opener,
+    /// Display the home directory as `~` in the paths.
+    tilde_path: bool,
 }
 
 impl<'a> MoreInfos<'a> {
     pub fn new(file_info: &'a FileInfo, opener: &'a Opener) -> Self {
-        Self { file_info, opener }
+        Self {
+            file_info,
+            opener,
+            tilde_path: false,
+        }
+    }
+
+    /// Replace the home directory by `~` in the paths if `collapse` is true.
+    pub fn with_home_collapsed(mut self, collapse: bool) -> Self {
+        self.tilde_path = collapse;
+        self
+    }
+
+    fn display_path(&self, path: &Path) -> String {
+        let path = path.display().to_string();
+        if self.tilde_path {
+            collapse_home(&path).into_owned()
+        } else {
+            path
+        }
     }
 
     /// Informations about the file as a vector of string.
     pub fn to_lines(&self) -> Vec<String> {
         let mut lines = vec![];
 
+        self.path(&mut lines);
         self.owner_group(&mut lines);
         self.perms(&mut lines);
         self.size(&mut lines);
         self.times(&mut lines);
         self.opener(&mut lines);
         self.kind(&mut lines);
+        self.xattrs(&mut lines);
 
         lines
     }
 
+    /// Every information about the file, displayed in the properties view.
+    /// The size of directories is computed recursively, which may be slow.
+    pub fn to_properties(&self) -> Vec<String> {
+        let mut lines = vec![];
+
+        self.path(&mut lines);
+        self.file_type(&mut lines);
+        self.recursive_size(&mut lines);
+        self.owner_group(&mut lines);
+        self.perms(&mut lines);
+        self.octal_perms(&mut lines);
+        self.times(&mut lines);
+        self.changed(&mut lines);
+        self.mime(&mut lines);
+        self.opener(&mut lines);
+        self.kind(&mut lines);
+        self.dimensions(&mut lines);
+        self.xattrs(&mut lines);
+
+        lines
+    }
+
+    fn path(&self, lines: &mut Vec<String>) {
+        lines.push(format!(
+            "Path:        {path}",
+            path = self.display_path(&self.file_info.path)
+        ));
+        if let FileKind::SymbolicLink(_) = self.file_info.file_kind {
+            if let Ok(target) = std::fs::read_link(&self.file_info.path) {
+                lines.push(format!(
+                    "Target:      {target}",
+                    target = self.display_path(&target)
+                ));
+            }
+        }
+    }
+
+    fn file_type(&self, lines: &mut Vec<String>) {
+        lines.push(format!(
+            "Type:        {kind}",
+            kind = self.file_info.file_kind.long_description()
+        ));
+    }
+
+    fn recursive_size(&self, lines: &mut Vec<String>) {
+        let size = if matches!(self.file_info.file_kind, FileKind::Directory) {
+            recursive_size(&self.file_info.path)
+        } else {
+            self.file_info.true_size
+        };
+        lines.push(format!(
+            "Size:        {human} ({size} bytes)",
+            human = human_size(size).trim()
+        ));
+    }
+
+    fn octal_perms(&self, lines: &mut Vec<String>) {
+        if let Ok(metadata) = self.file_info.metadata() {
+            lines.push(format!(
+                "Octal:       {mode:04o}",
+                mode = metadata.permissions().mode() & 0o7777
+            ));
+        }
+    }
+
+    fn changed(&self, lines: &mut Vec<String>) {
+        if let Ok(metadata) = self.file_info.metadata() {
+            let changed = std::time::UNIX_EPOCH
+                + std::time::Duration::from_secs(metadata.ctime().max(0) as u64);
+            if let Ok(dt) = extract_datetime(changed) {
+                lines.push(format!("Changed:     {dt}"))
+            }
+        }
+    }
+
+    fn mime(&self, lines: &mut Vec<String>) {
+        if !matches!(self.file_info.file_kind, FileKind::NormalFile) {
+            return;
+        }
+        if let Some(mime) = mime_type(&self.file_info.path) {
+            lines.push(format!("Mime type:   {mime}"));
+        }
+    }
+
+    fn dimensions(&self, lines: &mut Vec<String>) {
+        if !matches!(
+            ExtensionKind::matcher(&self.file_info.extension.to_lowercase()),
+            ExtensionKind::Image
+        ) {
+            return;
+        }
+        if let Ok((width, height)) = image::image_dimensions(&self.file_info.path) {
+            lines.push(format!("Dimensions:  {width} x {height}"));
+        }
+    }
+
     fn owner_group(&self, lines: &mut Vec<String>) {
         lines.push(format!(
             "Owner/Group: {owner} / {group}",
@@ -130,4 +255,15 @@ impl<'a> MoreInfos<'a> {
             lines.push(format!("Kind:        {kind}"));
         }
     }
+
+    fn xattrs(&self, lines: &mut Vec<String>) {
+        match read_xattrs(&self.file_info.path) {
+            Some(xattrs) if !xattrs.is_empty() => {
+                for xattr in xattrs {
+                    lines.push(format!("Xattr:       {xattr}"));
+                }
+            }
+            _ => lines.push("Xattrs:      none".to_owned()),
+        }
+    }
 }
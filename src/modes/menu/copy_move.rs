@@ -1,10 +1,12 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fmt::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 use std::thread;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use fs_extra;
 use indicatif::{InMemoryTerm, ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
 
@@ -117,7 +119,8 @@ impl CopyMove {
 /// is installed.
 ///
 /// If a file is copied or moved to a folder which already contains a file with the same name,
-/// the copie/moved file has a `_` appended to its name.
+/// it's moved to its path in `targets`, indexed by source path, replacing the existing file or under another name.
+/// Otherwise the copied/moved file has a `_` appended to its name.
 ///
 /// This is done by :
 /// 1. creating a random temporary folder in the destination,
@@ -133,6 +136,7 @@ pub fn copy_move<P>(
     copy_or_move: CopyMove,
     sources: Vec<PathBuf>,
     dest: P,
+    targets: HashMap<PathBuf, PathBuf>,
     width: u16,
     height: u16,
     fm_sender: Arc<Sender<FmEvents>>,
@@ -144,7 +148,7 @@ where
     let handle_progress = move |process_info: fs_extra::TransitProcess| {
         handle_progress_display(&progress_bar, process_info)
     };
-    let conflict_handler = ConflictHandler::new(dest, &sources)?;
+    let conflict_handler = ConflictHandler::new(dest, &sources, targets)?;
 
     let _ = thread::spawn(move || {
         let transfered_bytes = match copy_or_move.copier()(
@@ -175,6 +179,78 @@ where
     Ok(in_mem)
 }
 
+/// Will copy a single file or directory to `target`, whose name may differ from the source one.
+/// A progress bar is displayed.
+/// The file is copied into a temporary folder created next to `target`, then renamed.
+/// An existing `target` file is replaced, an existing directory isn't.
+///
+/// It also sends an event "file copied" once the file is copied.
+pub fn copy_as(
+    source: PathBuf,
+    target: PathBuf,
+    width: u16,
+    height: u16,
+    fm_sender: Arc<Sender<FmEvents>>,
+) -> Result<InMemoryTerm> {
+    let copy_or_move = CopyMove::Copy;
+    let (in_mem, progress_bar, options) = copy_or_move.setup_progress_bar(width, height)?;
+    let handle_progress = move |process_info: fs_extra::TransitProcess| {
+        handle_progress_display(&progress_bar, process_info)
+    };
+    let parent = target
+        .parent()
+        .context("copy as: target should have a parent")?;
+    let filename = source
+        .file_name()
+        .context("copy as: source should have a name")?
+        .to_owned();
+    let temp_dest = ConflictHandler::create_temporary_destination(parent)?;
+
+    let _ = thread::spawn(move || {
+        let transfered_bytes = match copy_or_move.copier()(
+            std::slice::from_ref(&source),
+            &temp_dest,
+            &options,
+            handle_progress,
+        ) {
+            Ok(transfered_bytes) => {
+                if let Err(e) = replace_by(&temp_dest.join(&filename), &target) {
+                    log_info!("Copy as error: {e:?}");
+                    log_line!("Error: {e:?}");
+                }
+                transfered_bytes
+            }
+            Err(e) => {
+                log_info!("Error: {e:?}");
+                log_line!("Error: {e:?}");
+                0
+            }
+        };
+        let _ = std::fs::remove_dir_all(&temp_dest);
+
+        fm_sender.send(FmEvents::Refresh).unwrap_or_default();
+        copy_or_move.log_and_notify(&human_size(transfered_bytes));
+        fm_sender.send(FmEvents::FileCopied).unwrap_or_default();
+    });
+    Ok(in_mem)
+}
+
+/// Rename `copied` to `target`, replacing an existing file.
+///
+/// # Errors
+///
+/// Fails if `target` is a directory: its content would be lost, directories are never replaced.
+pub fn replace_by(copied: &Path, target: &Path) -> Result<()> {
+    if target.is_dir() && !target.is_symlink() {
+        bail!(
+            "{target} is a directory, it won't be replaced",
+            target = target.display()
+        );
+    }
+    std::fs::rename(copied, target)?;
+    Ok(())
+}
+
 /// Deal with conflicting filenames during a copy or a move.
 struct ConflictHandler {
     /// The destination of the files.
@@ -188,12 +264,14 @@ struct ConflictHandler {
     /// Defined to the final destination if there's a conflict.
     /// None otherwise.
     final_dest: Option<PathBuf>,
+    /// Final path of the conflicting files, by filename in the temporary folder, chosen by the user.
+    targets: HashMap<OsString, PathBuf>,
 }
 
 impl ConflictHandler {
     /// Creates a new `ConflictHandler` instance.
     /// We check for conflict and create the temporary folder if needed.
-    fn new<P>(dest: P, sources: &[PathBuf]) -> Result<Self>
+    fn new<P>(dest: P, sources: &[PathBuf], targets: HashMap<PathBuf, PathBuf>) -> Result<Self>
     where
         P: AsRef<std::path::Path>,
     {
@@ -208,10 +286,17 @@ impl ConflictHandler {
             final_dest = None;
         };
 
+        // Sources are copied into the temporary folder under their own name.
+        // They're unique, sources with the same name are refused by `Conflicts::new`.
+        let targets = targets
+            .into_iter()
+            .filter_map(|(source, target)| Some((source.file_name()?.to_owned(), target)))
+            .collect();
         Ok(Self {
             temp_dest,
             has_conflict,
             final_dest,
+            targets,
         })
     }
 
@@ -248,9 +333,13 @@ impl ConflictHandler {
     }
 
     /// Move a single file to `final_dest`.
-    /// If the file already exists in `final_dest` the moved one has enough '_' appended
+    /// If the user chose its final path, it's moved there, replacing any existing file.
+    /// Otherwise, if the file already exists in `final_dest` the moved one has enough '_' appended
     /// to its name to make it unique.
     fn move_single_file_to_dest(&self, file: std::fs::DirEntry) -> Result<()> {
+        if let Some(target) = self.targets.get(&file.file_name()) {
+            return replace_by(&file.path(), target);
+        }
         let mut file_name = file
             .file_name()
             .to_str()
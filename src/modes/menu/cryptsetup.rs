@@ -16,6 +16,7 @@ use crate::{impl_content, impl_selectable, log_info};
 pub enum BlockDeviceAction {
     MOUNT,
     UMOUNT,
+    POWEROFF,
 }
 
 /// get devices list from lsblk
@@ -1,10 +1,12 @@
-use std::{fs::File, path::Path};
+use std::fs::{create_dir_all, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use flate2::read::{GzDecoder, ZlibDecoder};
 use tar::Archive;
 
-use crate::common::{is_in_path, path_to_string, BSDTAR, SEVENZ};
+use crate::common::{is_in_path, path_to_string, tmp_archive_entries_dir, BSDTAR, SEVENZ};
 use crate::io::{execute_and_output, execute_without_output};
 use crate::{log_info, log_line};
 
@@ -43,6 +45,17 @@ pub fn decompress_gz(source: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Read the content of a gz compressed file, which isn't an archive, into a string.
+///
+/// # Errors
+///
+/// It may fail if the file can't be opened or if its content isn't valid gzip or utf-8.
+pub fn read_gz_to_string(source: &Path) -> Result<String> {
+    let mut content = String::new();
+    GzDecoder::new(File::open(source)?).read_to_string(&mut content)?;
+    Ok(content)
+}
+
 /// Decompress a 7z compressed file into its parent directory.
 ///
 /// # Errors
@@ -130,3 +143,116 @@ where
     }
     Err(anyhow::anyhow!("Tar couldn't read the file content"))
 }
+
+/// List the paths of the entries of a tar.something file, without their metadata.
+/// Folders end with a `/`.
+///
+/// # Errors
+///
+/// It may fail if the `bsdtar --list` command returns an error.
+pub fn list_paths_tar<P>(source: P) -> Result<Vec<String>>
+where
+    P: AsRef<Path>,
+{
+    let output = execute_and_output(
+        BSDTAR,
+        ["--list", "--file", path_to_string(&source).as_str()],
+    )
+    .map_err(|_| anyhow::anyhow!("Tar couldn't read the file content"))?;
+    Ok(String::from_utf8(output.stdout)
+        .unwrap_or_default()
+        .lines()
+        .map(std::borrow::ToOwned::to_owned)
+        .collect())
+}
+
+/// Extract a single file of a zip or tar archive into a temporary folder and returns its path.
+/// The entry is its path relative to the archive, as displayed when the archive is previewed as a tree.
+/// The temporary folder is removed when fm exits.
+///
+/// # Errors
+///
+/// It may fail if the archive can't be read, if the entry isn't a file of the archive
+/// or if it can't be written.
+pub fn extract_entry_to_tmp(archive: &Path, entry: &Path) -> Result<PathBuf> {
+    let destination = tmp_archive_entries_dir().join(
+        archive
+            .file_name()
+            .context("extract: archive should have a name")?,
+    );
+    create_dir_all(&destination)?;
+    let is_zip = archive
+        .extension()
+        .is_some_and(|extension| extension.to_string_lossy().to_lowercase() == "zip");
+    if is_zip {
+        extract_zip_entry(archive, entry, &destination)?;
+    } else {
+        extract_tar_entry(archive, entry, &destination)?;
+    }
+    let extracted = destination.join(entry);
+    if !extracted.is_file() {
+        return Err(anyhow!(
+            "extract: {entry} isn't a file",
+            entry = entry.display()
+        ));
+    }
+    Ok(extracted)
+}
+
+/// True if the archive entry `name` has the path `entry`, ignoring the `./` prefixes.
+fn is_same_entry(name: &str, entry: &Path) -> bool {
+    Path::new(name)
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .eq(entry.components())
+}
+
+fn extract_zip_entry(archive: &Path, entry: &Path, destination: &Path) -> Result<()> {
+    let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+    for index in 0..zip.len() {
+        let mut file = zip.by_index(index)?;
+        if file.is_dir() || !is_same_entry(file.name(), entry) {
+            continue;
+        }
+        let target = destination.join(entry);
+        if let Some(parent) = target.parent() {
+            create_dir_all(parent)?;
+        }
+        std::io::copy(&mut file, &mut File::create(&target)?)?;
+        return Ok(());
+    }
+    Err(anyhow!(
+        "extract: {entry} isn't in the archive",
+        entry = entry.display()
+    ))
+}
+
+fn extract_tar_entry(archive: &Path, entry: &Path, destination: &Path) -> Result<()> {
+    let name = list_paths_tar(archive)?
+        .into_iter()
+        .find(|name| is_same_entry(name, entry))
+        .with_context(|| {
+            format!(
+                "extract: {entry} isn't in the archive",
+                entry = entry.display()
+            )
+        })?;
+    let output = execute_and_output(
+        BSDTAR,
+        [
+            "-x",
+            "-f",
+            &path_to_string(&archive),
+            "-C",
+            &path_to_string(&destination),
+            &name,
+        ],
+    )?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "extract: {BSDTAR} couldn't extract {entry}",
+            entry = entry.display()
+        ));
+    }
+    Ok(())
+}
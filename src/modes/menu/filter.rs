@@ -1,16 +1,18 @@
-use std::{borrow::Borrow, fmt::Display};
+use std::{borrow::Borrow, fmt::Display, path::PathBuf};
 
 use regex::Regex;
 
 use crate::modes::{FileInfo, FileKind};
 
 /// Different kinds of filters.
-/// By extension, by name, only the directory or all files.
+/// By extension, by name, only the directory, only the flagged files or all files.
 #[derive(Clone)]
 pub enum FilterKind {
     Extension(String),
     Name(String),
     Directory,
+    /// Files flagged when the filter was set.
+    FlaggedOnly(Vec<PathBuf>),
     All,
 }
 
@@ -37,6 +39,7 @@ impl FilterKind {
             Self::Extension(ext) => Self::filter_by_extension(fileinfo, ext, keep_dirs),
             Self::Name(filename) => Self::filter_by_name(fileinfo, filename, keep_dirs),
             Self::Directory => Self::filter_directory(fileinfo),
+            Self::FlaggedOnly(flagged) => Self::filter_flagged(fileinfo, flagged),
             Self::All => true,
         }
     }
@@ -60,6 +63,12 @@ impl FilterKind {
     fn filter_directory(fileinfo: &FileInfo) -> bool {
         matches!(fileinfo.file_kind, FileKind::Directory)
     }
+
+    fn filter_flagged(fileinfo: &FileInfo, flagged: &[PathBuf]) -> bool {
+        flagged
+            .iter()
+            .any(|path| path.as_path() == fileinfo.path.as_ref())
+    }
 }
 
 /// Format the corresponding variant to be printed in the second line.
@@ -70,6 +79,7 @@ impl Display for FilterKind {
             Self::Directory => write!(f, "Filter: Directory only"),
             Self::Extension(s) => write!(f, "Filter: by extension \"{s}\""),
             Self::Name(s) => write!(f, "Filter: by name \"{s}\""),
+            Self::FlaggedOnly(_) => write!(f, "Filter: flagged only"),
             Self::All => write!(f, ""),
         }
     }
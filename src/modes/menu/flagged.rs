@@ -21,6 +21,8 @@ pub struct Flagged {
     pub content: Vec<PathBuf>,
     /// The index of the selected file. Used to jump.
     pub index: usize,
+    /// The flagged files when their total size was computed and this size in bytes.
+    total_size: Option<(Vec<PathBuf>, u64)>,
 }
 
 impl Flagged {
@@ -66,6 +68,13 @@ impl Flagged {
         }
     }
 
+    /// Remove the path from the content if it's flagged.
+    pub fn remove(&mut self, path: &Path) {
+        if let Ok(pos) = self.content.binary_search(&path.to_path_buf()) {
+            self.remove_index(pos)
+        }
+    }
+
     fn remove_index(&mut self, index: usize) {
         self.content.remove(index);
         if self.index >= self.len() {
@@ -112,6 +121,21 @@ impl Flagged {
         });
     }
 
+    /// Records the total size of those flagged files.
+    pub fn set_total_size(&mut self, flagged: Vec<PathBuf>, size: u64) {
+        self.total_size = Some((flagged, size));
+    }
+
+    /// Total size in bytes of the flagged files.
+    /// None if it wasn't computed or if the flagged files changed since.
+    #[must_use]
+    pub fn total_size(&self) -> Option<u64> {
+        match &self.total_size {
+            Some((flagged, size)) if flagged == &self.content => Some(*size),
+            _ => None,
+        }
+    }
+
     /// Returns the flagged files as a vector of strings
     pub fn as_strings(&self) -> Vec<String> {
         self.content
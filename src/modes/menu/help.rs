@@ -98,20 +98,46 @@ Different modes for the main window
 Different modes for the bottom window
 {menu_modes}
 ",
-        quit = action_descriptions!(Quit, Help),
+        quit = action_descriptions!(Quit, QuitCdSelected, QuitWithoutCd, Help),
         navigation = action_descriptions!(
-            MoveLeft, MoveRight, MoveUp, MoveDown, KeyHome, End, PageUp, PageDown, Tab
+            MoveLeft,
+            MoveRight,
+            MoveUp,
+            MoveDown,
+            ReorderUp,
+            ReorderDown,
+            MoveWordLeft,
+            MoveWordRight,
+            KeyHome,
+            End,
+            PageUp,
+            PageDown,
+            Tab,
+            EnterInOtherTab,
+            FollowSymlink,
+            CdToRealPath
         ),
         actions = action_descriptions!(
             ToggleDualPane,
             TogglePreviewSecond,
+            ToggleFollowSelection,
+            ToggleRelativeLineNumbers,
+            PinPreview,
             ToggleDisplayFull,
+            ToggleGroupByExtension,
             ToggleHidden,
+            ToggleHiddenHere,
+            ToggleSortReversed,
+            ToggleTildePath,
             Shell,
+            ToggleTerminalPane,
             OpenFile,
+            OpenNotes,
+            OpenArchiveEntry,
             NvimFilepicker,
             NvimSetAddress,
             Preview,
+            BinaryCycleWidth,
             Back,
             Home,
             GoRoot,
@@ -122,11 +148,19 @@ Different modes for the bottom window
             TempMarksJump,
             SearchNext,
             FuzzyFind,
+            FuzzyFindAll,
             FuzzyFindLine,
             FuzzyFindHelp,
+            FuzzyFindCommand,
             RefreshView,
+            RefreshChanges,
             CopyFilename,
             CopyFilepath,
+            CopyCurrentDir,
+            CopyImage,
+            SetWallpaper,
+            CopyPreviewVisible,
+            CopyPreviewAll,
             OpenConfig,
             CloudDrive,
         ),
@@ -134,38 +168,82 @@ Different modes for the bottom window
         flagged_actions = action_descriptions!(
             ToggleFlag,
             FlagAll,
+            ToggleFlaggedOnly,
             ClearFlags,
             ReverseFlags,
+            FlagOlder,
+            FlagNewer,
+            ToggleVisual,
             Symlink,
             CopyPaste,
+            CopyAs,
             CutPaste,
+            CopyToOtherTab,
+            MoveToOtherTab,
+            CompareTabs,
             Delete,
+            DeletePermanently,
             TrashMoveFile,
+            CancelRemoval,
             Compress,
+            ConvertImages,
+            SplitFile,
+            JoinParts,
             FlaggedToClipboard,
-            FlaggedFromClipboard
+            FlaggedFromClipboard,
+            FlaggedFromGrep,
+            FlaggedSave,
+            FlaggedLoad,
+            FlaggedTotalSize,
+            TouchFlagged
         ),
         trash_actions = action_descriptions!(TrashOpen, TrashEmpty),
         tree_actions = action_descriptions!(Tree, TreeFold, TreeFoldAll, TreeUnFoldAll),
-        display_modes = action_descriptions!(ResetMode, Tree, Preview),
+        display_modes = action_descriptions!(
+            ResetMode,
+            Tree,
+            CycleDisplayMode,
+            Preview,
+            Pager,
+            QuickLook,
+            PreviewScriptOutput,
+            Properties,
+            GitDiff,
+            GitLog,
+            DiskUsage
+        ),
         menu_modes = action_descriptions!(
             Chmod,
             Exec,
             NewDir,
+            NewDirAndEnter,
             NewFile,
             Rename,
             Cd,
             RegexMatch,
+            GlobMatch,
             Sort,
             History,
             Shortcut,
             EncryptedDrive,
             RemovableDevices,
             Search,
+            ToggleSearchRegex,
             Action,
             Bulk,
+            ExifRename,
+            RenameLowercase,
+            RenameUppercase,
+            RenameSnakeCase,
+            RenameUrlDecode,
+            NumberedRename,
+            RenameExtension,
+            SetXattr,
+            RemoveXattr,
             TuiMenu,
             CliMenu,
+            ShellStream,
+            ShellDryRun,
             RemoteMount,
             Filter,
             DisplayFlagged,
@@ -0,0 +1,219 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+
+use anyhow::{bail, Result};
+use indicatif::{InMemoryTerm, ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+use crate::common::{is_in_path, CONVERT, FFMPEG, MAGICK};
+use crate::event::FmEvents;
+use crate::io::{execute_and_output_no_log, CowStr, DrawMenu, Extension};
+use crate::modes::extract_extension;
+use crate::{impl_content, impl_selectable, log_info, log_line};
+
+/// Image formats the flagged images can be converted to.
+#[derive(Debug)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Webp,
+    Avif,
+    Gif,
+}
+
+impl ImageFormat {
+    fn to_str(&self) -> &'static str {
+        match self {
+            Self::Png => "PNG:  .png",
+            Self::Jpeg => "JPEG: .jpg",
+            Self::Webp => "WEBP: .webp",
+            Self::Avif => "AVIF: .avif",
+            Self::Gif => "GIF:  .gif",
+        }
+    }
+
+    /// Extension of the converted images, without the dot.
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::Webp => "webp",
+            Self::Avif => "avif",
+            Self::Gif => "gif",
+        }
+    }
+}
+
+/// Holds the formats available for image conversion.
+#[derive(Debug, Default)]
+pub struct ImageConverter {
+    content: Vec<ImageFormat>,
+    pub index: usize,
+}
+
+impl ImageConverter {
+    pub fn setup(&mut self) {
+        self.content = vec![
+            ImageFormat::Png,
+            ImageFormat::Jpeg,
+            ImageFormat::Webp,
+            ImageFormat::Avif,
+            ImageFormat::Gif,
+        ];
+    }
+
+    /// Extension of the selected format.
+    pub fn selected_extension(&self) -> Option<&'static str> {
+        self.selected().map(|format| format.extension())
+    }
+}
+
+impl_selectable!(ImageConverter);
+impl_content!(ImageFormat, ImageConverter);
+
+impl CowStr for ImageFormat {
+    fn cow_str(&self) -> Cow<'_, str> {
+        self.to_str().into()
+    }
+}
+
+impl DrawMenu<ImageFormat> for ImageConverter {}
+
+/// Program used to convert images: ImageMagick (version 7 or 6) or ffmpeg.
+#[derive(Clone, Copy)]
+enum ConvertTool {
+    Magick,
+    Convert,
+    Ffmpeg,
+}
+
+impl ConvertTool {
+    /// First installed tool, ImageMagick being preferred.
+    fn detect() -> Option<Self> {
+        if is_in_path(MAGICK) {
+            Some(Self::Magick)
+        } else if is_in_path(CONVERT) {
+            Some(Self::Convert)
+        } else if is_in_path(FFMPEG) {
+            Some(Self::Ffmpeg)
+        } else {
+            None
+        }
+    }
+
+    fn convert(&self, source: &str, dest: &str) -> Result<()> {
+        let output = match self {
+            Self::Magick => execute_and_output_no_log(MAGICK, [source, dest])?,
+            Self::Convert => execute_and_output_no_log(CONVERT, [source, dest])?,
+            Self::Ffmpeg => execute_and_output_no_log(
+                FFMPEG,
+                ["-nostdin", "-loglevel", "error", "-n", "-i", source, dest],
+            )?,
+        };
+        if !output.status.success() {
+            bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(())
+    }
+}
+
+/// Converts images in a thread, displaying its progress in a progress bar.
+///
+/// Non images are skipped and existing files are never overwritten.
+/// When the thread is done, [`FmEvents::ConversionFinished`] is sent with the converted images.
+pub struct ConvertWorker {
+    progress: InMemoryTerm,
+}
+
+impl ConvertWorker {
+    /// Start converting the images among `sources` into `dest_dir`, with the format of this extension.
+    pub fn start(
+        extension: &'static str,
+        sources: Vec<PathBuf>,
+        dest_dir: PathBuf,
+        width: u16,
+        height: u16,
+        fm_sender: Arc<Sender<FmEvents>>,
+    ) -> Result<Self> {
+        let Some(tool) = ConvertTool::detect() else {
+            bail!("Converting images requires {MAGICK}, {CONVERT} or {FFMPEG}");
+        };
+        let images: Vec<PathBuf> = sources.into_iter().filter(|path| is_image(path)).collect();
+        if images.is_empty() {
+            bail!("No image to convert");
+        }
+        let (progress, progress_bar) = Self::setup_progress_bar(width, height)?;
+        thread::spawn(move || {
+            progress_bar.set_length(images.len() as u64);
+            let mut converted = vec![];
+            for source in images.iter() {
+                match convert_image(tool, source, &dest_dir, extension) {
+                    Ok(dest) => converted.push(dest),
+                    Err(error) => {
+                        log_info!("convert {path}: {error}", path = source.display())
+                    }
+                }
+                progress_bar.inc(1);
+            }
+            log_line!(
+                "Converted {nb} of {total} images to {extension}",
+                nb = converted.len(),
+                total = images.len()
+            );
+            progress_bar.finish_and_clear();
+            fm_sender.send(FmEvents::Refresh).unwrap_or_default();
+            fm_sender
+                .send(FmEvents::ConversionFinished(converted))
+                .unwrap_or_default();
+        });
+        Ok(Self { progress })
+    }
+
+    fn setup_progress_bar(width: u16, height: u16) -> Result<(InMemoryTerm, ProgressBar)> {
+        let width = width.saturating_sub(4);
+        let in_mem = InMemoryTerm::new(height, width);
+        let pb = ProgressBar::with_draw_target(
+            Some(0),
+            ProgressDrawTarget::term_like(Box::new(in_mem.clone())),
+        );
+        pb.set_style(
+            ProgressStyle::with_template("{spinner} convert [{elapsed}] [{wide_bar}] {pos}/{len}")?
+                .progress_chars("#>-"),
+        );
+        Ok((in_mem, pb))
+    }
+
+    /// Content of the progress bar.
+    pub fn contents(&self) -> String {
+        self.progress.contents()
+    }
+}
+
+fn is_image(path: &Path) -> bool {
+    path.is_file()
+        && matches!(
+            Extension::matcher(&extract_extension(path).to_lowercase()),
+            Extension::Bitmap
+        )
+}
+
+/// Convert a single image into `dest_dir`, keeping its name and replacing its extension.
+/// Returns the path of the converted image.
+fn convert_image(
+    tool: ConvertTool,
+    source: &Path,
+    dest_dir: &Path,
+    extension: &str,
+) -> Result<PathBuf> {
+    let Some(stem) = source.file_stem() else {
+        bail!("no filename");
+    };
+    let dest = dest_dir.join(format!("{stem}.{extension}", stem = stem.to_string_lossy()));
+    if dest.exists() {
+        bail!("{dest} already exists", dest = dest.display());
+    }
+    tool.convert(&source.to_string_lossy(), &dest.to_string_lossy())?;
+    Ok(dest)
+}
@@ -75,6 +75,28 @@ impl Input {
         }
     }
 
+    /// Move the cursor to the start of the previous word.
+    /// Separators (space, slash, dot etc.) before the cursor are skipped first.
+    pub fn cursor_word_left(&mut self) {
+        while self.cursor_index > 0 && is_word_separator(&self.chars[self.cursor_index - 1]) {
+            self.cursor_index -= 1;
+        }
+        while self.cursor_index > 0 && !is_word_separator(&self.chars[self.cursor_index - 1]) {
+            self.cursor_index -= 1;
+        }
+    }
+
+    /// Move the cursor to the end of the next word.
+    /// Separators (space, slash, dot etc.) after the cursor are skipped first.
+    pub fn cursor_word_right(&mut self) {
+        while self.cursor_index < self.len() && is_word_separator(&self.chars[self.cursor_index]) {
+            self.cursor_index += 1;
+        }
+        while self.cursor_index < self.len() && !is_word_separator(&self.chars[self.cursor_index]) {
+            self.cursor_index += 1;
+        }
+    }
+
     /// Backspace, delete the char under the cursor and move left
     pub fn delete_char_left(&mut self) {
         if self.cursor_index > 0 && !self.chars.is_empty() {
@@ -116,3 +138,9 @@ impl Input {
         self.cursor_end();
     }
 }
+
+/// True if the grapheme doesn't contain any alphanumeric char.
+/// Words are delimited by those separators.
+fn is_word_separator(grapheme: &str) -> bool {
+    !grapheme.chars().any(char::is_alphanumeric)
+}
@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context, Result};
 
-use crate::common::{read_lines, tilde, MARKS_FILEPATH};
+use crate::common::{read_lines, tilde, MARKS_FILEPATH, RANGER_BOOKMARKS_FILEPATH};
 use crate::io::DrawMenu;
 use crate::{impl_content, impl_selectable, log_info, log_line};
 
@@ -112,6 +112,38 @@ impl Marks {
         Ok(())
     }
 
+    /// Import the bookmarks of ranger (~/.local/share/ranger/bookmarks).
+    /// They use the same `char:path` format as fm.
+    /// Chars already bound are skipped, so are the ' bookmark (ranger's last position) and `:`.
+    /// The merged marks are saved and the number of imported marks is logged.
+    ///
+    /// # Errors
+    ///
+    /// It may fail if the bookmarks file can't be read or if writing to the marks file fails.
+    pub fn import_ranger_bookmarks(&mut self) -> Result<usize> {
+        let bookmarks_path = PathBuf::from(tilde(RANGER_BOOKMARKS_FILEPATH).as_ref());
+        let lines = read_lines(&bookmarks_path).with_context(|| {
+            format!(
+                "Couldn't read ranger bookmarks {path}",
+                path = bookmarks_path.display()
+            )
+        })?;
+        let mut imported = 0;
+        for (ch, path) in lines.filter_map(|line| Self::parse_line(line).ok()) {
+            if ch == '\'' || ch == ':' || self.get(ch).is_some() {
+                continue;
+            }
+            self.content.push((ch, path));
+            self.used_chars.insert(ch);
+            imported += 1;
+        }
+        if imported > 0 {
+            self.save_marks()?;
+        }
+        log_line!("Imported {imported} marks from ranger bookmarks");
+        Ok(imported)
+    }
+
     fn update_mark(&mut self, ch: char, path: &Path) {
         let mut found_index = None;
         for (index, (k, _)) in self.content.iter().enumerate() {
@@ -2,6 +2,7 @@ mod bulkrename;
 mod cli_menu;
 mod completion;
 mod compress;
+mod conflict;
 mod context;
 mod copy_move;
 mod cryptsetup;
@@ -10,6 +11,7 @@ mod filter;
 mod flagged;
 mod help;
 mod history;
+mod image_convert;
 mod input;
 mod iso;
 mod marks;
@@ -18,30 +20,36 @@ mod nvim;
 mod password;
 mod permissions;
 mod picker;
+mod protected;
 mod regex;
 mod remote;
 mod removable_devices;
+mod removal;
 mod search;
 mod shortcut;
 mod sort;
 mod temp_marks;
 mod trash;
 mod tui_menu;
+mod xattrs;
 
-pub use bulkrename::Bulk;
+pub use bulkrename::{Bulk, CaseTransform, NumberedSequence};
 pub use cli_menu::{CliApplications, Execute, TerminalApplications};
 pub use completion::{Completion, InputCompleted};
 pub use compress::Compresser;
+pub use conflict::{ConflictChoice, Conflicts};
 pub use context::{ContextMenu, MoreInfos};
-pub use copy_move::{copy_move, CopyMove};
+pub use copy_move::{copy_as, copy_move, replace_by, CopyMove};
 pub use cryptsetup::{lsblk_and_cryptsetup_installed, BlockDeviceAction, CryptoDeviceOpener};
 pub use decompress::{
-    decompress_7z, decompress_gz, decompress_xz, decompress_zip, list_files_tar, list_files_zip,
+    decompress_7z, decompress_gz, decompress_xz, decompress_zip, extract_entry_to_tmp,
+    list_files_tar, list_files_zip, list_paths_tar, read_gz_to_string,
 };
 pub use filter::FilterKind;
 pub use flagged::Flagged;
 pub use help::help_string;
 pub use history::History;
+pub use image_convert::{ConvertWorker, ImageConverter};
 pub use input::Input;
 pub use iso::IsoDevice;
 pub use marks::Marks;
@@ -50,12 +58,15 @@ pub use nvim::nvim;
 pub use password::{PasswordHolder, PasswordKind, PasswordUsage};
 pub use permissions::{parse_input_permission, Permissions, MAX_MODE};
 pub use picker::*;
-pub use regex::{regex_flagger, CaseDependantRegex};
+pub use protected::{Guarded, ProtectedGuard};
+pub use regex::{glob_flagger, glob_to_regex, regex_flagger, CaseDependantRegex};
 pub use remote::Remote;
-pub use removable_devices::RemovableDevices;
-pub use search::Search;
+pub use removable_devices::{Removable, RemovableDevices};
+pub use removal::{Removal, RemovalWorker};
+pub use search::{Search, SearchKind};
 pub use shortcut::Shortcut;
 pub use sort::SortKind;
 pub use temp_marks::*;
 pub use trash::Trash;
 pub use tui_menu::{open_tui_program, TuiApplications};
+pub use xattrs::{read_xattrs, remove_xattr, set_xattr};
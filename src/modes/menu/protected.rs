@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::is_protected;
+use crate::modes::CopyMove;
+
+/// Destructive action waiting for the user to confirm the protected files it modifies.
+pub enum Guarded {
+    /// Delete the flagged files.
+    Delete,
+    /// Move the flagged files to the trash.
+    Trash,
+    /// Rename the files of the bulk rename.
+    BulkRename,
+    /// Copy the source to the target, overwriting it.
+    CopyAs(PathBuf, PathBuf),
+    /// Copy or move the sources to the destination, conflicting files being moved to their target.
+    Transfer(CopyMove, Vec<PathBuf>, PathBuf, HashMap<PathBuf, PathBuf>),
+    /// Save the flagged files to the path, overwriting it.
+    SaveFlagged(PathBuf),
+}
+
+/// Protected files modified by a destructive action.
+/// The user types the full path of every one of them, in order, before the action runs.
+/// See `protected_paths` in the config file.
+pub struct ProtectedGuard {
+    /// Protected paths not confirmed yet. The first one is asked to the user.
+    pending: Vec<PathBuf>,
+    action: Guarded,
+}
+
+impl ProtectedGuard {
+    /// Guard the action if any of `paths` is protected.
+    /// Otherwise the action is given back since it can run at once.
+    pub fn new(paths: &[PathBuf], action: Guarded) -> Result<Self, Guarded> {
+        let mut pending: Vec<PathBuf> = vec![];
+        for path in paths.iter().filter(|path| is_protected(path)) {
+            if !pending.contains(path) {
+                pending.push(path.to_owned());
+            }
+        }
+        if pending.is_empty() {
+            Err(action)
+        } else {
+            Ok(Self { pending, action })
+        }
+    }
+
+    /// Protected path the user has to type.
+    pub fn current(&self) -> Option<&Path> {
+        self.pending.first().map(|path| path.as_path())
+    }
+
+    /// Number of protected paths not confirmed yet.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// True if every protected path is confirmed.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Confirm the current protected path if `typed` is its full path.
+    /// Returns false if it doesn't match.
+    pub fn confirm(&mut self, typed: &Path) -> bool {
+        if self.current() != Some(typed) {
+            return false;
+        }
+        self.pending.remove(0);
+        true
+    }
+
+    /// The guarded action.
+    pub fn into_action(self) -> Guarded {
+        self.action
+    }
+}
@@ -26,6 +26,59 @@ pub fn regex_flagger(input_string: &str, paths: &[&Path], flagged: &mut Flagged)
     Ok(())
 }
 
+/// Flag every file matching a typed glob in current directory.
+/// Flags are added to the existing ones.
+/// Returns the number of newly flagged files.
+///
+/// # Errors
+///
+/// It may fail if the glob can't be compiled, ie. if a class range is reversed like `[z-a]`.
+/// It may also fail if a file in the directory has a filename which can't be decoded as utf-8.
+pub fn glob_flagger(input_string: &str, paths: &[&Path], flagged: &mut Flagged) -> Result<usize> {
+    let regex = CaseDependantRegex::new(&glob_to_regex(input_string))?;
+    let mut nb_flagged = 0;
+    for path in paths {
+        if regex.is_match(filename_from_path(path)?) && !flagged.contains(path) {
+            flagged.push(path.to_path_buf());
+            nb_flagged += 1;
+        }
+    }
+    Ok(nb_flagged)
+}
+
+/// Translate a glob into an equivalent regular expression matching the whole filename.
+/// `*` matches any sequence of chars, `?` any single char and `[...]` a class of chars (`[!...]` is negated).
+/// Ranges like `[a-z]` are kept in classes.
+/// Every other char is matched literally.
+pub fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut in_class = false;
+    for c in glob.chars() {
+        match c {
+            '*' if !in_class => regex.push_str(".*"),
+            '?' if !in_class => regex.push('.'),
+            '[' if !in_class => {
+                in_class = true;
+                regex.push('[');
+            }
+            '!' if in_class && regex.ends_with('[') => regex.push('^'),
+            ']' if in_class => {
+                in_class = false;
+                regex.push(']');
+            }
+            '-' if in_class => regex.push('-'),
+            '^' | '&' | '~' | '[' | '\\' if in_class => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            _ if in_class => regex.push(c),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
 /// Case dependant regular expression.
 ///
 /// It holds an input string (the original regular expression) and a regular expression.
@@ -55,6 +108,19 @@ impl CaseDependantRegex {
         })
     }
 
+    /// Creates a new case dependant expression matching the input string literally.
+    /// Every char of the input is escaped, the input string itself is kept for display.
+    ///
+    /// # Errors
+    ///
+    /// It may fail if the escaped expression is too large to be compiled.
+    pub fn literal(input_string: &str) -> Result<Self> {
+        Ok(Self {
+            input_string: input_string.to_string(),
+            regex: Self::complete_regex(&regex::escape(input_string))?,
+        })
+    }
+
     /// True if the input string is empty.
     pub fn is_empty(&self) -> bool {
         self.input_string.is_empty()
@@ -84,3 +150,56 @@ impl std::fmt::Display for CaseDependantRegex {
         f.write_str(&self.input_string)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(glob: &str, filename: &str) -> bool {
+        CaseDependantRegex::new(&glob_to_regex(glob))
+            .expect("glob should compile")
+            .is_match(filename)
+    }
+
+    #[test]
+    fn test_glob_wildcards() {
+        assert!(matches("*.rs", "main.rs"));
+        assert!(!matches("*.rs", "main.rs.bak"));
+        assert!(matches("file?.txt", "file1.txt"));
+        assert!(!matches("file?.txt", "file10.txt"));
+    }
+
+    #[test]
+    fn test_glob_literal_chars() {
+        assert!(matches("a+b (1).txt", "a+b (1).txt"));
+        assert!(!matches("a.b", "axb"));
+        assert!(matches("my-file", "my-file"));
+    }
+
+    #[test]
+    fn test_glob_class_range() {
+        assert!(matches("file[0-9].txt", "file5.txt"));
+        assert!(!matches("file[0-9].txt", "filea.txt"));
+        assert!(!matches("file[0-9].txt", "file-.txt"));
+        assert!(matches("[a-c]*", "beta"));
+        assert!(!matches("[a-c]*", "delta"));
+    }
+
+    #[test]
+    fn test_glob_negated_class() {
+        assert!(matches("[!0-9]*", "abc"));
+        assert!(!matches("[!0-9]*", "1bc"));
+    }
+
+    #[test]
+    fn test_glob_class_special_chars() {
+        assert!(matches("[a^]", "^"));
+        assert!(matches("[-a]", "-"));
+        assert!(matches("[a&&b]", "&"));
+    }
+
+    #[test]
+    fn test_glob_reversed_range_fails() {
+        assert!(CaseDependantRegex::new(&glob_to_regex("[z-a]")).is_err());
+    }
+}
@@ -1,17 +1,19 @@
 use std::borrow::Cow;
 use std::fmt::Display;
 use std::io::Read;
+use std::path::Path;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 
 use crate::common::{
-    current_uid, filename_from_path, is_dir_empty, is_in_path, EJECT_EXECUTABLE, GIO, MKDIR, MOUNT,
+    current_uid, filename_from_path, is_dir_empty, is_in_path, EJECT_EXECUTABLE, GIO, LSBLK, MKDIR,
+    MOUNT, UDISKSCTL, UMOUNT,
 };
 use crate::impl_content;
 use crate::impl_selectable;
 use crate::io::{
-    drop_sudo_privileges, execute_and_output, execute_sudo_command, reset_sudo_faillock,
-    set_sudo_session, CowStr, DrawMenu,
+    drop_sudo_privileges, execute_and_capture_output, execute_and_output, execute_sudo_command,
+    reset_sudo_faillock, set_sudo_session, CowStr, DrawMenu,
 };
 use crate::modes::{MountCommands, MountRepr, PasswordHolder};
 use crate::{log_info, log_line};
@@ -66,6 +68,20 @@ impl RemovableDevices {
     fn usb_from_builder() -> Vec<Removable> {
         UsbDevicesBuilder::list_usb_disks().unwrap_or_else(|_| vec![])
     }
+
+    /// Select the device if it's already known, add it and select it otherwise.
+    pub fn push_and_select(&mut self, removable: Removable) {
+        if let Some(index) = self
+            .content
+            .iter()
+            .position(|known| known.name == removable.name)
+        {
+            self.index = index;
+        } else {
+            self.content.push(removable);
+            self.index = self.content.len() - 1;
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -146,6 +162,20 @@ impl Removable {
         }
     }
 
+    /// Creates a device from a filesystem label or UUID.
+    /// It doesn't have to be removable, any block device with a label or an UUID can be mounted.
+    ///
+    /// # Errors
+    ///
+    /// Fails if no device has this label or UUID.
+    pub fn from_label_or_uuid(identifier: &str) -> Result<Self> {
+        let volume = UsbDevicesBuilder::volume_from_label_or_uuid(identifier)
+            .with_context(|| format!("No device with label or UUID {identifier}"))?;
+        let proc_mounts = UsbDevicesBuilder::read_proc_mounts()?;
+        let mount_point = UsbDevicesBuilder::get_mount_point_for_volume(&proc_mounts, &volume);
+        Ok(Self::from_usb(volume, mount_point, false))
+    }
+
     /// Format itself as a valid `gio mount $device` argument.
     fn format_for_gio(&self) -> String {
         match self.kind {
@@ -190,6 +220,78 @@ impl Removable {
         Ok(umount)
     }
 
+    /// Unmount the usb device if it's mounted, then power off its disk with `udisksctl power-off`.
+    /// A powered off disk is no longer spun up and can be removed safely.
+    /// Disks which don't support it stay unmounted and the error is logged.
+    /// Returns true if the disk was powered off.
+    pub fn power_off(&mut self, password: &mut PasswordHolder) -> Result<bool> {
+        if !self.is_usb() {
+            log_line!("Only usb disks can be powered off");
+            return Ok(false);
+        }
+        if !is_in_path(UDISKSCTL) {
+            log_line!("{UDISKSCTL} is required to power off a device");
+            return Ok(false);
+        }
+        let success = set_sudo_session(password)?;
+        password.reset();
+        if !success {
+            return Ok(false);
+        }
+        if self.is_mounted {
+            let (success, stdout, stderr) = execute_sudo_command(&[UMOUNT, self.path.as_str()])?;
+            log_info!("umount: success {success} -- stdout {stdout} -- stderr {stderr}");
+            if !success {
+                log_line!("Couldn't unmount {name}: {stderr}", name = self.name);
+                reset_sudo_faillock()?;
+                drop_sudo_privileges()?;
+                return Ok(false);
+            }
+            self.is_mounted = false;
+        }
+        let disk = self.disk();
+        let (success, stdout, stderr) =
+            execute_sudo_command(&[UDISKSCTL, "power-off", "-b", disk.as_str()])?;
+        log_info!("power-off: success {success} -- stdout {stdout} -- stderr {stderr}");
+        drop_sudo_privileges()?;
+        if success {
+            self.is_ejected = true;
+            log_line!("Powered off {disk}. It can be removed safely.");
+        } else {
+            log_line!(
+                "Unmounted {name} but couldn't power off {disk}: {stderr}",
+                name = self.name,
+                stderr = stderr.trim()
+            );
+        }
+        Ok(success)
+    }
+
+    /// Disk holding the volume, like `/dev/sdd` for `/dev/sdd1` or `/dev/nvme0n1` for `/dev/nvme0n1p1`.
+    /// The parent is read from `lsblk`, then from `/sys/class/block`. A volume without parent is a disk itself.
+    fn disk(&self) -> String {
+        let volume = self.format_for_gio();
+        let parent = execute_and_capture_output(LSBLK, &["-no", "PKNAME", &volume])
+            .ok()
+            .map(|output| output.trim().to_owned())
+            .filter(|parent| !parent.is_empty())
+            .or_else(|| Self::parent_from_sysfs(&volume));
+        match parent {
+            Some(parent) => format!("/dev/{parent}"),
+            None => volume,
+        }
+    }
+
+    /// Name of the disk holding a partition, read from `/sys/class/block/<partition>/..`.
+    fn parent_from_sysfs(volume: &str) -> Option<String> {
+        let partition = Path::new("/sys/class/block").join(Path::new(volume).file_name()?);
+        if !partition.join("partition").exists() {
+            return None;
+        }
+        let disk = std::fs::canonicalize(partition.join("..")).ok()?;
+        Some(disk.file_name()?.to_string_lossy().into_owned())
+    }
+
     /// True iff the device is an usb disk.
     pub fn is_usb(&self) -> bool {
         matches!(self.kind, RemovableKind::Usb)
@@ -346,6 +448,20 @@ impl UsbDevicesBuilder {
         Ok(volumes)
     }
 
+    /// Resolve a volume path (like /dev/sdd1) from a filesystem label or UUID.
+    /// udev creates links for both in `/dev/disk/by-label` and `/dev/disk/by-uuid`.
+    /// Spaces in labels are escaped by udev.
+    fn volume_from_label_or_uuid(identifier: &str) -> Option<String> {
+        let escaped_label = identifier.replace(' ', "\\x20");
+        [
+            std::path::Path::new("/dev/disk/by-uuid").join(identifier),
+            std::path::Path::new("/dev/disk/by-label").join(escaped_label),
+        ]
+        .iter()
+        .find_map(|link| std::fs::canonicalize(link).ok())
+        .map(|volume| volume.to_string_lossy().to_string())
+    }
+
     /// Extract the mount point of a volume
     fn get_mount_point_for_volume(proc_mount: &str, volume: &str) -> Option<String> {
         for line in proc_mount.lines().filter(|line| line.starts_with(volume)) {
@@ -0,0 +1,172 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+
+use anyhow::Result;
+use indicatif::{InMemoryTerm, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use walkdir::WalkDir;
+
+use crate::event::FmEvents;
+use crate::modes::Trash;
+use crate::{log_info, log_line};
+
+/// How the flagged files are removed.
+pub enum Removal {
+    /// Recursively and permanently deleted.
+    Delete,
+    /// Moved to the trash.
+    Trash(Trash),
+}
+
+impl Removal {
+    fn verb(&self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Trash(_) => "trash",
+        }
+    }
+}
+
+/// Removes files in a thread, displaying its progress in a progress bar.
+///
+/// Deletion progress is counted in files, since every file and folder is removed one by one.
+/// Trash progress is counted in flagged files.
+/// The removal can be canceled between two files. Removed files aren't restored.
+/// When the thread is done, [`FmEvents::RemovalFinished`] is sent.
+pub struct RemovalWorker {
+    progress: InMemoryTerm,
+    cancel: Arc<AtomicBool>,
+}
+
+impl RemovalWorker {
+    /// Start removing the `sources` in a thread.
+    pub fn start(
+        removal: Removal,
+        sources: Vec<PathBuf>,
+        width: u16,
+        height: u16,
+        fm_sender: Arc<Sender<FmEvents>>,
+    ) -> Result<Self> {
+        let (progress, progress_bar) = Self::setup_progress_bar(removal.verb(), width, height)?;
+        let cancel = Arc::new(AtomicBool::new(false));
+        let canceled = Arc::clone(&cancel);
+        thread::spawn(move || {
+            let verb = removal.verb();
+            let removed = match removal {
+                Removal::Delete => delete_all(&sources, &progress_bar, &canceled),
+                Removal::Trash(mut trash) => {
+                    trash_all(&mut trash, &sources, &progress_bar, &canceled)
+                }
+            };
+            if canceled.load(Ordering::Relaxed) {
+                log_line!(
+                    "Canceled {verb}: {removed} of {nb} removed",
+                    nb = progress_bar.length().unwrap_or_default()
+                );
+            } else {
+                log_line!(
+                    "{verb}: {removed} of {nb} files removed",
+                    nb = progress_bar.length().unwrap_or_default()
+                );
+            }
+            progress_bar.finish_and_clear();
+            fm_sender.send(FmEvents::Refresh).unwrap_or_default();
+            fm_sender
+                .send(FmEvents::RemovalFinished)
+                .unwrap_or_default();
+        });
+        Ok(Self { progress, cancel })
+    }
+
+    fn setup_progress_bar(
+        verb: &'static str,
+        width: u16,
+        height: u16,
+    ) -> Result<(InMemoryTerm, ProgressBar)> {
+        let width = width.saturating_sub(4);
+        let in_mem = InMemoryTerm::new(height, width);
+        let pb = ProgressBar::with_draw_target(
+            Some(0),
+            ProgressDrawTarget::term_like(Box::new(in_mem.clone())),
+        );
+        pb.set_style(
+            ProgressStyle::with_template(&format!(
+                "{{spinner}} {verb} [{{elapsed}}] [{{wide_bar}}] {{pos}}/{{len}}"
+            ))?
+            .progress_chars("#>-"),
+        );
+        Ok((in_mem, pb))
+    }
+
+    /// Ask the thread to stop after the current file.
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Content of the progress bar.
+    pub fn contents(&self) -> String {
+        self.progress.contents()
+    }
+}
+
+/// Recursively delete the sources, children first.
+/// Returns the number of removed files and folders.
+fn delete_all(sources: &[PathBuf], progress_bar: &ProgressBar, cancel: &AtomicBool) -> u64 {
+    let total = sources
+        .iter()
+        .map(|source| WalkDir::new(source).into_iter().count() as u64)
+        .sum();
+    progress_bar.set_length(total);
+    let mut removed = 0;
+    for source in sources {
+        for entry in WalkDir::new(source)
+            .contents_first(true)
+            .into_iter()
+            .flatten()
+        {
+            if cancel.load(Ordering::Relaxed) {
+                return removed;
+            }
+            match delete_entry(entry.path(), entry.file_type().is_dir()) {
+                Ok(()) => removed += 1,
+                Err(error) => log_info!("delete {path}: {error}", path = entry.path().display()),
+            }
+            progress_bar.inc(1);
+        }
+    }
+    removed
+}
+
+fn delete_entry(path: &Path, is_dir: bool) -> std::io::Result<()> {
+    if is_dir {
+        fs::remove_dir(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// Move the sources to the trash.
+/// Returns the number of trashed files.
+fn trash_all(
+    trash: &mut Trash,
+    sources: &[PathBuf],
+    progress_bar: &ProgressBar,
+    cancel: &AtomicBool,
+) -> u64 {
+    progress_bar.set_length(sources.len() as u64);
+    let mut removed = 0;
+    for source in sources {
+        if cancel.load(Ordering::Relaxed) {
+            return removed;
+        }
+        match trash.trash(source) {
+            Ok(()) => removed += 1,
+            Err(error) => log_info!("trash {path}: {error}", path = source.display()),
+        }
+        progress_bar.inc(1);
+    }
+    removed
+}
@@ -5,6 +5,40 @@ use anyhow::Result;
 use crate::app::Tab;
 use crate::modes::{CaseDependantRegex, Display, FileInfo, Go, IndexToIndex, To, ToPath, Tree};
 
+/// How the typed search is matched against the filenames.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SearchKind {
+    /// Filenames containing the typed text.
+    #[default]
+    Substring,
+    /// Filenames matching the typed regular expression.
+    /// Every match is flagged in directory mode.
+    Regex,
+}
+
+impl SearchKind {
+    /// The other kind of search.
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Substring => Self::Regex,
+            Self::Regex => Self::Substring,
+        }
+    }
+
+    pub fn is_regex(&self) -> bool {
+        matches!(self, Self::Regex)
+    }
+}
+
+impl std::fmt::Display for SearchKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Substring => write!(f, "substring"),
+            Self::Regex => write!(f, "regex"),
+        }
+    }
+}
+
 /// The current search term.
 /// it records the regex used, the matched paths and where we are in those pathes.
 /// The pathes are refreshed every time we jump to another match, allowing the
@@ -40,9 +74,18 @@ impl Search {
         }
     }
 
-    pub fn new(searched: &str) -> Result<Self> {
+    /// Creates a new search, matching the filenames according to its kind.
+    ///
+    /// # Errors
+    ///
+    /// It may fail if the searched string isn't a valid regular expression.
+    pub fn new(searched: &str, kind: SearchKind) -> Result<Self> {
+        let regex = match kind {
+            SearchKind::Substring => CaseDependantRegex::literal(searched)?,
+            SearchKind::Regex => CaseDependantRegex::new(searched)?,
+        };
         Ok(Self {
-            regex: CaseDependantRegex::new(searched)?,
+            regex,
             paths: vec![],
             index: 0,
         })
@@ -16,6 +16,7 @@ use ratatui::{
 use crate::colored_skip_take;
 use crate::common::{
     current_uid, path_to_config_folder, tilde, HARDCODED_SHORTCUTS, TRASH_FOLDER_FILES,
+    XDG_USER_DIRS_PATH,
 };
 use crate::config::{ColorG, Gradient, MENU_STYLES};
 use crate::io::{color_to_style, git_root, CowStr, DrawMenu};
@@ -50,6 +51,7 @@ impl Shortcut {
     fn build_content(start_folder: &Path) -> Vec<PathBuf> {
         let mut content = Self::hardcoded_shortcuts();
         Self::push_home_path(&mut content);
+        Self::push_xdg_user_dirs(&mut content);
         Self::push_trash_folder(&mut content);
         Self::push_config_folder(&mut content);
         Self::push_start_folder(&mut content, start_folder);
@@ -72,6 +74,39 @@ impl Shortcut {
         }
     }
 
+    /// Insert shortcuts to the XDG user directories (Documents, Downloads, Pictures...).
+    /// Their locations are read from `~/.config/user-dirs.dirs`, respecting localized or custom names.
+    /// Nothing is inserted if the file can't be read and missing directories are skipped.
+    fn push_xdg_user_dirs(shortcuts: &mut Vec<PathBuf>) {
+        let Ok(content) = std::fs::read_to_string(tilde(XDG_USER_DIRS_PATH).as_ref()) else {
+            return;
+        };
+        let home = tilde("~");
+        for line in content.lines() {
+            let Some(path) = Self::parse_xdg_user_dir(line, &home) else {
+                continue;
+            };
+            if path.is_dir() && path != Path::new(home.as_ref()) {
+                shortcuts.push(path);
+            }
+        }
+    }
+
+    /// Parse a line like `XDG_DOWNLOAD_DIR="$HOME/Downloads"` into a path.
+    /// Comments and other lines are ignored.
+    fn parse_xdg_user_dir(line: &str, home: &str) -> Option<PathBuf> {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return None;
+        }
+        let (key, value) = line.split_once('=')?;
+        if !key.starts_with("XDG_") || !key.ends_with("_DIR") {
+            return None;
+        }
+        let value = value.trim().trim_matches('"');
+        Some(PathBuf::from(value.replacen("$HOME", home, 1)))
+    }
+
     /// Insert a shortcut to trash directory of the current user.
     fn push_trash_folder(shortcuts: &mut Vec<PathBuf>) {
         if let Ok(trash_path) = PathBuf::from_str(tilde(TRASH_FOLDER_FILES).borrow()) {
@@ -1,5 +1,7 @@
 use std::cmp::Ordering;
+use std::time::{Duration, SystemTime};
 
+use crate::log_line;
 use crate::modes::FileInfo;
 
 /// Different kind of sort
@@ -12,6 +14,8 @@ enum SortBy {
     File,
     /// by date
     Date,
+    /// by access time
+    Access,
     /// by size
     Size,
     /// by extension
@@ -23,6 +27,7 @@ impl std::fmt::Display for SortBy {
         let sort_by = match &self {
             Self::Exte => "Exte",
             Self::Date => "Date",
+            Self::Access => "Acce",
             Self::File => "File",
             Self::Size => "Size",
             Self::Kind => "Kind",
@@ -57,6 +62,8 @@ pub struct SortKind {
     sort_by: SortBy,
     /// Ascending or descending order
     order: Order,
+    /// Reverse the order whatever the key, kept when the key is changed
+    reversed: bool,
 }
 
 impl SortKind {
@@ -67,11 +74,32 @@ impl SortKind {
         Self {
             sort_by: SortBy::Kind,
             order: Order::Descending,
+            reversed: false,
+        }
+    }
+
+    /// True if the order is reversed whatever the key.
+    #[must_use]
+    pub const fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// Reverse the order or restore it. It's kept when the key is changed.
+    pub fn set_reversed(&mut self, reversed: bool) {
+        self.reversed = reversed
+    }
+
+    /// Order used to sort, taking the reversal into account.
+    const fn effective_order(&self) -> Order {
+        if self.reversed {
+            self.order.reverse()
+        } else {
+            self.order
         }
     }
 
     /// Updates itself from a given character.
-    /// If the character describes a kind of sort, we apply it. (k n m s e -- K N M S E)
+    /// If the character describes a kind of sort, we apply it. (k n m a s e -- K N M A S E)
     /// If the character is lowercase, we sort by Ascending order, else Descending order.
     /// If the character is 'r' or 'R' we reverse current kind of sort.
     pub fn update_from_char(&mut self, c: char) {
@@ -79,6 +107,7 @@ impl SortKind {
             'K' => self.sort_by = SortBy::Kind,
             'N' => self.sort_by = SortBy::File,
             'M' => self.sort_by = SortBy::Date,
+            'A' => self.sort_by = SortBy::Access,
             'S' => self.sort_by = SortBy::Size,
             'E' => self.sort_by = SortBy::Exte,
             'R' => self.order = self.order.reverse(),
@@ -94,6 +123,25 @@ impl SortKind {
             }
         }
     }
+
+    /// When sorting by access time, warn the user if no file was accessed in the last day.
+    /// Most filesystems are mounted with `relatime` or `noatime`, updating the access time
+    /// at most once a day or never. The sort is still done from the metadata.
+    pub fn log_if_access_times_look_stale(&self, files: &[FileInfo]) {
+        if !matches!(self.sort_by, SortBy::Access) {
+            return;
+        }
+        let Some(a_day_ago) = SystemTime::now().checked_sub(Duration::from_secs(24 * 60 * 60))
+        else {
+            return;
+        };
+        if files.iter().all(|file| file.access_time < a_day_ago) {
+            log_line!(
+                "Access times look stale. The filesystem may be mounted with relatime or noatime."
+            );
+        }
+    }
+
     /// Use Higher Rank Trait Bounds
     /// Avoid using slices to sort a collection.
     /// It allows use to use references to `String` (`&str`) instead of cloning the `String`.
@@ -125,11 +173,12 @@ impl SortKind {
 
     /// Sort a collection of file depending of enum variants.
     pub fn sort(&self, files: &mut [FileInfo]) {
-        if matches!(self.order, Order::Ascending) {
+        if matches!(self.effective_order(), Order::Ascending) {
             match self.sort_by {
                 SortBy::Kind => Self::sort_by_key_hrtb(files, |f| &f.kind_format),
                 SortBy::File => Self::sort_by_key_hrtb(files, |f| &f.filename),
                 SortBy::Date => Self::sort_by_key_hrtb(files, |f| &f.system_time),
+                SortBy::Access => Self::sort_by_key_hrtb(files, |f| &f.access_time),
                 SortBy::Size => Self::sort_by_key_hrtb(files, |f| &f.true_size),
                 SortBy::Exte => Self::sort_by_key_hrtb(files, |f| &f.extension),
             }
@@ -138,6 +187,7 @@ impl SortKind {
                 SortBy::Kind => Self::reversed_sort_by_key_hrtb(files, |f| &f.kind_format),
                 SortBy::File => Self::reversed_sort_by_key_hrtb(files, |f| &f.filename),
                 SortBy::Date => Self::reversed_sort_by_key_hrtb(files, |f| &f.system_time),
+                SortBy::Access => Self::reversed_sort_by_key_hrtb(files, |f| &f.access_time),
                 SortBy::Size => Self::reversed_sort_by_key_hrtb(files, |f| &f.true_size),
                 SortBy::Exte => Self::reversed_sort_by_key_hrtb(files, |f| &f.extension),
             }
@@ -147,10 +197,15 @@ impl SortKind {
 
 impl std::fmt::Display for SortKind {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let sort_order = match &self.order {
+        let sort_order = match self.effective_order() {
             Order::Ascending => "↓",
             Order::Descending => "↑",
         };
-        write!(f, "{sort_by} {sort_order}", sort_by = &self.sort_by)
+        let reversed = if self.reversed { " rev" } else { "" };
+        write!(
+            f,
+            "{sort_by} {sort_order}{reversed}",
+            sort_by = &self.sort_by
+        )
     }
 }
@@ -1,14 +1,16 @@
 use std::borrow::Cow;
 use std::cmp::Ordering;
-use std::fs::{create_dir, read_dir, remove_dir_all};
+use std::fs::{create_dir, read_dir, remove_dir_all, DirBuilder};
 use std::io::prelude::*;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
 use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context, Result};
 use chrono::{Local, NaiveDateTime};
+use sysinfo::Disks;
 
 use crate::common::{
-    read_lines, tilde, TRASH_CONFIRM_LINE, TRASH_FOLDER_FILES, TRASH_FOLDER_INFO,
+    current_uid, read_lines, tilde, TRASH_CONFIRM_LINE, TRASH_FOLDER_FILES, TRASH_FOLDER_INFO,
     TRASH_INFO_EXTENSION,
 };
 use crate::config::Bindings;
@@ -16,6 +18,10 @@ use crate::io::{CowStr, DrawMenu};
 use crate::{impl_content, impl_selectable, log_info, log_line};
 
 const TRASHINFO_DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+/// Subfolder of a trash holding the trashed files.
+const FILES: &str = "files";
+/// Subfolder of a trash holding the trashinfo files.
+const INFO: &str = "info";
 
 /// Holds the information about a trashed file.
 /// Follow the specifications of .trashinfo files as described in
@@ -24,17 +30,20 @@ const TRASHINFO_DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
 /// - where the file came from,
 /// - what name it was given when trashed,
 /// - when it was trashed
+/// - which trash folder holds it.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Info {
     origin: PathBuf,
     dest_name: String,
     deletion_date: String,
+    trash_root: PathBuf,
 }
 
 impl Info {
     /// Returns a new `Info` instance.
     /// The `deletion_date` is calculated on creation, before the file is actually trashed.
-    pub fn new(origin: &Path, dest_name: &str) -> Self {
+    /// `trash_root` is the trash folder itself, parent of its `files` and `info` folders.
+    pub fn new(origin: &Path, dest_name: &str, trash_root: &Path) -> Self {
         let date = Local::now();
         let deletion_date = format!("{}", date.format(TRASHINFO_DATETIME_FORMAT));
         let dest_name = dest_name.to_owned();
@@ -42,6 +51,7 @@ impl Info {
             origin: PathBuf::from(origin),
             dest_name,
             deletion_date,
+            trash_root: trash_root.to_owned(),
         }
     }
 
@@ -51,11 +61,37 @@ impl Info {
 Path={origin}
 DeletionDate={date}
 ",
-            origin = url_escape::encode_fragment(&self.origin.to_string_lossy()),
+            origin = url_escape::encode_fragment(&self.written_origin().to_string_lossy()),
             date = self.deletion_date
         )
     }
 
+    /// The origin as written in the trashinfo file.
+    /// The specs require the path to be relative to the top directory for trashes
+    /// located at the root of a mount point.
+    fn written_origin(&self) -> &Path {
+        if is_device_trash(&self.trash_root) {
+            if let Some(topdir) = self.trash_root.parent() {
+                if let Ok(relative) = self.origin.strip_prefix(topdir) {
+                    return relative;
+                }
+            }
+        }
+        &self.origin
+    }
+
+    /// Path of the trashed file itself.
+    fn trashfile_path(&self) -> PathBuf {
+        self.trash_root.join(FILES).join(&self.dest_name)
+    }
+
+    /// Path of the trashinfo file.
+    fn trashinfo_path(&self) -> PathBuf {
+        self.trash_root
+            .join(INFO)
+            .join(format!("{}{TRASH_INFO_EXTENSION}", self.dest_name))
+    }
+
     /// Write itself into a .trashinfo file.
     /// The format looks like :
     ///
@@ -98,10 +134,20 @@ DeletionDate={date}
         match (option_path, option_deleted_time) {
             (Some(origin), Some(deletion_date)) => {
                 let dest_name = Self::get_dest_name(trash_info_file)?;
+                let trash_root = trash_info_file
+                    .parent()
+                    .and_then(Path::parent)
+                    .context("Couldn't find the trash folder of the trash info file")?
+                    .to_owned();
+                let origin = match trash_root.parent() {
+                    Some(topdir) if origin.is_relative() => topdir.join(origin),
+                    _ => origin,
+                };
                 Ok(Self {
                     origin,
                     dest_name,
                     deletion_date,
+                    trash_root,
                 })
             }
             _ => Err(anyhow!("Couldn't parse the trash info file")),
@@ -200,9 +246,11 @@ impl std::fmt::Display for Info {
 
 /// Represent a view of the trash.
 /// Its content is navigable so we use a Vector to hold the content.
-/// Only files that share the same mount point as the trash folder (generally ~/.local/share/Trash)
-/// can be moved to trash.
-/// Other files are unaffected.
+/// Files sharing the same mount point as the home trash folder (generally ~/.local/share/Trash)
+/// are moved there.
+/// Other files are moved to the `.Trash-<uid>` folder of their mount point if it can be used,
+/// else they're copied to the home trash folder and then deleted.
+/// The content lists the files of every trash.
 #[derive(Clone)]
 pub struct Trash {
     /// Trashed files info.
@@ -246,28 +294,31 @@ impl Trash {
         })
     }
 
-    fn pick_dest_name(&self, origin: &Path) -> Result<String> {
+    fn pick_dest_name(trash_folder_files: &Path, origin: &Path) -> Result<String> {
         if let Some(file_name) = origin.file_name() {
             let mut dest = file_name
                 .to_str()
                 .context("pick_dest_name: Couldn't parse the origin filename into a string")?
                 .to_owned();
-            let mut dest_path = PathBuf::from(&self.trash_folder_files);
-            dest_path.push(&dest);
+            let mut dest_path = trash_folder_files.join(&dest);
             while dest_path.exists() {
                 dest.push_str(&rand_string());
-                dest_path = PathBuf::from(&self.trash_folder_files);
-                dest_path.push(&dest);
+                dest_path = trash_folder_files.join(&dest);
             }
             return Ok(dest);
         }
         Err(anyhow!("pick_dest_name: Couldn't extract the filename",))
     }
 
-    fn parse_updated_content(trash_folder_info: &str) -> Result<Vec<Info>> {
+    /// The home trash folder, parent of its `files` and `info` folders.
+    fn home_trash_root(&self) -> Result<PathBuf> {
+        find_parent(Path::new(&self.trash_folder_files))
+    }
+
+    fn parse_updated_content(trash_folder_info: &Path) -> Result<Vec<Info>> {
         match read_dir(trash_folder_info) {
             Ok(read_dir) => {
-                let mut content: Vec<Info> = read_dir
+                let content: Vec<Info> = read_dir
                     .filter_map(std::result::Result::ok)
                     .filter(|direntry| direntry.path().extension().is_some())
                     .filter(|direntry| {
@@ -277,7 +328,6 @@ impl Trash {
                     .filter_map(std::result::Result::ok)
                     .collect();
 
-                content.sort_unstable();
                 Ok(content)
             }
             Err(error) => {
@@ -289,6 +339,7 @@ impl Trash {
 
     /// Parse the info files into a new instance.
     /// Only the file we can parse are read.
+    /// The home trash is read first, then the `.Trash-<uid>` folders of every mount point.
     ///
     /// # Errors
     ///
@@ -296,55 +347,72 @@ impl Trash {
     /// If any file is listed in `trash_folder_info` but doesn't exist.
     pub fn update(&mut self) -> Result<()> {
         self.index = 0;
-        self.content = Self::parse_updated_content(&self.trash_folder_info)?;
+        let mut content = Self::parse_updated_content(Path::new(&self.trash_folder_info))?;
+        for device_trash in device_trashes() {
+            if let Ok(device_content) = Self::parse_updated_content(&device_trash.join(INFO)) {
+                content.extend(device_content);
+            }
+        }
+        content.sort_unstable();
+        self.content = content;
         Ok(())
     }
 
     /// Move a file to the trash folder and create a new trash info file.
     /// Add a new `TrashInfo` to the content.
     ///
+    /// Files on the same device as the home trash are moved there.
+    /// Others are moved to the `.Trash-<uid>` folder at the root of their mount point,
+    /// created if needed. If it can't be used, they're copied to the home trash and deleted.
+    ///
     /// # Errors
     ///
     /// This function may fail if the origin path is a relative path.
     /// We have no way to know where the file is exactly located.
     ///
     /// It may also fail  if the trash folder can't be located, we wouldn't be
-    /// able to create a new path for the file.
+    /// able to create a new path for the file, or if the file can't be moved into the trash.
     pub fn trash(&mut self, origin: &Path) -> Result<()> {
         if origin.is_relative() {
             return Err(anyhow!("trash: origin path should be absolute"));
         }
-
-        let dest_file_name = self.pick_dest_name(origin)?;
-
-        self.trash_a_file(Info::new(origin, &dest_file_name), &dest_file_name)
-    }
-
-    fn concat_path(root: &str, filename: &str) -> PathBuf {
-        let mut concatened_path = PathBuf::from(root);
-        concatened_path.push(filename);
-        concatened_path
-    }
-
-    fn trashfile_path(&self, dest_file_name: &str) -> PathBuf {
-        Self::concat_path(&self.trash_folder_files, dest_file_name)
-    }
-
-    fn trashinfo_path(&self, dest_trashinfo_name: &str) -> PathBuf {
-        let mut dest_trashinfo_name = dest_trashinfo_name.to_owned();
-        dest_trashinfo_name.push_str(TRASH_INFO_EXTENSION);
-        Self::concat_path(&self.trash_folder_info, &dest_trashinfo_name)
+        let home_trash = self.home_trash_root()?;
+        if is_same_device(origin, &home_trash) {
+            return self.trash_a_file(origin, &home_trash, false);
+        }
+        if let Some(device_trash) = device_trash_root(origin) {
+            return self.trash_a_file(origin, &device_trash, false);
+        }
+        log_info!(
+            "trash: no usable trash on the device of {origin}, copying it to the home trash",
+            origin = origin.display()
+        );
+        self.trash_a_file(origin, &home_trash, true)
     }
 
-    fn trash_a_file(&mut self, trash_info: Info, dest_file_name: &str) -> Result<()> {
-        let trashfile_filename = &self.trashfile_path(dest_file_name);
-        if let Err(error) = std::fs::rename(&trash_info.origin, trashfile_filename) {
-            log_info!("Couldn't trash {trash_info}. Error: {error:?}");
+    /// Move the file into the `files` folder of the trash and write its trash info.
+    /// If `copy` is true, the file is copied and then deleted, allowing moves across devices.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can't be moved into the trash, it's left in place then.
+    fn trash_a_file(&mut self, origin: &Path, trash_root: &Path, copy: bool) -> Result<()> {
+        let dest_file_name = Self::pick_dest_name(&trash_root.join(FILES), origin)?;
+        let trash_info = Info::new(origin, &dest_file_name, trash_root);
+        let trashfile_filename = trash_info.trashfile_path();
+        let moved = if copy {
+            move_across_devices(origin, &trashfile_filename)
         } else {
-            Self::log_trash_add(&trash_info.origin, dest_file_name);
-            trash_info.write_trash_info(&self.trashinfo_path(dest_file_name))?;
-            self.content.push(trash_info);
+            std::fs::rename(origin, &trashfile_filename).map_err(|error| anyhow!(error))
+        };
+        if let Err(error) = moved {
+            log_info!("Couldn't trash {trash_info}. Error: {error:?}");
+            log_line!("Couldn't trash {origin}", origin = origin.display());
+            return Err(error);
         }
+        Self::log_trash_add(origin, &dest_file_name);
+        trash_info.write_trash_info(&trash_info.trashinfo_path())?;
+        self.content.push(trash_info);
         Ok(())
     }
 
@@ -354,6 +422,7 @@ impl Trash {
     }
 
     /// Empty the trash, removing all the files and the trashinfo.
+    /// The `.Trash-<uid>` folders of every mount point are emptied too.
     /// This action requires a confirmation.
     /// Watchout, it may delete files that weren't parsed.
     ///
@@ -374,11 +443,16 @@ impl Trash {
     }
 
     fn empty_trash_dirs(&self) -> Result<(), std::io::Error> {
-        Self::empty_dir(&self.trash_folder_files)?;
-        Self::empty_dir(&self.trash_folder_info)
+        Self::empty_dir(Path::new(&self.trash_folder_files))?;
+        Self::empty_dir(Path::new(&self.trash_folder_info))?;
+        for device_trash in device_trashes() {
+            Self::empty_dir(&device_trash.join(FILES))?;
+            Self::empty_dir(&device_trash.join(INFO))?;
+        }
+        Ok(())
     }
 
-    fn empty_dir(dir: &str) -> Result<(), std::io::Error> {
+    fn empty_dir(dir: &Path) -> Result<(), std::io::Error> {
         remove_dir_all(dir)?;
         create_dir(dir)
     }
@@ -399,8 +473,8 @@ impl Trash {
 
         let parent = find_parent(&trashinfo.origin)?;
 
-        let trashed_file_content = self.trashfile_path(&trashinfo.dest_name);
-        let trashed_file_info = self.trashinfo_path(&trashinfo.dest_name);
+        let trashed_file_content = trashinfo.trashfile_path();
+        let trashed_file_info = trashinfo.trashinfo_path();
 
         if !trashed_file_content.exists() {
             return Err(anyhow!("trash restore: Couldn't find the trashed file"));
@@ -423,6 +497,7 @@ impl Trash {
 
     /// Restores a file from the trash to its previous directory.
     /// If the parent (or ancestor) folder were deleted, it is recreated.
+    /// Files trashed from another device are copied back and removed from the trash.
     ///
     /// # Errors
     ///
@@ -447,6 +522,9 @@ impl Trash {
         if !parent.exists() {
             std::fs::create_dir_all(parent)?;
         }
+        if !is_same_device(trashed_file_content, parent) {
+            return move_across_devices(trashed_file_content, origin);
+        }
         std::fs::rename(trashed_file_content, origin)?;
         Ok(())
     }
@@ -505,6 +583,84 @@ fn find_parent(path: &Path) -> Result<PathBuf> {
         .to_owned())
 }
 
+/// True if both paths are on the same device.
+/// False if any of them can't be read.
+fn is_same_device(path: &Path, other: &Path) -> bool {
+    match (path.symlink_metadata(), other.metadata()) {
+        (Ok(path_metadata), Ok(other_metadata)) => path_metadata.dev() == other_metadata.dev(),
+        _ => false,
+    }
+}
+
+/// Name of the trash folder at the root of a mount point for the current user.
+fn device_trash_name() -> Result<String> {
+    Ok(format!(".Trash-{uid}", uid = current_uid()?))
+}
+
+fn is_device_trash(trash_root: &Path) -> bool {
+    trash_root
+        .file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with(".Trash-"))
+}
+
+/// The `.Trash-<uid>` folder at the root of the mount point holding `origin`.
+/// The top directory is the highest ancestor on the same device.
+/// Its `files` and `info` folders are created if needed.
+/// None if it can't be created or if it's a symlink, as required by the specs.
+fn device_trash_root(origin: &Path) -> Option<PathBuf> {
+    let device = origin.symlink_metadata().ok()?.dev();
+    let topdir = origin
+        .ancestors()
+        .skip(1)
+        .take_while(|ancestor| {
+            ancestor
+                .metadata()
+                .is_ok_and(|metadata| metadata.dev() == device)
+        })
+        .last()?;
+    let trash_root = topdir.join(device_trash_name().ok()?);
+    if trash_root.is_symlink() {
+        return None;
+    }
+    let mut builder = DirBuilder::new();
+    builder.recursive(true).mode(0o700);
+    builder.create(trash_root.join(FILES)).ok()?;
+    builder.create(trash_root.join(INFO)).ok()?;
+    Some(trash_root)
+}
+
+/// Existing `.Trash-<uid>` folders at the root of every mount point.
+fn device_trashes() -> Vec<PathBuf> {
+    let Ok(trash_name) = device_trash_name() else {
+        return vec![];
+    };
+    let mut trashes: Vec<PathBuf> = Disks::new_with_refreshed_list()
+        .iter()
+        .map(|disk| disk.mount_point().join(&trash_name))
+        .filter(|trash_root| trash_root.is_dir() && !trash_root.is_symlink())
+        .collect();
+    trashes.sort_unstable();
+    trashes.dedup();
+    trashes
+}
+
+/// Move a file or a directory, copying it first and then deleting it.
+/// Used when the destination is on another device, where `rename` fails.
+/// Symlinks are recreated, not followed.
+fn move_across_devices(source: &Path, dest: &Path) -> Result<()> {
+    if source.is_symlink() {
+        std::os::unix::fs::symlink(std::fs::read_link(source)?, dest)?;
+        std::fs::remove_file(source)?;
+    } else if source.is_dir() {
+        let mut options = fs_extra::dir::CopyOptions::new();
+        options.copy_inside = true;
+        fs_extra::dir::move_dir(source, dest, &options)?;
+    } else {
+        fs_extra::file::move_file(source, dest, &fs_extra::file::CopyOptions::new())?;
+    }
+    Ok(())
+}
+
 fn create_if_not_exists<P>(path: P) -> std::io::Result<()>
 where
     std::path::PathBuf: From<P>,
@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use anyhow::{bail, Result};
+
+/// Extended attributes of the file as `name=value` pairs.
+/// Values which aren't valid utf-8 are displayed lossily.
+/// Returns `None` if the filesystem doesn't support extended attributes or they can't be read.
+pub fn read_xattrs(path: &Path) -> Option<Vec<String>> {
+    if !xattr::SUPPORTED_PLATFORM {
+        return None;
+    }
+    let pairs = xattr::list(path)
+        .ok()?
+        .map(|name| {
+            let value = xattr::get(path, &name).ok().flatten().unwrap_or_default();
+            format!(
+                "{name}={value}",
+                name = name.to_string_lossy(),
+                value = String::from_utf8_lossy(&value).trim_end_matches('\0')
+            )
+        })
+        .collect();
+    Some(pairs)
+}
+
+/// Set an extended attribute from an input like `user.comment=some text`.
+/// The value may be empty but the name can't.
+///
+/// # Errors
+///
+/// It fails if the input has no `=`, if the name is empty or if the attribute can't be set:
+/// unsupported filesystem, missing permissions or a namespace other than `user.` for a normal user.
+pub fn set_xattr(path: &Path, input: &str) -> Result<()> {
+    let Some((name, value)) = input.split_once('=') else {
+        bail!("expected name=value, got {input}");
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("the name of the attribute is empty");
+    }
+    xattr::set(path, name, value.as_bytes())?;
+    Ok(())
+}
+
+/// Remove the extended attribute with this name.
+///
+/// # Errors
+///
+/// It fails if the attribute doesn't exist or can't be removed.
+pub fn remove_xattr(path: &Path, name: &str) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("the name of the attribute is empty");
+    }
+    xattr::remove(path, name)?;
+    Ok(())
+}
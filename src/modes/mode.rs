@@ -1,9 +1,13 @@
 use std::fmt;
 
 use crate::common::{
-    UtfWidth, CHMOD_LINES, CLOUD_NEWDIR_LINES, FILTER_LINES, NEWDIR_LINES, NEWFILE_LINES,
-    NVIM_ADDRESS_LINES, PASSWORD_LINES_DEVICE, PASSWORD_LINES_SUDO, REGEX_LINES, REMOTE_LINES,
-    RENAME_LINES, SHELL_LINES, SORT_LINES,
+    UtfWidth, ARCHIVE_NAME_LINES, CHMOD_LINES, CLOUD_NEWDIR_LINES, CONFIRM_PROTECTED_LINES,
+    CONFLICT_RENAME_LINES, COPY_AS_LINES, EXTENSION_RENAME_LINES, FILTER_LINES, GLOB_LINES,
+    GREP_FLAG_LINES, LOAD_FLAGGED_LINES, MOUNT_LABEL_LINES, NEWDIR_AND_ENTER_LINES, NEWDIR_LINES,
+    NEWFILE_LINES, NUMBERED_RENAME_LINES, NVIM_ADDRESS_LINES, PASSWORD_LINES_DEVICE,
+    PASSWORD_LINES_SUDO, REGEX_LINES, REMOTE_LINES, REMOVE_XATTR_LINES, RENAME_LINES,
+    SAVE_FLAGGED_LINES, SET_XATTR_LINES, SHELL_DRY_RUN_LINES, SHELL_LINES, SHELL_STREAM_LINES,
+    SORT_LINES, SPLIT_FILE_LINES,
 };
 use crate::modes::BlockDeviceAction;
 use crate::modes::InputCompleted;
@@ -36,6 +40,16 @@ pub enum NeedConfirmation {
     BulkAction,
     /// Delete cloud files
     DeleteCloud,
+    /// Run the selected script and preview its output
+    RunScript,
+    /// Overwrite the target of a copy as
+    CopyAs,
+    /// Run an expanded shell command
+    ShellCommand,
+    /// Resolve a copied or moved file whose name is already used in the destination
+    Conflict,
+    /// Overwrite an existing file with the flagged files
+    SaveFlagged,
 }
 
 impl NeedConfirmation {
@@ -61,6 +75,22 @@ impl NeedConfirmation {
             }
             Self::BulkAction => "Those files will be renamed or created :".to_owned(),
             Self::DeleteCloud => "Remote Files will be deleted permanently".to_owned(),
+            Self::RunScript => {
+                "This script will be executed. It may run arbitrary code, only run trusted files !"
+                    .to_owned()
+            }
+            Self::CopyAs => {
+                "This file already exists. y overwrites it, anything else chooses another name"
+                    .to_owned()
+            }
+            Self::ShellCommand => "This command will be executed :".to_owned(),
+            Self::SaveFlagged => {
+                "This file already exists. y overwrites it with the flagged files".to_owned()
+            }
+            Self::Conflict => {
+                "o overwrite, s skip, r rename, n overwrite if newer. Uppercase applies to all, anything else cancels"
+                    .to_owned()
+            }
         }
     }
 }
@@ -84,6 +114,11 @@ impl std::fmt::Display for NeedConfirmation {
             Self::Copy => write!(f, "Copy files here :"),
             Self::EmptyTrash => write!(f, "Empty the trash ?"),
             Self::BulkAction => write!(f, "Bulk :"),
+            Self::RunScript => write!(f, "Run script :"),
+            Self::CopyAs => write!(f, "Overwrite :"),
+            Self::ShellCommand => write!(f, "Run command :"),
+            Self::Conflict => write!(f, "Conflict :"),
+            Self::SaveFlagged => write!(f, "Overwrite :"),
         }
     }
 }
@@ -103,8 +138,12 @@ pub enum InputSimple {
     Newfile,
     /// Mkdir a new directory
     Newdir,
+    /// Mkdir a new directory and cd into it
+    NewdirAndEnter,
     /// Flag files matching a regex
     RegexMatch,
+    /// Flag files matching a glob
+    GlobMatch,
     /// Change the type of sort
     Sort,
     /// Filter by extension, name, directory or no filter
@@ -115,10 +154,40 @@ pub enum InputSimple {
     Password(Option<BlockDeviceAction>, PasswordUsage),
     /// Shell command execute as is
     ShellCommand,
+    /// Shell command whose output is streamed into the preview
+    ShellStream,
     /// Mount a remote directory with sshfs
     Remote,
+    /// Mount a device from its label or UUID
+    MountByLabel,
+    /// Name of the archive created from flagged files
+    ArchiveName,
     /// Create a new file in the current cloud
     CloudNewdir,
+    /// First number and width of the sequence prefixing flagged filenames
+    NumberedRename,
+    /// New extension of the flagged filenames
+    ExtensionRename,
+    /// Name and value of an extended attribute set on the selected file
+    SetXattr,
+    /// Name of an extended attribute removed from the selected file
+    RemoveXattr,
+    /// Path of the file where the flagged files are saved
+    SaveFlagged,
+    /// Path of the file from which the flagged files are loaded
+    LoadFlagged,
+    /// Pattern searched in the files of the current directory, matching files are flagged
+    GrepFlag,
+    /// Full path of a protected file, typed to confirm the action modifying it
+    ConfirmProtected,
+    /// Name of the copy of the selected file
+    CopyAs,
+    /// Shell command expanded and displayed before running
+    ShellDryRun,
+    /// Size of the parts of the selected file
+    SplitFile,
+    /// New name of a copied or moved file whose name is already used in the destination
+    ConflictRename,
 }
 
 impl fmt::Display for InputSimple {
@@ -128,10 +197,13 @@ impl fmt::Display for InputSimple {
             Self::Chmod => write!(f, "Chmod:   "),
             Self::Newfile => write!(f, "Newfile: "),
             Self::Newdir => write!(f, "Newdir:  "),
+            Self::NewdirAndEnter => write!(f, "Newdir:  "),
             Self::RegexMatch => write!(f, "Regex:   "),
+            Self::GlobMatch => write!(f, "Glob:    "),
             Self::SetNvimAddr => write!(f, "Neovim:  "),
             Self::CloudNewdir => write!(f, "Newdir:  "),
             Self::ShellCommand => write!(f, "Shell:   "),
+            Self::ShellStream => write!(f, "Stream:  "),
             Self::Sort => {
                 write!(f, "Sort: ")
             }
@@ -141,6 +213,20 @@ impl fmt::Display for InputSimple {
             }
             Self::Password(_, _) => write!(f, " sudo: "),
             Self::Remote => write!(f, "Remote:  "),
+            Self::MountByLabel => write!(f, "Mount:   "),
+            Self::ArchiveName => write!(f, "Archive: "),
+            Self::NumberedRename => write!(f, "Number:  "),
+            Self::ExtensionRename => write!(f, "Ext:     "),
+            Self::SetXattr => write!(f, "Xattr:   "),
+            Self::RemoveXattr => write!(f, "Xattr:   "),
+            Self::SaveFlagged => write!(f, "Save:    "),
+            Self::LoadFlagged => write!(f, "Load:    "),
+            Self::GrepFlag => write!(f, "Grep:    "),
+            Self::ConfirmProtected => write!(f, "Confirm: "),
+            Self::CopyAs => write!(f, "Copy as: "),
+            Self::ShellDryRun => write!(f, "Dry run: "),
+            Self::SplitFile => write!(f, "Split:   "),
+            Self::ConflictRename => write!(f, "Rename:  "),
         }
     }
 }
@@ -158,6 +244,7 @@ impl InputSimple {
             Self::Chmod => &CHMOD_LINES,
             Self::Filter => &FILTER_LINES,
             Self::Newdir => &NEWDIR_LINES,
+            Self::NewdirAndEnter => &NEWDIR_AND_ENTER_LINES,
             Self::Newfile => &NEWFILE_LINES,
             Self::Password(_, PasswordUsage::CRYPTSETUP(PasswordKind::SUDO)) => {
                 &PASSWORD_LINES_SUDO
@@ -167,12 +254,28 @@ impl InputSimple {
             }
             Self::Password(_, _) => &PASSWORD_LINES_SUDO,
             Self::RegexMatch => &REGEX_LINES,
+            Self::GlobMatch => &GLOB_LINES,
             Self::Rename => &RENAME_LINES,
             Self::SetNvimAddr => &NVIM_ADDRESS_LINES,
             Self::ShellCommand => &SHELL_LINES,
+            Self::ShellStream => &SHELL_STREAM_LINES,
             Self::Sort => &SORT_LINES,
             Self::Remote => &REMOTE_LINES,
+            Self::MountByLabel => &MOUNT_LABEL_LINES,
+            Self::ArchiveName => &ARCHIVE_NAME_LINES,
             Self::CloudNewdir => &CLOUD_NEWDIR_LINES,
+            Self::NumberedRename => &NUMBERED_RENAME_LINES,
+            Self::ExtensionRename => &EXTENSION_RENAME_LINES,
+            Self::SetXattr => &SET_XATTR_LINES,
+            Self::RemoveXattr => &REMOVE_XATTR_LINES,
+            Self::SaveFlagged => &SAVE_FLAGGED_LINES,
+            Self::LoadFlagged => &LOAD_FLAGGED_LINES,
+            Self::GrepFlag => &GREP_FLAG_LINES,
+            Self::ConfirmProtected => &CONFIRM_PROTECTED_LINES,
+            Self::CopyAs => &COPY_AS_LINES,
+            Self::ShellDryRun => &SHELL_DRY_RUN_LINES,
+            Self::SplitFile => &SPLIT_FILE_LINES,
+            Self::ConflictRename => &CONFLICT_RENAME_LINES,
         }
     }
 
@@ -189,12 +292,28 @@ impl Leave for InputSimple {
     fn must_refresh(&self) -> bool {
         !matches!(
             self,
-            Self::ShellCommand | Self::Filter | Self::Password(_, _) | Self::Sort
+            Self::ShellCommand
+                | Self::ShellStream
+                | Self::Filter
+                | Self::Password(_, _)
+                | Self::Sort
+                | Self::MountByLabel
+                | Self::NumberedRename
+                | Self::ExtensionRename
         )
     }
 
     fn must_reset_mode(&self) -> bool {
-        !matches!(self, Self::ShellCommand | Self::Password(_, _) | Self::Sort)
+        !matches!(
+            self,
+            Self::ShellCommand
+                | Self::ShellStream
+                | Self::Password(_, _)
+                | Self::Sort
+                | Self::MountByLabel
+                | Self::NumberedRename
+                | Self::ExtensionRename
+        )
     }
 }
 
@@ -219,6 +338,8 @@ pub enum Navigate {
     TempMarks(MarkAction),
     /// Pick a compression method
     Compress,
+    /// Pick the format of the converted images
+    ConvertImages,
     /// Shell menu applications. Start a new shell with this application.
     TuiApplication,
     /// Cli info
@@ -245,6 +366,7 @@ impl fmt::Display for Navigate {
                 write!(f, "Start a new shell running a command:")
             }
             Self::Compress => write!(f, "Compress :"),
+            Self::ConvertImages => write!(f, "Convert images to :"),
             Self::EncryptedDrive => {
                 write!(f, "Encrypted devices :")
             }
@@ -262,11 +384,11 @@ impl fmt::Display for Navigate {
 
 impl Leave for Navigate {
     fn must_refresh(&self) -> bool {
-        !matches!(self, Self::CliApplication | Self::Context)
+        !matches!(self, Self::CliApplication | Self::Context | Self::Compress)
     }
 
     fn must_reset_mode(&self) -> bool {
-        !matches!(self, Self::CliApplication | Self::Context)
+        !matches!(self, Self::CliApplication | Self::Context | Self::Compress)
     }
 }
 
@@ -276,6 +398,7 @@ impl Navigate {
         matches!(
             self,
             Self::Compress
+                | Self::ConvertImages
                 | Self::Shortcut
                 | Self::TuiApplication
                 | Self::CliApplication
@@ -343,6 +466,7 @@ impl Menu {
 
     pub fn binds_per_mode(&self) -> &'static str {
         match self {
+            Self::InputCompleted(InputCompleted::Search) => "Tab: completion. Ctrl+w: toggle substring / regex. shift+⬆️, shift+⬇️: previous entries. Enter: validate",
             Self::InputCompleted(_) => "Tab: completion. shift+⬆️, shift+⬇️: previous entries, shift+⬅️: erase line. Enter: validate",
             Self::InputSimple(InputSimple::Filter) => "Enter reset the filters",
             Self::InputSimple(InputSimple::Sort ) => "Enter reset the sort",
@@ -353,6 +477,7 @@ impl Menu {
             Self::Navigate(Navigate::Cloud) => "l: leave drive, arrows: navigation, Enter: enter dir / download file, d: new dir, x: delete selected, u: upload local file",
             Self::Navigate(Navigate::Flagged) => "Up, Down: navigate, Enter / j: jump to this file, x: remove from flagged, u: clear",
             Self::Navigate(Navigate::Trash) => "Up, Down: navigate.",
+            Self::Navigate(Navigate::RemovableDevices) => "Up, Down: navigate, m: mount, u: unmount, g: go to mount point, l: mount by label or UUID",
             Self::Navigate(_) => "up, down to navigate, Enter to select an element",
             Self::NeedConfirmation(_) => "",
             _ => "",
@@ -422,6 +547,10 @@ impl Display {
         self == &other
     }
 
+    pub fn is_directory(&self) -> bool {
+        self.is(Self::Directory)
+    }
+
     pub fn is_tree(&self) -> bool {
         self.is(Self::Tree)
     }
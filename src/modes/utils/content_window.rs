@@ -1,5 +1,7 @@
 use std::cmp::{max, min};
 
+use crate::config::scroll_off;
+
 /// Holds the information about the displayed window of lines.
 /// When there's too much lines to display in one screen, we can scroll
 /// and this struct is responsible for that.
@@ -74,10 +76,31 @@ impl ContentWindow {
         self.bottom = Self::default_bottom(len, self.height);
     }
 
+    /// Number of lines kept between the selected index and the border of the window.
+    /// It's the greatest of the default padding and the configured `scroll_off`.
+    /// The configured value is capped to half the window height, otherwise the window would jump.
+    fn padding(&self) -> usize {
+        max(
+            Self::WINDOW_PADDING,
+            min(scroll_off(), self.height.saturating_sub(1) / 2),
+        )
+    }
+
+    /// Update the length of the content when lines are appended to it.
+    /// If the last line was displayed, the window follows the end of the content.
+    pub fn append_len(&mut self, len: usize) {
+        let was_at_end = self.bottom >= self.len;
+        self.len = len;
+        if was_at_end {
+            self.bottom = len;
+            self.top = len.saturating_sub(self.height);
+        }
+    }
+
     /// Move the window one line up if possible.
     /// Does nothing if the index can't be reached.
     pub fn scroll_up_one(&mut self, index: usize) {
-        if (index < self.top + Self::WINDOW_PADDING || index > self.bottom) && self.top > 0 {
+        if (index < self.top + self.padding() || index > self.bottom) && self.top > 0 {
             self.top -= 1;
             self.bottom -= 1;
         }
@@ -90,7 +113,7 @@ impl ContentWindow {
         if self.len < self.height {
             return;
         }
-        if index < self.top || index + Self::WINDOW_PADDING > self.bottom {
+        if index < self.top || index + self.padding() > self.bottom {
             self.top += 1;
             self.bottom += 1;
         }
@@ -105,7 +128,7 @@ impl ContentWindow {
         }
         if self.is_index_outside_window(index) {
             let height = max(self.bottom.saturating_sub(self.top), self.height);
-            self.top = index.saturating_sub(Self::WINDOW_PADDING);
+            self.top = index.saturating_sub(self.padding());
             self.bottom = self.top + height;
         }
     }
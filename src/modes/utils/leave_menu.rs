@@ -29,10 +29,13 @@ impl LeaveMenu {
             Menu::InputSimple(InputSimple::Rename) => LeaveMenu::rename(status),
             Menu::InputSimple(InputSimple::Newfile) => LeaveMenu::new_file(status),
             Menu::InputSimple(InputSimple::Newdir) => LeaveMenu::new_dir(status),
+            Menu::InputSimple(InputSimple::NewdirAndEnter) => LeaveMenu::new_dir_and_enter(status),
             Menu::InputSimple(InputSimple::Chmod) => LeaveMenu::chmod(status),
             Menu::InputSimple(InputSimple::RegexMatch) => LeaveMenu::regex_match(status),
+            Menu::InputSimple(InputSimple::GlobMatch) => LeaveMenu::glob_match(status),
             Menu::InputSimple(InputSimple::SetNvimAddr) => LeaveMenu::set_nvim_addr(status),
             Menu::InputSimple(InputSimple::ShellCommand) => LeaveMenu::shell_command(status),
+            Menu::InputSimple(InputSimple::ShellStream) => LeaveMenu::shell_stream(status),
             Menu::InputSimple(InputSimple::Sort) => LeaveMenu::sort(status),
             Menu::InputSimple(InputSimple::Filter) => LeaveMenu::filter(status),
             Menu::InputSimple(InputSimple::Password(action, usage)) => {
@@ -43,6 +46,35 @@ impl LeaveMenu {
                 return Ok(());
             }
             Menu::InputSimple(InputSimple::Remote) => LeaveMenu::remote(status),
+            Menu::InputSimple(InputSimple::MountByLabel) => LeaveMenu::mount_by_label(status),
+            Menu::InputSimple(InputSimple::ArchiveName) => LeaveMenu::archive_name(status),
+            Menu::InputSimple(InputSimple::NumberedRename) => LeaveMenu::numbered_rename(status),
+            Menu::InputSimple(InputSimple::ExtensionRename) => LeaveMenu::extension_rename(status),
+            Menu::InputSimple(InputSimple::SetXattr) => LeaveMenu::set_xattr(status),
+            Menu::InputSimple(InputSimple::RemoveXattr) => LeaveMenu::remove_xattr(status),
+            Menu::InputSimple(InputSimple::SaveFlagged) => {
+                LeaveMenu::save_flagged(status)?;
+                return Ok(());
+            }
+            Menu::InputSimple(InputSimple::GrepFlag) => LeaveMenu::grep_flag(status),
+            Menu::InputSimple(InputSimple::LoadFlagged) => LeaveMenu::load_flagged(status),
+            Menu::InputSimple(InputSimple::ShellDryRun) => {
+                LeaveMenu::shell_dry_run(status)?;
+                return Ok(());
+            }
+            Menu::InputSimple(InputSimple::SplitFile) => LeaveMenu::split_file(status),
+            Menu::InputSimple(InputSimple::CopyAs) => {
+                LeaveMenu::copy_as(status)?;
+                return Ok(());
+            }
+            Menu::InputSimple(InputSimple::ConfirmProtected) => {
+                LeaveMenu::confirm_protected(status)?;
+                return Ok(());
+            }
+            Menu::InputSimple(InputSimple::ConflictRename) => {
+                LeaveMenu::conflict_rename(status)?;
+                return Ok(());
+            }
             Menu::Navigate(Navigate::History) => LeaveMenu::history(status),
             Menu::Navigate(Navigate::Shortcut) => LeaveMenu::shortcut(status),
             Menu::Navigate(Navigate::Trash) => LeaveMenu::trash(status),
@@ -58,6 +90,7 @@ impl LeaveMenu {
             Menu::Navigate(Navigate::TempMarks(MarkAction::New)) => LeaveMenu::tempmark_upd(status),
             Menu::Navigate(Navigate::TempMarks(MarkAction::Jump)) => LeaveMenu::tempmark_jp(status),
             Menu::Navigate(Navigate::Compress) => LeaveMenu::compress(status),
+            Menu::Navigate(Navigate::ConvertImages) => LeaveMenu::convert_images(status),
             Menu::Navigate(Navigate::Context) => LeaveMenu::context(status, binds),
             Menu::Navigate(Navigate::RemovableDevices) => LeaveMenu::go_to_mount(status),
             Menu::Navigate(Navigate::Picker) => {
@@ -206,6 +239,18 @@ impl LeaveMenu {
         Ok(())
     }
 
+    /// Flag every file matching the typed glob in current dir.
+    fn glob_match(status: &mut Status) -> Result<()> {
+        status.flag_from_glob()?;
+        status.menu.input.reset();
+        Ok(())
+    }
+
+    /// Mount the device whose label or UUID was typed.
+    fn mount_by_label(status: &mut Status) -> Result<()> {
+        status.mount_by_label()
+    }
+
     /// Execute a shell command typed by the user.
     /// but expansions are supported
     /// It won't return an `Err` if the command fail but log a message.
@@ -214,6 +259,16 @@ impl LeaveMenu {
         Ok(())
     }
 
+    /// Expand a shell command typed by the user and ask a confirmation before running it.
+    fn shell_dry_run(status: &mut Status) -> Result<()> {
+        status.shell_dry_run()
+    }
+
+    /// Execute a shell command typed by the user and stream its output into the preview.
+    fn shell_stream(status: &mut Status) -> Result<()> {
+        status.stream_shell_command_from_input()
+    }
+
     /// Execute a rename of the selected file.
     /// It uses the `fs::rename` function and has the same limitations.
     /// Intermediates directory are created if needed.
@@ -262,6 +317,22 @@ impl LeaveMenu {
         Ok(())
     }
 
+    /// Creates a new directory with input string as name and cd into it.
+    /// Like [`LeaveMenu::new_dir`], nested directories can be created, the deepest one is entered.
+    fn new_dir_and_enter(status: &mut Status) -> Result<()> {
+        match NodeCreation::Newdir.create(status) {
+            Ok(path) => {
+                status.refresh_tabs()?;
+                status.thumbnail_queue_clear();
+                status.current_tab_mut().cd(&path)?;
+                status.current_tab_mut().refresh_view()?;
+                status.update_second_pane_for_preview()?;
+            }
+            Err(error) => log_info!("Error creating directory. Error: {error}",),
+        }
+        Ok(())
+    }
+
     /// Tries to execute the selected file with an executable which is read
     /// from the input string. It will fail silently if the executable can't
     /// be found.
@@ -336,15 +407,84 @@ impl LeaveMenu {
         status.execute_password_command(action, usage)
     }
 
+    /// The compression method is chosen, ask for the name of the archive.
+    fn compress(status: &mut Status) -> Result<()> {
+        status.set_menu_mode(status.index, Menu::InputSimple(InputSimple::ArchiveName))
+    }
+
+    /// The format is chosen, convert the flagged images in a thread.
+    fn convert_images(status: &mut Status) -> Result<()> {
+        status.convert_images()
+    }
+
     /// Compress the flagged files into an archive.
     /// Compression method is chosen by the user.
-    /// The archive is created in the current directory and is named "archive.tar.??" or "archive.zip".
+    /// The archive is created in the current directory and is named from the input, with the extension of the method.
     /// Files which are above the CWD are filtered out since they can't be added to an archive.
     /// Archive creation depends on CWD so we ensure it's set to the selected tab.
-    fn compress(status: &mut Status) -> Result<()> {
+    fn archive_name(status: &mut Status) -> Result<()> {
         status.compress()
     }
 
+    /// Ask a confirmation before prefixing the flagged filenames with the typed sequence.
+    fn numbered_rename(status: &mut Status) -> Result<()> {
+        status.numbered_rename()
+    }
+
+    /// Ask a confirmation before replacing the extension of the flagged filenames.
+    fn extension_rename(status: &mut Status) -> Result<()> {
+        status.extension_rename()
+    }
+
+    /// Set the typed extended attribute on the selected file.
+    fn set_xattr(status: &mut Status) -> Result<()> {
+        status.set_xattr()
+    }
+
+    /// Remove the typed extended attribute from the selected file.
+    fn remove_xattr(status: &mut Status) -> Result<()> {
+        status.remove_xattr()
+    }
+
+    /// Flag the files of the current directory whose content matches the typed pattern.
+    fn grep_flag(status: &mut Status) -> Result<()> {
+        status.grep_flag()
+    }
+
+    /// Split the selected file into parts of the typed size.
+    fn split_file(status: &mut Status) -> Result<()> {
+        status.split_file()
+    }
+
+    /// Copy the selected file to the typed name.
+    /// The menu is reset by the copy itself since an existing target requires a confirmation.
+    fn copy_as(status: &mut Status) -> Result<()> {
+        status.copy_as()
+    }
+
+    /// Transfer the conflicting file under the typed name and resolve the next conflict.
+    /// An invalid or used name is asked again.
+    fn conflict_rename(status: &mut Status) -> Result<()> {
+        status.conflict_rename()
+    }
+
+    /// Confirm the protected file if its full path was typed, running the action once they're all confirmed.
+    /// The menu is reset by the action itself since a move may require to resolve conflicts.
+    fn confirm_protected(status: &mut Status) -> Result<()> {
+        status.confirm_protected()
+    }
+
+    /// Save the flagged files to the typed path.
+    /// The menu is reset by the save itself since an existing file requires a confirmation.
+    fn save_flagged(status: &mut Status) -> Result<()> {
+        status.save_flagged()
+    }
+
+    /// Replace the flagged files by those saved in the typed path.
+    fn load_flagged(status: &mut Status) -> Result<()> {
+        status.load_flagged()
+    }
+
     /// Open a menu with most common actions
     fn context(status: &mut Status, binds: &Bindings) -> Result<()> {
         let command = status.menu.context.matcher().to_owned();
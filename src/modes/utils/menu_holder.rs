@@ -1,4 +1,5 @@
 use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
 
 use anyhow::Result;
 use ratatui::layout::Rect;
@@ -9,12 +10,12 @@ use crate::common::{index_from_a, INPUT_HISTORY_PATH};
 use crate::config::Bindings;
 use crate::io::DrawMenu;
 use crate::io::{drop_sudo_privileges, InputHistory, OpendalContainer};
-use crate::log_line;
 use crate::modes::{
-    Bulk, CliApplications, Completion, Compresser, Content, ContentWindow, ContextMenu,
-    CryptoDeviceOpener, Flagged, History, Input, InputCompleted, IsoDevice, Marks, Menu,
-    MountCommands, Navigate, PasswordHolder, Picker, Remote, RemovableDevices, Selectable,
-    Shortcut, TempMarks, Trash, TuiApplications, MAX_MODE,
+    Bulk, CliApplications, Completion, Compresser, Conflicts, Content, ContentWindow, ContextMenu,
+    CryptoDeviceOpener, Flagged, History, ImageConverter, Input, InputCompleted, IsoDevice, Marks,
+    Menu, MountCommands, Navigate, PasswordHolder, Picker, ProtectedGuard, Remote,
+    RemovableDevices, SearchKind, Selectable, Shortcut, TempMarks, Trash, TuiApplications,
+    MAX_MODE,
 };
 
 /// Holds almost every menu except for the history, which is tab specific.
@@ -44,8 +45,12 @@ pub struct MenuHolder {
     pub context: ContextMenu,
     /// Encrypted devices opener
     pub encrypted_devices: CryptoDeviceOpener,
+    /// Files of a copy or move whose name is already used in the destination
+    pub conflicts: Option<Conflicts>,
     /// The flagged files
     pub flagged: Flagged,
+    /// Formats of image conversion
+    pub image_converter: ImageConverter,
     /// The typed input by the user
     pub input: Input,
     /// The user input history.
@@ -60,8 +65,18 @@ pub struct MenuHolder {
     pub password_holder: PasswordHolder,
     /// basic picker
     pub picker: Picker,
+    /// Protected files of a destructive action, confirmed by the user before it runs
+    pub protected: Option<ProtectedGuard>,
+    /// Command running the script the user is asked to confirm
+    pub script_command: Option<Vec<String>>,
     /// MTP devices
     pub removable_devices: RemovableDevices,
+    /// How the search input is matched: substring or regex
+    pub search_kind: SearchKind,
+    /// Error of the last search, displayed in the search menu
+    pub search_error: Option<String>,
+    /// Files flagged by the current regex search which weren't flagged before it
+    pub search_flagged: Vec<PathBuf>,
     /// Predefined shortcuts
     pub shortcut: Shortcut,
     /// TUI application
@@ -82,17 +97,24 @@ impl MenuHolder {
             cloud: OpendalContainer::default(),
             completion: Completion::default(),
             compression: Compresser::default(),
+            conflicts: None,
             context: ContextMenu::default(),
             encrypted_devices: CryptoDeviceOpener::default(),
             flagged: Flagged::default(),
             history: History::default(),
+            image_converter: ImageConverter::default(),
             input: Input::default(),
             input_history: InputHistory::load(INPUT_HISTORY_PATH)?,
             iso_device: None,
             marks: Marks::default(),
             password_holder: PasswordHolder::default(),
             picker: Picker::default(),
+            protected: None,
             removable_devices: RemovableDevices::default(),
+            script_command: None,
+            search_error: None,
+            search_flagged: vec![],
+            search_kind: SearchKind::default(),
             shortcut: Shortcut::empty(start_dir),
             sudo_command: None,
             temp_marks: TempMarks::default(),
@@ -202,20 +224,6 @@ impl MenuHolder {
         self.trash.delete_permanently()
     }
 
-    pub fn delete_flagged_files(&mut self) -> Result<()> {
-        let nb = self.flagged.len();
-        for pathbuf in self.flagged.content.iter() {
-            if pathbuf.is_dir() {
-                std::fs::remove_dir_all(pathbuf)?;
-            } else {
-                std::fs::remove_file(pathbuf)?;
-            }
-        }
-        self.flagged.clear();
-        log_line!("Deleted {nb} flagged files");
-        Ok(())
-    }
-
     pub fn clear_sudo_attributes(&mut self) -> Result<()> {
         self.password_holder.reset();
         drop_sudo_privileges()?;
@@ -339,6 +347,7 @@ impl MenuHolder {
         match navigate {
             Navigate::CliApplication => func(&mut self.cli_applications),
             Navigate::Compress => func(&mut self.compression),
+            Navigate::ConvertImages => func(&mut self.image_converter),
             Navigate::Context => func(&mut self.context),
             Navigate::EncryptedDrive => func(&mut self.encrypted_devices),
             Navigate::History => func(&mut self.history),
@@ -361,6 +370,7 @@ impl MenuHolder {
         match navigate {
             Navigate::CliApplication => func(&self.cli_applications),
             Navigate::Compress => func(&self.compression),
+            Navigate::ConvertImages => func(&self.image_converter),
             Navigate::Context => func(&self.context),
             Navigate::EncryptedDrive => func(&self.encrypted_devices),
             Navigate::History => func(&self.history),
@@ -386,6 +396,7 @@ impl MenuHolder {
     pub fn draw_navigate(&self, f: &mut Frame, rect: &Rect, navigate: Navigate) {
         match navigate {
             Navigate::Compress => self.compression.draw_menu(f, rect, &self.window),
+            Navigate::ConvertImages => self.image_converter.draw_menu(f, rect, &self.window),
             Navigate::Shortcut => self.shortcut.draw_menu(f, rect, &self.window),
             Navigate::Marks(_) => self.marks.draw_menu(f, rect, &self.window),
             Navigate::TuiApplication => self.tui_applications.draw_menu(f, rect, &self.window),
@@ -7,6 +7,7 @@ mod mount_help;
 mod second_line;
 mod selectable_content;
 mod shell_parser;
+mod visual;
 
 pub use ansi::*;
 pub use content_window::ContentWindow;
@@ -17,3 +18,4 @@ pub use mount_help::{MountCommands, MountParameters, MountRepr};
 pub use second_line::SecondLine;
 pub use selectable_content::{Content, IndexToIndex, Selectable, ToPath};
 pub use shell_parser::{shell_command_parser, SAME_WINDOW_TOKEN};
+pub use visual::Visual;
@@ -25,6 +25,7 @@ impl SecondLine for Navigate {
             Self::History => "Pick a destination",
             Self::Shortcut => "Pick a destination",
             Self::Compress => "Archive and compress the flagged files using selected algorithm.",
+            Self::ConvertImages => "Convert the flagged images into the current directory.",
             Self::Marks(mark_action) => mark_action.second_line(),
             Self::TempMarks(mark_action) => mark_action.second_line(),
             Self::Context => "Pick an action",
@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+
+use crate::modes::Flagged;
+
+/// Visual mode : every visible file between an anchor and the selected file is flagged.
+///
+/// The anchor is the file selected when the mode was entered.
+/// The files flagged by the visual mode are remembered, so moving back unflags them.
+/// Files flagged before entering the mode are never unflagged.
+///
+/// Only visible files are flagged : in tree mode, the content of folded nodes is skipped.
+/// If the anchor is hidden by a fold, its closest visible ancestor is used instead.
+/// In directory mode, there's no fold and an anchor which can't be found means the directory changed.
+///
+/// Dragging the mouse over the files also enters the visual mode, which is left when the button is released.
+#[derive(Default)]
+pub struct Visual {
+    anchor: Option<PathBuf>,
+    flagged_by_visual: Vec<PathBuf>,
+    from_mouse: bool,
+}
+
+impl Visual {
+    /// True if the visual mode is active.
+    pub fn is_active(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    /// True if the visual mode was entered by dragging the mouse.
+    pub fn is_from_mouse(&self) -> bool {
+        self.from_mouse
+    }
+
+    /// Enter the visual mode from this path.
+    pub fn enter(&mut self, anchor: PathBuf) {
+        self.anchor = Some(anchor);
+        self.flagged_by_visual.clear();
+    }
+
+    /// Enter the visual mode from this path while the mouse is dragged.
+    pub fn enter_from_mouse(&mut self, anchor: PathBuf) {
+        self.enter(anchor);
+        self.from_mouse = true;
+    }
+
+    /// Leave the visual mode. Flagged files stay flagged.
+    pub fn leave(&mut self) {
+        self.anchor = None;
+        self.flagged_by_visual.clear();
+        self.from_mouse = false;
+    }
+
+    /// Flag the contiguous range of visible files between the anchor and the selected file.
+    /// Files flagged by a previous update which are outside the new range are unflagged.
+    ///
+    /// `visible` are the displayed paths, in the displayed order and `selected` is the index of the selected file.
+    /// `in_tree` allows the anchor to be replaced by its closest visible ancestor.
+    /// If the anchor can't be found in the visible files, the visual mode is left.
+    pub fn update(
+        &mut self,
+        visible: &[&Path],
+        selected: usize,
+        in_tree: bool,
+        flagged: &mut Flagged,
+    ) {
+        if selected >= visible.len() {
+            return;
+        }
+        let Some(anchor_index) = self.anchor_index(visible, in_tree) else {
+            self.leave();
+            return;
+        };
+        let (start, end) = if anchor_index <= selected {
+            (anchor_index, selected)
+        } else {
+            (selected, anchor_index)
+        };
+        let range = &visible[start..=end];
+        self.flagged_by_visual.retain(|path| {
+            let keep = range.contains(&path.as_path());
+            if !keep {
+                flagged.remove(path);
+            }
+            keep
+        });
+        for path in range {
+            if !flagged.contains(path) {
+                flagged.push(path.to_path_buf());
+                self.flagged_by_visual.push(path.to_path_buf());
+            }
+        }
+    }
+
+    /// Index of the anchor in the visible paths.
+    /// In a tree, if the anchor itself isn't visible, it's the index of its deepest visible ancestor.
+    fn anchor_index(&self, visible: &[&Path], in_tree: bool) -> Option<usize> {
+        let anchor = self.anchor.as_ref()?;
+        if let Some(index) = visible.iter().position(|path| *path == anchor.as_path()) {
+            return Some(index);
+        }
+        if !in_tree {
+            return None;
+        }
+        visible
+            .iter()
+            .enumerate()
+            .filter(|(_, path)| anchor.starts_with(path))
+            .max_by_key(|(_, path)| path.components().count())
+            .map(|(index, _)| index)
+    }
+}